@@ -73,7 +73,7 @@ impl<E: Hash + Eq + Clone> BitAndAssign for ElementSet<E> {
             (Self::Some(x), Self::Some(y)) => {
                 let set_x: HashSet<E> = x.iter().cloned().collect();
                 let set_y: HashSet<E> = y.iter().cloned().collect();
-                *self = Self::Some(set_x.intersection(&set_y).into_iter().cloned().collect());
+                *self = Self::Some(set_x.intersection(&set_y).cloned().collect());
             }
         }
     }
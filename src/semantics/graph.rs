@@ -98,7 +98,7 @@ impl<T> GraphNode<T> {
     }
 }
 
-impl<'a, T: 'a> GraphNode<T> {
+impl<T> GraphNode<T> {
     /// Replace the inner value based on the old value.
     ///
     /// TODO: Test safety
@@ -138,7 +138,7 @@ impl<T> RawGraphNode<T> {
     }
 }
 
-impl<'a, T: 'a> RawGraphNode<T> {
+impl<T> RawGraphNode<T> {
     /// SAFETY: We wrap the old inner with the new inner so that there is no
     /// duplication. We use a creator callback so that we never expose a
     /// dangling reference to the internal type. The use of `Fn` ensures that
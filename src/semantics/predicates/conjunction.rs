@@ -243,7 +243,7 @@ impl<E: Hash + Eq + Clone, const WITH_ARITY: usize, const C_ARITY: usize, const
                     l &= r;
                     l
                 })
-                .chain(self.inner.get_elements_for_false(sig).into_iter())
+                .chain(self.inner.get_elements_for_false(sig))
                 .collect()
         }
     }
@@ -261,7 +261,7 @@ impl<E: Hash + Eq + Clone, const WITH_ARITY: usize, const C_ARITY: usize, const
                 .get_elements_for_true(sig)
                 .into_iter()
                 .map(|args| self.map_this.forward(&args))
-                .chain(self.inner.get_elements_for_true(sig).into_iter())
+                .chain(self.inner.get_elements_for_true(sig))
                 .collect()
         }
     }
@@ -223,7 +223,7 @@ impl<E: Hash + Eq + Clone, const WITH_ARITY: usize, const D_ARITY: usize, const
                 .get_elements_for_false(sig)
                 .into_iter()
                 .map(|args| self.map_this.forward(&args))
-                .chain(self.inner.get_elements_for_false(sig).into_iter())
+                .chain(self.inner.get_elements_for_false(sig))
                 .collect()
         }
     }
@@ -126,7 +126,7 @@ impl<E, const ARITY: usize> Predicate<E, ARITY> for IsNegated<E, ARITY> {
             self.negation
                 .get_elements_for_true(sig)
                 .into_iter()
-                .chain(self.inner.get_elements_for_false(sig).into_iter())
+                .chain(self.inner.get_elements_for_false(sig))
                 .collect()
         }
     }
@@ -143,7 +143,7 @@ impl<E, const ARITY: usize> Predicate<E, ARITY> for IsNegated<E, ARITY> {
             self.negation
                 .get_elements_for_false(sig)
                 .into_iter()
-                .chain(self.inner.get_elements_for_true(sig).into_iter())
+                .chain(self.inner.get_elements_for_true(sig))
                 .collect()
         }
     }
@@ -47,7 +47,7 @@ impl<E: Eq + Clone, const ARITY: usize> Predicate<E, ARITY> for TrueForArguments
                     ElementQuantifier::Any => ElementSet::All,
                 })
             })
-            .chain(self.inner.get_elements_for_true(sig).into_iter())
+            .chain(self.inner.get_elements_for_true(sig))
             .collect()
     }
 }
@@ -0,0 +1,372 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use super::{Equality, GenericTerm};
+
+/// Why two terms were merged.
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    /// An asserted equation, by index.
+    Input(usize),
+    /// Two function calls whose arguments are pairwise equal.
+    Congruence(usize, usize),
+}
+
+/// A disequation which the asserted equations contradict, together with the
+/// equations which show that its sides are equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The violated disequation.
+    pub disequation: Equality<GenericTerm, GenericTerm>,
+    /// The equations which together imply the sides of the disequation are
+    /// equal.
+    pub equations: Vec<Equality<GenericTerm, GenericTerm>>,
+}
+
+/// A congruence-closure decision procedure for ground equations.
+///
+/// Terms are merged into equivalence classes as equations are asserted, and
+/// the classes are kept closed under congruence: if `s₁ = t₁, …, sₙ = tₙ`
+/// then `f(s₁, …, sₙ) = f(t₁, …, tₙ)`. Each merge is recorded in a proof
+/// forest, so that an equality can be explained by the equations it follows
+/// from.
+///
+/// Variables are treated as uninterpreted constants.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   congruence::CongruenceClosure, Constant, Equality, FunctionCall, GenericTerm,
+/// # };
+/// let a: GenericTerm = Constant { label: b'a'.into() }.into();
+/// let f = |t| -> GenericTerm { FunctionCall { function: b'f'.into(), terms: vec![t] }.into() };
+///
+/// // f(f(f(a))) = a and f(f(f(f(f(a))))) = a imply f(a) = a.
+/// let mut closure = CongruenceClosure::new();
+/// closure.assert_equal(Equality { left: f(f(f(a.clone()))), right: a.clone() });
+/// closure.assert_equal(Equality { left: f(f(f(f(f(a.clone()))))), right: a.clone() });
+///
+/// assert!(closure.are_equal(&f(a.clone()), &a));
+/// assert_eq!(closure.explain(&f(a.clone()), &a).unwrap().len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CongruenceClosure {
+    ids: HashMap<GenericTerm, usize>,
+    terms: Vec<GenericTerm>,
+    /// The arguments of each function call node.
+    arguments: Vec<Vec<usize>>,
+    /// The representative of each node's class.
+    representative: Vec<usize>,
+    /// The members of each class, stored on its representative.
+    members: Vec<Vec<usize>>,
+    /// The function calls with an argument in each class, stored on its
+    /// representative.
+    uses: Vec<Vec<usize>>,
+    /// Function calls, by their function and the classes of their arguments.
+    signatures: HashMap<(u64, Vec<usize>), usize>,
+    /// The proof forest: an edge to another node in the same class, and the
+    /// reason they were merged.
+    proof: Vec<Option<(usize, Reason)>>,
+    equations: Vec<Equality<GenericTerm, GenericTerm>>,
+    disequations: Vec<(usize, usize, Equality<GenericTerm, GenericTerm>)>,
+    pending: Vec<(usize, usize, Reason)>,
+}
+
+impl CongruenceClosure {
+    /// Create an empty congruence closure, in which every term is only equal
+    /// to itself.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assert that the two sides of an equation are equal.
+    pub fn assert_equal(&mut self, equation: Equality<GenericTerm, GenericTerm>) {
+        let left = self.add_term(&equation.left);
+        let right = self.add_term(&equation.right);
+        self.pending
+            .push((left, right, Reason::Input(self.equations.len())));
+        self.equations.push(equation);
+        self.propagate();
+    }
+
+    /// Assert that the two sides of an equation are distinct.
+    pub fn assert_distinct(&mut self, disequation: Equality<GenericTerm, GenericTerm>) {
+        let left = self.add_term(&disequation.left);
+        let right = self.add_term(&disequation.right);
+        self.disequations.push((left, right, disequation));
+        self.propagate();
+    }
+
+    /// Whether two terms are equal in every model of the asserted equations.
+    pub fn are_equal(&mut self, left: &GenericTerm, right: &GenericTerm) -> bool {
+        let left = self.add_term(left);
+        let right = self.add_term(right);
+        self.propagate();
+        self.representative[left] == self.representative[right]
+    }
+
+    /// The asserted equations from which the equality of two terms follows,
+    /// or `None` if it does not follow.
+    ///
+    /// Disequations are not taken into account: if there is a
+    /// [conflict](CongruenceClosure::conflict), every equation follows.
+    pub fn explain(
+        &mut self,
+        left: &GenericTerm,
+        right: &GenericTerm,
+    ) -> Option<Vec<Equality<GenericTerm, GenericTerm>>> {
+        if !self.are_equal(left, right) {
+            return None;
+        }
+        let (left, right) = (self.ids[left], self.ids[right]);
+        Some(self.explain_nodes(left, right))
+    }
+
+    /// A disequation contradicted by the asserted equations, if there is one.
+    pub fn conflict(&self) -> Option<Conflict> {
+        self.disequations
+            .iter()
+            .find(|(l, r, _)| self.representative[*l] == self.representative[*r])
+            .map(|(l, r, disequation)| Conflict {
+                disequation: disequation.clone(),
+                equations: self.explain_nodes(*l, *r),
+            })
+    }
+
+    /// Whether the asserted equations and disequations are satisfiable.
+    pub fn is_consistent(&self) -> bool {
+        self.conflict().is_none()
+    }
+
+    fn add_term(&mut self, term: &GenericTerm) -> usize {
+        if let Some(id) = self.ids.get(term) {
+            return *id;
+        }
+        let arguments: Vec<usize> = match term {
+            GenericTerm::FunctionCall(f) => f.terms.iter().map(|t| self.add_term(t)).collect(),
+            GenericTerm::Variable(_) | GenericTerm::Constant(_) => vec![],
+        };
+
+        let id = self.terms.len();
+        self.ids.insert(term.clone(), id);
+        self.terms.push(term.clone());
+        self.representative.push(id);
+        self.members.push(vec![id]);
+        self.uses.push(vec![]);
+        self.proof.push(None);
+        for argument in &arguments {
+            let class = self.representative[*argument];
+            if !self.uses[class].contains(&id) {
+                self.uses[class].push(id);
+            }
+        }
+        self.arguments.push(arguments);
+
+        if let Some(signature) = self.signature(id) {
+            match self.signatures.get(&signature) {
+                Some(other) => self
+                    .pending
+                    .push((id, *other, Reason::Congruence(id, *other))),
+                None => {
+                    self.signatures.insert(signature, id);
+                }
+            }
+        }
+        id
+    }
+
+    /// The function and argument classes of a function call node.
+    fn signature(&self, id: usize) -> Option<(u64, Vec<usize>)> {
+        match &self.terms[id] {
+            GenericTerm::FunctionCall(f) => Some((
+                f.function,
+                self.arguments[id]
+                    .iter()
+                    .map(|a| self.representative[*a])
+                    .collect(),
+            )),
+            GenericTerm::Variable(_) | GenericTerm::Constant(_) => None,
+        }
+    }
+
+    fn propagate(&mut self) {
+        while let Some((left, right, reason)) = self.pending.pop() {
+            let (l, r) = (self.representative[left], self.representative[right]);
+            if l == r {
+                continue;
+            }
+
+            // Record the merge in the proof forest, rerooting the tree
+            // containing `left` so that it can point at `right`.
+            let mut previous = None;
+            let mut current = left;
+            while let Some((next, edge)) = std::mem::replace(&mut self.proof[current], previous) {
+                previous = Some((current, edge));
+                current = next;
+            }
+            self.proof[left] = Some((right, reason));
+
+            // Merge the smaller class into the larger.
+            let (small, large) = if self.members[l].len() < self.members[r].len() {
+                (l, r)
+            } else {
+                (r, l)
+            };
+            let uses = std::mem::take(&mut self.uses[small]);
+            for u in &uses {
+                if let Some(signature) = self.signature(*u) {
+                    if self.signatures.get(&signature) == Some(u) {
+                        self.signatures.remove(&signature);
+                    }
+                }
+            }
+            let members = std::mem::take(&mut self.members[small]);
+            for m in &members {
+                self.representative[*m] = large;
+            }
+            self.members[large].extend(members);
+
+            for u in uses {
+                if let Some(signature) = self.signature(u) {
+                    match self.signatures.get(&signature) {
+                        Some(other) if *other != u => {
+                            self.pending
+                                .push((u, *other, Reason::Congruence(u, *other)))
+                        }
+                        Some(_) => {}
+                        None => {
+                            self.signatures.insert(signature, u);
+                        }
+                    }
+                }
+                if !self.uses[large].contains(&u) {
+                    self.uses[large].push(u);
+                }
+            }
+        }
+    }
+
+    /// The path from a node to the root of its proof tree.
+    fn proof_path(&self, mut node: usize) -> Vec<usize> {
+        let mut path = vec![node];
+        while let Some((next, _)) = self.proof[node] {
+            path.push(next);
+            node = next;
+        }
+        path
+    }
+
+    fn explain_nodes(&self, left: usize, right: usize) -> Vec<Equality<GenericTerm, GenericTerm>> {
+        let mut used = BTreeSet::new();
+        let mut seen = HashSet::new();
+        let mut stack = vec![(left, right)];
+
+        while let Some((left, right)) = stack.pop() {
+            if left == right || !seen.insert((left, right)) {
+                continue;
+            }
+            let left_path = self.proof_path(left);
+            let right_path: HashSet<usize> = self.proof_path(right).into_iter().collect();
+            let ancestor = *left_path.iter().find(|n| right_path.contains(n)).unwrap();
+
+            for mut node in [left, right] {
+                while node != ancestor {
+                    let (next, reason) = self.proof[node].unwrap();
+                    match reason {
+                        Reason::Input(i) => {
+                            used.insert(i);
+                        }
+                        Reason::Congruence(u, v) => stack.extend(
+                            self.arguments[u]
+                                .iter()
+                                .copied()
+                                .zip(self.arguments[v].iter().copied()),
+                        ),
+                    }
+                    node = next;
+                }
+            }
+        }
+
+        used.into_iter()
+            .map(|i| self.equations[i].clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{Constant, Equality, FunctionCall, GenericTerm};
+
+    use super::CongruenceClosure;
+
+    fn c(label: u8) -> GenericTerm {
+        Constant {
+            label: label.into(),
+        }
+        .into()
+    }
+
+    fn f(terms: Vec<GenericTerm>) -> GenericTerm {
+        FunctionCall {
+            function: b'f'.into(),
+            terms,
+        }
+        .into()
+    }
+
+    fn eq(left: GenericTerm, right: GenericTerm) -> Equality<GenericTerm, GenericTerm> {
+        Equality { left, right }
+    }
+
+    #[test]
+    fn test_congruence() {
+        // a = b, b = c ⊢ f(a, b) = f(c, a), but not f(a) = a.
+        let mut closure = CongruenceClosure::new();
+        closure.assert_equal(eq(c(b'a'), c(b'b')));
+        closure.assert_equal(eq(c(b'b'), c(b'c')));
+        closure.assert_equal(eq(c(b'd'), c(b'e')));
+
+        let left = f(vec![c(b'a'), c(b'b')]);
+        let right = f(vec![c(b'c'), c(b'a')]);
+        assert!(closure.are_equal(&left, &right));
+        assert_eq!(
+            closure.explain(&left, &right),
+            Some(vec![eq(c(b'a'), c(b'b')), eq(c(b'b'), c(b'c'))])
+        );
+        assert!(!closure.are_equal(&f(vec![c(b'a')]), &c(b'a')));
+        assert_eq!(closure.explain(&c(b'a'), &c(b'd')), None);
+    }
+
+    #[test]
+    fn test_terms_added_after_merge() {
+        let mut closure = CongruenceClosure::new();
+        closure.assert_equal(eq(c(b'a'), c(b'b')));
+        closure.assert_equal(eq(f(vec![c(b'a')]), c(b'c')));
+
+        // f(b) is only added now, but is congruent to f(a).
+        assert!(closure.are_equal(&f(vec![c(b'b')]), &c(b'c')));
+        assert_eq!(
+            closure.explain(&f(vec![c(b'b')]), &c(b'c')).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_conflict() {
+        let mut closure = CongruenceClosure::new();
+        closure.assert_distinct(eq(f(vec![c(b'a')]), f(vec![c(b'b')])));
+        assert!(closure.is_consistent());
+
+        closure.assert_equal(eq(c(b'a'), c(b'c')));
+        closure.assert_equal(eq(c(b'c'), c(b'b')));
+        closure.assert_equal(eq(c(b'd'), c(b'e')));
+
+        let conflict = closure.conflict().unwrap();
+        assert_eq!(conflict.disequation, eq(f(vec![c(b'a')]), f(vec![c(b'b')])));
+        assert_eq!(
+            conflict.equations,
+            vec![eq(c(b'a'), c(b'c')), eq(c(b'c'), c(b'b'))]
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::syntax::{unification::Substitution, Constant, GenericTerm, PredicateCall, Variable};
+
+use super::DatalogError;
+
+/// A row of a relation.
+pub type Tuple = Vec<Constant>;
+
+////////////////////////////////////////////////////////////////////////////////
+// Relations
+////////////////////////////////////////////////////////////////////////////////
+
+/// A set of tuples, indexed on each of their columns.
+#[derive(Debug, Clone, Default)]
+struct Relation {
+    tuples: Vec<Tuple>,
+    members: HashSet<Tuple>,
+    /// For each column, the positions in `tuples` of the tuples with each
+    /// value in that column.
+    indexes: Vec<HashMap<Constant, Vec<usize>>>,
+}
+
+impl Relation {
+    fn insert(&mut self, tuple: Tuple) -> bool {
+        if self.members.contains(&tuple) {
+            return false;
+        }
+        if self.indexes.len() < tuple.len() {
+            self.indexes.resize_with(tuple.len(), HashMap::new);
+        }
+        for (column, value) in tuple.iter().enumerate() {
+            self.indexes[column]
+                .entry(*value)
+                .or_default()
+                .push(self.tuples.len());
+        }
+        self.members.insert(tuple.clone());
+        self.tuples.push(tuple);
+        true
+    }
+
+    /// The tuples which may match a pattern, found through the index of its
+    /// most selective bound column.
+    fn candidates<'a>(
+        &'a self,
+        pattern: &[Option<Constant>],
+    ) -> Box<dyn Iterator<Item = &'a Tuple> + 'a> {
+        let positions = pattern
+            .iter()
+            .enumerate()
+            .filter_map(|(column, value)| {
+                let value = value.as_ref()?;
+                Some(
+                    self.indexes
+                        .get(column)
+                        .and_then(|index| index.get(value))
+                        .map_or(&[][..], |p| p.as_slice()),
+                )
+            })
+            .min_by_key(|positions| positions.len());
+        match positions {
+            Some(positions) => Box::new(positions.iter().map(|i| &self.tuples[*i])),
+            None => Box::new(self.tuples.iter()),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Patterns
+////////////////////////////////////////////////////////////////////////////////
+
+/// An argument of a function-free atom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Argument {
+    Variable(Variable),
+    Constant(Constant),
+}
+
+/// A function-free atom, to be matched against the tuples of a relation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Pattern {
+    pub predicate: u64,
+    pub arguments: Vec<Argument>,
+}
+
+impl Pattern {
+    pub fn variables(&self) -> impl Iterator<Item = Variable> + '_ {
+        self.arguments.iter().filter_map(|a| match a {
+            Argument::Variable(v) => Some(*v),
+            Argument::Constant(_) => None,
+        })
+    }
+
+    /// The tuple of the pattern under bindings for all of its variables.
+    pub fn instantiate(&self, bindings: &Bindings) -> Tuple {
+        self.arguments
+            .iter()
+            .map(|a| match a {
+                Argument::Variable(v) => bindings[v],
+                Argument::Constant(c) => *c,
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&PredicateCall<GenericTerm>> for Pattern {
+    type Error = DatalogError;
+
+    fn try_from(atom: &PredicateCall<GenericTerm>) -> Result<Self, Self::Error> {
+        let arguments = atom
+            .terms
+            .iter()
+            .map(|t| match t {
+                GenericTerm::Variable(v) => Ok(Argument::Variable(*v)),
+                GenericTerm::Constant(c) => Ok(Argument::Constant(*c)),
+                GenericTerm::FunctionCall(_) => Err(DatalogError::NotFunctionFree(t.clone())),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            predicate: atom.predicate,
+            arguments,
+        })
+    }
+}
+
+pub(super) type Bindings = HashMap<Variable, Constant>;
+
+////////////////////////////////////////////////////////////////////////////////
+// Database
+////////////////////////////////////////////////////////////////////////////////
+
+/// A set of ground facts, stored as a relation for each predicate.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   datalog::Database, Constant, GenericTerm, PredicateCall, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let (a, b) = (Constant { label: 1 }, Constant { label: 2 });
+///
+/// let mut database = Database::new();
+/// database.insert(b'E'.into(), vec![a, b]);
+/// database.insert(b'E'.into(), vec![b, b]);
+///
+/// // E(x, x)
+/// let goal = PredicateCall { predicate: b'E'.into(), terms: vec![x.into(), x.into()] };
+/// let answers = database.query(&[goal]).unwrap();
+/// assert_eq!(answers.len(), 1);
+/// assert_eq!(answers[0].get(&x), Some(&GenericTerm::from(b)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Database {
+    relations: HashMap<u64, Relation>,
+    len: usize,
+}
+
+impl Database {
+    /// Create an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a fact, returning whether it is new.
+    pub fn insert(&mut self, predicate: u64, tuple: Tuple) -> bool {
+        let new = self.relations.entry(predicate).or_default().insert(tuple);
+        self.len += new as usize;
+        new
+    }
+
+    /// Add a ground atom as a fact, returning whether it is new.
+    pub fn insert_atom(&mut self, atom: &PredicateCall<GenericTerm>) -> Result<bool, DatalogError> {
+        let tuple = atom
+            .terms
+            .iter()
+            .map(|t| match t {
+                GenericTerm::Constant(c) => Ok(*c),
+                _ => Err(DatalogError::NotGround(atom.clone())),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(self.insert(atom.predicate, tuple))
+    }
+
+    /// Whether a fact is in the database.
+    pub fn contains(&self, predicate: u64, tuple: &[Constant]) -> bool {
+        self.relations
+            .get(&predicate)
+            .is_some_and(|r| r.members.contains(tuple))
+    }
+
+    /// The tuples of the facts with a predicate, in the order they were
+    /// added.
+    pub fn facts(&self, predicate: u64) -> impl Iterator<Item = &Tuple> {
+        self.relations
+            .get(&predicate)
+            .into_iter()
+            .flat_map(|r| r.tuples.iter())
+    }
+
+    /// The predicates with at least one fact.
+    pub fn predicates(&self) -> impl Iterator<Item = u64> + '_ {
+        self.relations.keys().copied()
+    }
+
+    /// The number of facts.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no facts.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Find every binding of the variables of a conjunction of function-free
+    /// atoms to constants which makes each of them a fact.
+    pub fn query(
+        &self,
+        goals: &[PredicateCall<GenericTerm>],
+    ) -> Result<Vec<Substitution>, DatalogError> {
+        let patterns = goals
+            .iter()
+            .map(Pattern::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let sources: Vec<_> = patterns.iter().map(|p| (p, self)).collect();
+        let mut answers = Vec::new();
+        join(&sources, &mut Bindings::new(), &mut |bindings| {
+            answers.push(bindings.iter().map(|(v, c)| (*v, (*c).into())).collect())
+        });
+        Ok(answers)
+    }
+
+    fn candidates<'a>(
+        &'a self,
+        pattern: &Pattern,
+        bindings: &Bindings,
+    ) -> Box<dyn Iterator<Item = &'a Tuple> + 'a> {
+        let Some(relation) = self.relations.get(&pattern.predicate) else {
+            return Box::new(std::iter::empty());
+        };
+        let bound: Vec<Option<Constant>> = pattern
+            .arguments
+            .iter()
+            .map(|a| match a {
+                Argument::Variable(v) => bindings.get(v).copied(),
+                Argument::Constant(c) => Some(*c),
+            })
+            .collect();
+        relation.candidates(&bound)
+    }
+}
+
+/// Call `found` with each extension of `bindings` under which every pattern
+/// matches a fact of the database it is paired with.
+pub(super) fn join(
+    patterns: &[(&Pattern, &Database)],
+    bindings: &mut Bindings,
+    found: &mut dyn FnMut(&Bindings),
+) {
+    let Some(((pattern, database), rest)) = patterns.split_first() else {
+        found(bindings);
+        return;
+    };
+    for tuple in database.candidates(pattern, bindings) {
+        if tuple.len() != pattern.arguments.len() {
+            continue;
+        }
+        let mut bound = Vec::new();
+        let matches = pattern
+            .arguments
+            .iter()
+            .zip(tuple)
+            .all(|(a, value)| match a {
+                Argument::Constant(c) => c == value,
+                Argument::Variable(v) => match bindings.get(v) {
+                    Some(c) => c == value,
+                    None => {
+                        bindings.insert(*v, *value);
+                        bound.push(*v);
+                        true
+                    }
+                },
+            });
+        if matches {
+            join(rest, bindings, found);
+        }
+        for v in bound {
+            bindings.remove(&v);
+        }
+    }
+}
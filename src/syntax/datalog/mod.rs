@@ -0,0 +1,510 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{
+    normal_forms::{ClauseNormalForm, Literal},
+    GenericAtomicFormula, GenericFormula, GenericTerm, PredicateCall, Variable,
+};
+
+mod database;
+mod stratification;
+
+use database::{join, Bindings, Pattern};
+pub use database::{Database, Tuple};
+pub use stratification::DependencyGraph;
+
+////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////
+
+/// The reasons a set of rules or facts is not a Datalog program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DatalogError {
+    /// The clause at this index is not a definite clause.
+    NotDefinite(usize),
+    /// An atom is an equality or a truth value, rather than a predicate call.
+    NotPredicate(GenericAtomicFormula),
+    /// A term has a function symbol.
+    NotFunctionFree(GenericTerm),
+    /// A fact has a variable in it.
+    NotGround(PredicateCall<GenericTerm>),
+    /// A variable of the head or of a negated literal of a rule does not
+    /// occur in a positive literal of its body.
+    Unsafe(Variable),
+    /// The predicates on this cycle depend on each other, at least once
+    /// negatively, so the rules cannot be stratified.
+    NegativeCycle(Vec<u64>),
+}
+
+impl Display for DatalogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotDefinite(x) => f.write_fmt(format_args!("clause {} is not definite", x)),
+            Self::NotPredicate(x) => {
+                f.write_fmt(format_args!("atom {} is not a predicate call", x))
+            }
+            Self::NotFunctionFree(x) => {
+                f.write_fmt(format_args!("term {} has a function symbol", x))
+            }
+            Self::NotGround(x) => f.write_fmt(format_args!("fact {} is not ground", x)),
+            Self::Unsafe(x) => f.write_fmt(format_args!(
+                "variable {} does not occur in a positive literal of its rule",
+                x
+            )),
+            Self::NegativeCycle(x) => {
+                f.write_str("the rules cannot be stratified, as there is a negative cycle ")?;
+                let mut first = true;
+                for predicate in x {
+                    if !first {
+                        f.write_str(" → ")?;
+                    }
+                    first = false;
+                    match u8::try_from(*predicate) {
+                        Ok(c) => f.write_fmt(format_args!("{}", c as char))?,
+                        Err(_) => f.write_fmt(format_args!("{}", predicate))?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DatalogError {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Rules
+////////////////////////////////////////////////////////////////////////////////
+
+/// A rule `head :- body`, stating that the head holds whenever every
+/// literal of the body does. A rule with an empty body is a fact.
+///
+/// A negated literal of the body holds when its atom is not in the model
+/// being computed, which is well-defined when the program is stratified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// The atom the rule derives.
+    pub head: PredicateCall<GenericTerm>,
+    /// The conditions of the rule.
+    pub body: Vec<Literal>,
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.head.fmt(f)?;
+        let mut first = true;
+        for literal in &self.body {
+            f.write_str(if first { " :- " } else { ", " })?;
+            first = false;
+            GenericFormula::from(literal.clone()).fmt(f)?;
+        }
+        f.write_str(".")
+    }
+}
+
+/// A rule with its atoms checked to be function-free.
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    head: Pattern,
+    positive: Vec<Pattern>,
+    negative: Vec<Pattern>,
+}
+
+fn predicate_call(
+    atom: &GenericAtomicFormula,
+) -> Result<&PredicateCall<GenericTerm>, DatalogError> {
+    match atom {
+        GenericAtomicFormula::Predicate(p) => Ok(p),
+        _ => Err(DatalogError::NotPredicate(atom.clone())),
+    }
+}
+
+impl CompiledRule {
+    fn new(rule: &Rule) -> Result<Self, DatalogError> {
+        let head = Pattern::try_from(&rule.head)?;
+        let (mut positive, mut negative) = (Vec::new(), Vec::new());
+        for literal in &rule.body {
+            let pattern = Pattern::try_from(predicate_call(literal.atom())?)?;
+            match literal {
+                Literal::Atom(_) => positive.push(pattern),
+                Literal::Negated(_) => negative.push(pattern),
+            }
+        }
+        if let Some(v) = std::iter::once(&head)
+            .chain(&negative)
+            .flat_map(Pattern::variables)
+            .find(|v| !positive.iter().any(|p| p.variables().any(|w| w == *v)))
+        {
+            return Err(DatalogError::Unsafe(v));
+        }
+        Ok(Self {
+            head,
+            positive,
+            negative,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Programs
+////////////////////////////////////////////////////////////////////////////////
+
+/// A Datalog program: a set of function-free rules, whose bodies may have
+/// negated literals if the rules can be stratified.
+///
+/// [`evaluate`](Program::evaluate) computes the least model of the program
+/// and a database of facts bottom-up, one stratum of its
+/// [`DependencyGraph`] at a time, so that the predicates of negated literals
+/// are complete before they are used. Each stratum is evaluated
+/// semi-naively: after the first round, a rule is only applied to matches
+/// which use at least one fact derived in the previous round, rather than
+/// rederiving what follows from the older facts alone.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   datalog::{Database, Program, Rule}, normal_forms::Literal, Constant, GenericTerm,
+/// #   PredicateCall, Variable,
+/// # };
+/// let [x, y, z] = [b'x', b'y', b'z'].map(|v| GenericTerm::from(Variable::new(v.into())));
+/// let atom = |p: u8, terms: Vec<GenericTerm>| PredicateCall { predicate: p.into(), terms };
+///
+/// // P(x, y) :- E(x, y).  P(x, z) :- E(x, y), P(y, z).
+/// let program = Program::new(vec![
+///     Rule {
+///         head: atom(b'P', vec![x.clone(), y.clone()]),
+///         body: vec![Literal::Atom(atom(b'E', vec![x.clone(), y.clone()]).into())],
+///     },
+///     Rule {
+///         head: atom(b'P', vec![x.clone(), z.clone()]),
+///         body: vec![
+///             Literal::Atom(atom(b'E', vec![x.clone(), y.clone()]).into()),
+///             Literal::Atom(atom(b'P', vec![y.clone(), z.clone()]).into()),
+///         ],
+///     },
+/// ])
+/// .unwrap();
+///
+/// // A chain 1 → 2 → 3 → 4 has six paths.
+/// let mut edges = Database::new();
+/// for i in 1..4 {
+///     edges.insert(b'E'.into(), vec![Constant { label: i }, Constant { label: i + 1 }]);
+/// }
+/// let model = program.evaluate(&edges);
+/// assert_eq!(model.facts(b'P'.into()).count(), 6);
+///
+/// // The nodes reachable from 2.
+/// let goal = atom(b'P', vec![Constant { label: 2 }.into(), x.clone()]);
+/// assert_eq!(model.query(&[goal]).unwrap().len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Program {
+    rules: Vec<Rule>,
+    /// The compiled rules of each stratum, in order.
+    strata: Vec<Vec<CompiledRule>>,
+}
+
+impl Program {
+    /// Check that a set of rules is a Datalog program: their atoms are
+    /// function-free predicate calls, every variable of a rule occurs in a
+    /// positive literal of its body, and the rules can be stratified.
+    pub fn new(rules: Vec<Rule>) -> Result<Self, DatalogError> {
+        let compiled = rules
+            .iter()
+            .map(CompiledRule::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let stratum_of: HashMap<u64, usize> = DependencyGraph::new(&rules)
+            .stratify()?
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, predicates)| predicates.into_iter().map(move |p| (p, i)))
+            .collect();
+        let mut strata = vec![Vec::new(); stratum_of.values().max().map_or(0, |s| s + 1)];
+        for rule in compiled {
+            strata[stratum_of[&rule.head.predicate]].push(rule);
+        }
+        Ok(Self { rules, strata })
+    }
+
+    /// Read a program from clauses with exactly one positive literal each,
+    /// which becomes the head of a rule with the atoms of the negative
+    /// literals as its body.
+    pub fn from_clauses(clauses: &ClauseNormalForm) -> Result<Self, DatalogError> {
+        let rules = clauses
+            .iter()
+            .enumerate()
+            .map(|(i, clause)| {
+                let mut heads = clause.iter().filter(|l| !l.is_negated());
+                let head = match (heads.next(), heads.next()) {
+                    (Some(head), None) => predicate_call(head.atom())?.clone(),
+                    _ => return Err(DatalogError::NotDefinite(i)),
+                };
+                let body = clause
+                    .iter()
+                    .filter(|l| l.is_negated())
+                    .map(Literal::negate)
+                    .collect();
+                Ok(Rule { head, body })
+            })
+            .collect::<Result<_, _>>()?;
+        Self::new(rules)
+    }
+
+    /// The rules of the program.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Compute the least model of the program which contains a database of
+    /// facts. With negated literals, this is the perfect model: the least
+    /// model of each stratum in turn.
+    pub fn evaluate(&self, facts: &Database) -> Database {
+        let mut model = facts.clone();
+        for stratum in &self.strata {
+            evaluate_stratum(stratum, &mut model);
+        }
+        model
+    }
+}
+
+/// Extend a model to the least fixed point of the rules of a stratum, whose
+/// negated literals only use predicates of lower strata.
+fn evaluate_stratum(rules: &[CompiledRule], model: &mut Database) {
+    let mut delta = Database::new();
+    for rule in rules {
+        let sources: Vec<_> = rule.positive.iter().map(|p| (p, &*model)).collect();
+        derive(rule, &sources, model, &mut delta);
+    }
+    extend(model, &delta);
+
+    while !delta.is_empty() {
+        let mut next = Database::new();
+        for rule in rules {
+            for (i, pattern) in rule.positive.iter().enumerate() {
+                if delta.facts(pattern.predicate).next().is_none() {
+                    continue;
+                }
+                // Match the new facts first, as there are usually fewer of
+                // them.
+                let sources: Vec<_> = std::iter::once((pattern, &delta))
+                    .chain(
+                        rule.positive
+                            .iter()
+                            .enumerate()
+                            .filter(|(j, _)| *j != i)
+                            .map(|(_, p)| (p, &*model)),
+                    )
+                    .collect();
+                derive(rule, &sources, model, &mut next);
+            }
+        }
+        extend(model, &next);
+        delta = next;
+    }
+}
+
+/// Add the heads of a rule for each match of its positive literals against
+/// their sources which satisfies its negated literals, if they are not
+/// already in the model.
+fn derive(
+    rule: &CompiledRule,
+    sources: &[(&Pattern, &Database)],
+    model: &Database,
+    derived: &mut Database,
+) {
+    join(sources, &mut Bindings::new(), &mut |bindings| {
+        let blocked = rule
+            .negative
+            .iter()
+            .any(|p| model.contains(p.predicate, &p.instantiate(bindings)));
+        let tuple = rule.head.instantiate(bindings);
+        if !blocked && !model.contains(rule.head.predicate, &tuple) {
+            derived.insert(rule.head.predicate, tuple);
+        }
+    });
+}
+
+fn extend(model: &mut Database, facts: &Database) {
+    for predicate in facts.predicates() {
+        for tuple in facts.facts(predicate) {
+            model.insert(predicate, tuple.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::Literal, Constant, GenericTerm, Negation, PredicateCall, Variable,
+    };
+
+    use super::{Database, DatalogError, Program, Rule};
+
+    fn atom(predicate: u8, terms: Vec<GenericTerm>) -> PredicateCall<GenericTerm> {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+    }
+
+    fn positive(predicate: u8, terms: Vec<GenericTerm>) -> Literal {
+        Literal::Atom(atom(predicate, terms).into())
+    }
+
+    #[test]
+    fn test_same_generation() {
+        let [x, y, u, v] =
+            [b'x', b'y', b'u', b'v'].map(|l| GenericTerm::from(Variable::new(l.into())));
+        let c = |label: u64| GenericTerm::from(Constant { label });
+
+        // The program, as clauses:
+        //   S(x, x) :- N(x).
+        //   S(x, y) :- C(x, u), S(u, v), C(y, v).
+        // with the nodes N(1), ..., N(7) of a binary tree rooted at 1, and the
+        // child relation C.
+        let mut clauses = vec![
+            vec![
+                positive(b'S', vec![x.clone(), x.clone()]),
+                positive(b'N', vec![x.clone()]).negate(),
+            ],
+            vec![
+                positive(b'S', vec![x.clone(), y.clone()]),
+                positive(b'C', vec![x.clone(), u.clone()]).negate(),
+                positive(b'S', vec![u.clone(), v.clone()]).negate(),
+                positive(b'C', vec![y.clone(), v.clone()]).negate(),
+            ],
+        ];
+        for i in 1..=7 {
+            clauses.push(vec![positive(b'N', vec![c(i)])]);
+        }
+        for i in 2..=7 {
+            clauses.push(vec![positive(b'C', vec![c(i), c(i / 2)])]);
+        }
+        let program = Program::from_clauses(&clauses).unwrap();
+        let model = program.evaluate(&Database::new());
+
+        // Nodes of the same depth: 1, then 2 and 3, then 4 to 7.
+        assert_eq!(model.facts(b'S'.into()).count(), 1 + 4 + 16);
+        let answers = model
+            .query(&[
+                atom(b'S', vec![c(4), x.clone()]),
+                atom(b'C', vec![x.clone(), c(3)]),
+            ])
+            .unwrap();
+        let mut cousins: Vec<_> = answers
+            .iter()
+            .map(|s| s.get(&Variable::new(b'x'.into())).cloned())
+            .collect();
+        cousins.sort_by_key(|t| t.as_ref().map(|t| t.to_string()));
+        assert_eq!(cousins, vec![Some(c(6)), Some(c(7))]);
+    }
+
+    #[test]
+    fn test_stratified_negation() {
+        let [u, r, v] = [b'u', b'r', b'v'].map(|l| GenericTerm::from(Variable::new(l.into())));
+        let c = |label: u64| GenericTerm::from(Constant { label });
+
+        // A(u, r) :- G(u, r), ¬X(u).
+        // X(u) :- R(u).
+        // X(u) :- M(u, v), X(v).
+        // D(u, r) :- G(u, r), ¬A(u, r).
+        // Access is granted by G and allowed by A, unless the user is
+        // excluded by X: revoked by R, or managed by M by an excluded user.
+        let program = Program::new(vec![
+            Rule {
+                head: atom(b'A', vec![u.clone(), r.clone()]),
+                body: vec![
+                    positive(b'G', vec![u.clone(), r.clone()]),
+                    positive(b'X', vec![u.clone()]).negate(),
+                ],
+            },
+            Rule {
+                head: atom(b'X', vec![u.clone()]),
+                body: vec![positive(b'R', vec![u.clone()])],
+            },
+            Rule {
+                head: atom(b'X', vec![u.clone()]),
+                body: vec![
+                    positive(b'M', vec![u.clone(), v.clone()]),
+                    positive(b'X', vec![v.clone()]),
+                ],
+            },
+            Rule {
+                head: atom(b'D', vec![u.clone(), r.clone()]),
+                body: vec![
+                    positive(b'G', vec![u.clone(), r.clone()]),
+                    positive(b'A', vec![u.clone(), r.clone()]).negate(),
+                ],
+            },
+        ])
+        .unwrap();
+
+        // Users 1 to 4 are granted resource 10. User 2 is revoked, and
+        // manages user 3.
+        let mut facts = Database::new();
+        for user in 1..=4 {
+            facts
+                .insert_atom(&atom(b'G', vec![c(user), c(10)]))
+                .unwrap();
+        }
+        facts.insert_atom(&atom(b'R', vec![c(2)])).unwrap();
+        facts.insert_atom(&atom(b'M', vec![c(3), c(2)])).unwrap();
+        let model = program.evaluate(&facts);
+
+        let allowed: Vec<_> = model.facts(b'A'.into()).map(|t| t[0].label).collect();
+        let denied: Vec<_> = model.facts(b'D'.into()).map(|t| t[0].label).collect();
+        assert_eq!(allowed, vec![1, 4]);
+        assert_eq!(denied, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let x = GenericTerm::from(Variable::new(b'x'.into()));
+        let y = GenericTerm::from(Variable::new(b'y'.into()));
+        let unsafe_rule = Rule {
+            head: atom(b'P', vec![x.clone(), y.clone()]),
+            body: vec![positive(b'Q', vec![x.clone()])],
+        };
+        assert_eq!(
+            Program::new(vec![unsafe_rule]).unwrap_err(),
+            DatalogError::Unsafe(Variable::new(b'y'.into()))
+        );
+
+        // P(x) :- Q(x), ¬R(x, y).
+        let unsafe_negation = Rule {
+            head: atom(b'P', vec![x.clone()]),
+            body: vec![
+                positive(b'Q', vec![x.clone()]),
+                Literal::Negated(Negation {
+                    right: atom(b'R', vec![x.clone(), y.clone()]).into(),
+                }),
+            ],
+        };
+        assert_eq!(
+            Program::new(vec![unsafe_negation]).unwrap_err(),
+            DatalogError::Unsafe(Variable::new(b'y'.into()))
+        );
+
+        // P(x) :- Q(x), ¬P(x).
+        let paradox = Rule {
+            head: atom(b'P', vec![x.clone()]),
+            body: vec![
+                positive(b'Q', vec![x.clone()]),
+                positive(b'P', vec![x.clone()]).negate(),
+            ],
+        };
+        assert_eq!(
+            Program::new(vec![paradox]).unwrap_err(),
+            DatalogError::NegativeCycle(vec![b'P'.into(), b'P'.into()])
+        );
+
+        let goal = vec![positive(b'P', vec![x.clone()]).negate()];
+        assert_eq!(
+            Program::from_clauses(&vec![goal]).unwrap_err(),
+            DatalogError::NotDefinite(0)
+        );
+
+        let mut database = Database::new();
+        assert!(database.insert_atom(&atom(b'P', vec![x])).is_err());
+    }
+}
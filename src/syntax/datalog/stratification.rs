@@ -0,0 +1,146 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use super::{DatalogError, Rule};
+use crate::syntax::GenericAtomicFormula;
+
+/// The dependency graph of a set of rules over their predicate symbols.
+///
+/// There is an edge from the predicate of each literal of the body of a rule
+/// to the predicate of its head, which is negative when the literal is
+/// negated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    /// For each predicate, the predicates depending on it, and whether any of
+    /// those dependencies is negative.
+    edges: BTreeMap<u64, BTreeMap<u64, bool>>,
+}
+
+impl DependencyGraph {
+    /// Build the dependency graph of a set of rules.
+    pub fn new(rules: &[Rule]) -> Self {
+        let mut graph = Self::default();
+        for rule in rules {
+            graph.edges.entry(rule.head.predicate).or_default();
+            for literal in &rule.body {
+                if let GenericAtomicFormula::Predicate(p) = literal.atom() {
+                    let negative = graph
+                        .edges
+                        .entry(p.predicate)
+                        .or_default()
+                        .entry(rule.head.predicate)
+                        .or_default();
+                    *negative |= literal.is_negated();
+                }
+            }
+        }
+        graph
+    }
+
+    /// The predicates of the graph.
+    pub fn predicates(&self) -> impl Iterator<Item = u64> + '_ {
+        self.edges.keys().copied()
+    }
+
+    /// The predicates depending directly on a predicate, and whether each
+    /// dependency is negative.
+    pub fn dependents(&self, predicate: u64) -> impl Iterator<Item = (u64, bool)> + '_ {
+        self.edges
+            .get(&predicate)
+            .into_iter()
+            .flat_map(|e| e.iter().map(|(p, negative)| (*p, *negative)))
+    }
+
+    /// A path from one predicate to another, including both.
+    fn path(&self, from: u64, to: u64) -> Option<Vec<u64>> {
+        let mut parents = BTreeMap::new();
+        let mut queue = VecDeque::from([from]);
+        let mut seen = BTreeSet::from([from]);
+        while let Some(p) = queue.pop_front() {
+            if p == to {
+                let mut path = vec![to];
+                while let Some(parent) = parents.get(path.last().unwrap()) {
+                    path.push(*parent);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (q, _) in self.dependents(p) {
+                if seen.insert(q) {
+                    parents.insert(q, p);
+                    queue.push_back(q);
+                }
+            }
+        }
+        None
+    }
+
+    /// Partition the predicates into strata, so that each predicate depends
+    /// positively only on predicates of its own or lower strata, and
+    /// negatively only on predicates of lower strata.
+    ///
+    /// This is possible exactly when no cycle of the graph has a negative
+    /// edge. If there is one, it is returned in the error, starting and ending
+    /// at the same predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use first_order_logic::syntax::{
+    /// #   datalog::{DatalogError, DependencyGraph, Rule}, normal_forms::Literal, GenericTerm,
+    /// #   PredicateCall, Variable,
+    /// # };
+    /// let x = GenericTerm::from(Variable::new(b'x'.into()));
+    /// let atom = |p: u8| PredicateCall { predicate: p.into(), terms: vec![x.clone()] };
+    /// let rule = |head: u8, body: Literal| Rule { head: atom(head), body: vec![body] };
+    ///
+    /// // A(x) :- B(x).  C(x) :- ¬A(x).
+    /// let mut rules = vec![
+    ///     rule(b'A', Literal::Atom(atom(b'B').into())),
+    ///     rule(b'C', Literal::Atom(atom(b'A').into()).negate()),
+    /// ];
+    /// let strata = DependencyGraph::new(&rules).stratify().unwrap();
+    /// assert_eq!(strata, vec![vec![b'A'.into(), b'B'.into()], vec![b'C'.into()]]);
+    ///
+    /// // B(x) :- C(x).
+    /// rules.push(rule(b'B', Literal::Atom(atom(b'C').into())));
+    /// assert_eq!(
+    ///     DependencyGraph::new(&rules).stratify(),
+    ///     Err(DatalogError::NegativeCycle(vec![b'A'.into(), b'C'.into(), b'B'.into(), b'A'.into()]))
+    /// );
+    /// ```
+    pub fn stratify(&self) -> Result<Vec<Vec<u64>>, DatalogError> {
+        for p in self.predicates() {
+            for (q, negative) in self.dependents(p) {
+                if negative {
+                    if let Some(mut cycle) = self.path(q, p) {
+                        cycle.insert(0, p);
+                        return Err(DatalogError::NegativeCycle(cycle));
+                    }
+                }
+            }
+        }
+
+        // With no negative cycles, raising each predicate to the stratum of
+        // its dependencies reaches a fixed point.
+        let mut strata: BTreeMap<u64, usize> = self.predicates().map(|p| (p, 0)).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for p in self.predicates() {
+                for (q, negative) in self.dependents(p) {
+                    let least = strata[&p] + negative as usize;
+                    if strata[&q] < least {
+                        strata.insert(q, least);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        let mut result = vec![Vec::new(); strata.values().max().map_or(0, |s| s + 1)];
+        for (p, s) in strata {
+            result[s].push(p);
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,309 @@
+use std::{collections::HashMap, convert::TryFrom, fmt::Display};
+
+use super::{
+    normal_forms::{ConjunctiveNormalFormula, Literal},
+    GenericAtomicFormula,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////
+
+/// The errors which can occur when moving between a CNF and DIMACS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DimacsError {
+    /// The CNF contains a literal with a variable in it, so is not
+    /// propositional.
+    NotGround(Literal),
+    /// A model referenced a DIMACS variable which has no corresponding atom.
+    UnknownVariable(i64),
+    /// A token in a model could not be parsed as a DIMACS literal.
+    Malformed(String),
+}
+
+impl Display for DimacsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotGround(x) => f.write_fmt(format_args!("literal {} is not ground", x)),
+            Self::UnknownVariable(x) => f.write_fmt(format_args!("unknown variable {}", x)),
+            Self::Malformed(x) => f.write_fmt(format_args!("malformed token '{}'", x)),
+        }
+    }
+}
+
+impl std::error::Error for DimacsError {}
+
+////////////////////////////////////////////////////////////////////////////////
+// DIMACS Encoding
+////////////////////////////////////////////////////////////////////////////////
+
+/// A ground CNF, encoded as a propositional problem in DIMACS form.
+///
+/// Each distinct ground atom is mapped to a DIMACS variable, numbered from `1`
+/// in order of first appearance. The `⊤` and `⊥` atoms are not given
+/// variables: clauses containing a true literal are dropped, and false literals
+/// are dropped from their clauses.
+///
+/// # Examples
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// # use first_order_logic::syntax::{
+/// #   dimacs::DimacsEncoding,
+/// #   normal_forms::{Clause, ConjunctiveNormalFormula, Literal},
+/// #   Constant, GenericAtomicFormula, PredicateCall,
+/// # };
+/// let p: GenericAtomicFormula = PredicateCall {
+///     predicate: b'P'.into(),
+///     terms: vec![Constant { label: 1 }.into()],
+/// }
+/// .into();
+///
+/// let cnf = ConjunctiveNormalFormula {
+///     clauses: vec![Clause {
+///         literals: vec![Literal::Atom(p.clone())],
+///     }],
+/// };
+///
+/// let encoding = DimacsEncoding::try_from(&cnf).unwrap();
+/// assert_eq!(encoding.to_string(), "p cnf 1 1\n1 0\n");
+///
+/// let model = encoding.read_model("s SATISFIABLE\nv 1 0\n").unwrap();
+/// assert_eq!(model.get(&p), Some(&true));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DimacsEncoding {
+    atoms: Vec<GenericAtomicFormula>,
+    variables: HashMap<GenericAtomicFormula, usize>,
+    clauses: Vec<Vec<i64>>,
+}
+
+impl DimacsEncoding {
+    /// The number of DIMACS variables in the encoding.
+    pub fn num_variables(&self) -> usize {
+        self.atoms.len()
+    }
+
+    /// The encoded clauses, as lists of non-zero DIMACS literals.
+    pub fn clauses(&self) -> &[Vec<i64>] {
+        &self.clauses
+    }
+
+    /// Get the atom corresponding to a DIMACS variable.
+    pub fn atom(&self, variable: usize) -> Option<&GenericAtomicFormula> {
+        variable.checked_sub(1).and_then(|i| self.atoms.get(i))
+    }
+
+    /// Get the DIMACS variable corresponding to an atom.
+    pub fn variable(&self, atom: &GenericAtomicFormula) -> Option<usize> {
+        self.variables.get(atom).copied()
+    }
+
+    /// Iterate over the `(variable, atom)` pairs of the encoding.
+    pub fn atoms(&self) -> impl Iterator<Item = (usize, &GenericAtomicFormula)> {
+        self.atoms.iter().enumerate().map(|(i, a)| (i + 1, a))
+    }
+
+    /// Read back a model produced by a SAT solver.
+    ///
+    /// Literals are read from the `v` lines of the solver output; all other
+    /// lines (`c`, `s`, ...) are ignored. Atoms whose variable is not mentioned
+    /// in the model are left out of the returned assignment.
+    pub fn read_model(
+        &self,
+        text: &str,
+    ) -> Result<HashMap<GenericAtomicFormula, bool>, DimacsError> {
+        let mut assignment = HashMap::new();
+
+        for line in text.lines() {
+            let mut tokens = line.split_whitespace();
+            if tokens.next() != Some("v") {
+                continue;
+            }
+
+            for token in tokens {
+                let literal: i64 = token
+                    .parse()
+                    .map_err(|_| DimacsError::Malformed(token.to_owned()))?;
+                if literal == 0 {
+                    break;
+                }
+
+                let atom = usize::try_from(literal.unsigned_abs())
+                    .ok()
+                    .and_then(|v| self.atom(v))
+                    .ok_or(DimacsError::UnknownVariable(literal))?;
+                assignment.insert(atom.clone(), literal > 0);
+            }
+        }
+
+        Ok(assignment)
+    }
+
+    fn encode_literal(&mut self, literal: &Literal) -> Result<i64, DimacsError> {
+        let atom = literal.atom();
+        if !atom.is_ground() {
+            return Err(DimacsError::NotGround(literal.clone()));
+        }
+
+        let variable = match self.variables.get(atom) {
+            Some(v) => *v,
+            None => {
+                self.atoms.push(atom.clone());
+                self.variables.insert(atom.clone(), self.atoms.len());
+                self.atoms.len()
+            }
+        };
+
+        let variable = variable as i64;
+        Ok(if literal.is_negated() {
+            -variable
+        } else {
+            variable
+        })
+    }
+}
+
+impl TryFrom<&ConjunctiveNormalFormula> for DimacsEncoding {
+    type Error = DimacsError;
+
+    fn try_from(f: &ConjunctiveNormalFormula) -> Result<Self, Self::Error> {
+        let mut encoding = Self {
+            atoms: Vec::new(),
+            variables: HashMap::new(),
+            clauses: Vec::with_capacity(f.clauses.len()),
+        };
+
+        'clauses: for clause in &f.clauses {
+            let mut encoded = Vec::with_capacity(clause.literals.len());
+            for literal in &clause.literals {
+                match (literal.atom(), literal.is_negated()) {
+                    // The clause is trivially satisfied.
+                    (GenericAtomicFormula::True, false) | (GenericAtomicFormula::False, true) => {
+                        continue 'clauses;
+                    }
+                    // The literal can never be satisfied.
+                    (GenericAtomicFormula::True, true) | (GenericAtomicFormula::False, false) => {}
+                    _ => encoded.push(encoding.encode_literal(literal)?),
+                }
+            }
+            encoding.clauses.push(encoded);
+        }
+
+        Ok(encoding)
+    }
+}
+
+/// Write the encoding as DIMACS `p cnf` text.
+impl Display for DimacsEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "p cnf {} {}\n",
+            self.num_variables(),
+            self.clauses.len()
+        ))?;
+        for clause in &self.clauses {
+            for literal in clause {
+                f.write_fmt(format_args!("{} ", literal))?;
+            }
+            f.write_str("0\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::syntax::{
+        normal_forms::{Clause, ConjunctiveNormalFormula, Literal},
+        Constant, GenericAtomicFormula, Negation, PredicateCall, Variable,
+    };
+
+    use super::{DimacsEncoding, DimacsError};
+
+    fn atom(predicate: u8, constant: u64) -> GenericAtomicFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms: vec![Constant { label: constant }.into()],
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_encode_and_read_model() {
+        let p = atom(b'P', 1);
+        let q = atom(b'Q', 2);
+
+        // (P(1) ∨ ¬Q(2)) ∧ (Q(2)) ∧ (⊤ ∨ P(1)) ∧ (⊥ ∨ ¬P(1))
+        let cnf = ConjunctiveNormalFormula {
+            clauses: vec![
+                Clause {
+                    literals: vec![
+                        Literal::Atom(p.clone()),
+                        Literal::Negated(Negation { right: q.clone() }),
+                    ],
+                },
+                Clause {
+                    literals: vec![Literal::Atom(q.clone())],
+                },
+                Clause {
+                    literals: vec![
+                        Literal::Atom(GenericAtomicFormula::True),
+                        Literal::Atom(p.clone()),
+                    ],
+                },
+                Clause {
+                    literals: vec![
+                        Literal::Atom(GenericAtomicFormula::False),
+                        Literal::Negated(Negation { right: p.clone() }),
+                    ],
+                },
+            ],
+        };
+
+        let encoding = DimacsEncoding::try_from(&cnf).unwrap();
+        assert_eq!(encoding.variable(&p), Some(1));
+        assert_eq!(encoding.variable(&q), Some(2));
+        assert_eq!(encoding.atom(2), Some(&q));
+        assert_eq!(encoding.atom(0), None);
+        assert_eq!(encoding.to_string(), "p cnf 2 3\n1 -2 0\n2 0\n-1 0\n");
+
+        let model = encoding
+            .read_model("c a comment\ns SATISFIABLE\nv -1\nv 2 0\n")
+            .unwrap();
+        assert_eq!(model.get(&p), Some(&false));
+        assert_eq!(model.get(&q), Some(&true));
+
+        assert_eq!(
+            encoding.read_model("v 3 0"),
+            Err(DimacsError::UnknownVariable(3))
+        );
+        assert_eq!(
+            encoding.read_model("v x 0"),
+            Err(DimacsError::Malformed("x".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_non_ground_rejected() {
+        let literal = Literal::Atom(
+            PredicateCall {
+                predicate: b'P'.into(),
+                terms: vec![Variable::new(b'x'.into()).into()],
+            }
+            .into(),
+        );
+        let cnf = ConjunctiveNormalFormula {
+            clauses: vec![Clause {
+                literals: vec![literal.clone()],
+            }],
+        };
+
+        assert_eq!(
+            DimacsEncoding::try_from(&cnf).unwrap_err(),
+            DimacsError::NotGround(literal)
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{
+    herbrand::{GroundInstance, GroundInstances, HerbrandUniverse},
+    normal_forms::{
+        ClauseNormalForm, ConjunctiveNormalFormula, Literal, PrenexNormalFormula,
+        SkolemNormalFormula,
+    },
+    resolution::equality_axioms,
+    sat::{SatResult, SatSolver},
+    structure::Structure,
+    tableaux::universal_closure,
+    Constant, Equality, GenericAtomicFormula, GenericFormula, GenericTerm, Negation, PredicateCall,
+    Signature,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+// Results
+////////////////////////////////////////////////////////////////////////////////
+
+/// The errors which can occur when deciding a set of formulas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EprError {
+    /// The formula at this index is not in the EPR fragment.
+    NotEpr(usize),
+}
+
+impl Display for EprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEpr(x) => f.write_fmt(format_args!("formula {} is not in EPR", x)),
+        }
+    }
+}
+
+impl std::error::Error for EprError {}
+
+/// A refutation of a set of EPR formulas: a set of ground instances of their
+/// clauses which is propositionally unsatisfiable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refutation {
+    /// The clauses of the formulas, with their existential quantifiers
+    /// Skolemised by constants, followed by the equality axioms if the
+    /// formulas use equality.
+    pub clauses: ClauseNormalForm,
+    /// The ground instances of the clauses.
+    pub instances: Vec<GroundInstance>,
+}
+
+/// The outcome of deciding a set of EPR formulas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EprResult {
+    /// The formulas are satisfied by a model whose elements are constants of
+    /// their Herbrand universe.
+    Satisfiable(Structure<Constant>),
+    /// The formulas are unsatisfiable.
+    Unsatisfiable(Refutation),
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Decision Procedure
+////////////////////////////////////////////////////////////////////////////////
+
+/// Replace calls to nullary functions, which Skolemising an `∃*∀*` formula
+/// introduces, by constants with the same labels.
+fn constants_for_nullary(term: &GenericTerm) -> GenericTerm {
+    match term {
+        GenericTerm::FunctionCall(f) if f.terms.is_empty() => Constant { label: f.function }.into(),
+        _ => term.clone(),
+    }
+}
+
+fn literal_with_constants(literal: &Literal) -> Literal {
+    let atom = match literal.atom() {
+        GenericAtomicFormula::Equality(e) => Equality {
+            left: constants_for_nullary(&e.left),
+            right: constants_for_nullary(&e.right),
+        }
+        .into(),
+        GenericAtomicFormula::Predicate(p) => PredicateCall {
+            predicate: p.predicate,
+            terms: p.terms.iter().map(constants_for_nullary).collect(),
+        }
+        .into(),
+        atom => atom.clone(),
+    };
+    if literal.is_negated() {
+        Literal::Negated(Negation { right: atom })
+    } else {
+        Literal::Atom(atom)
+    }
+}
+
+/// Decide whether a set of formulas in the Bernays–Schönfinkel (EPR)
+/// fragment is satisfiable. Free variables are treated as universally
+/// quantified.
+///
+/// The existential quantifiers of an `∃*∀*` formula are Skolemised by
+/// constants, so its Herbrand universe is the finite set of constants, and
+/// the formulas are equisatisfiable with the finite set of ground instances
+/// of their clauses, which is decided by the [`SatSolver`]. Equality is
+/// handled by adding the ground instances of the
+/// [`equality_axioms`](crate::syntax::resolution::equality_axioms).
+///
+/// Returns an error if a formula's [prenex normal form](PrenexNormalFormula::is_epr)
+/// is not in EPR.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   epr::{decide, EprResult}, Existential, GenericFormula, GenericTerm, Negation,
+/// #   PredicateCall, Universal, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let y = Variable::new(b'y'.into());
+/// let r = |s: GenericTerm, t: GenericTerm| -> GenericFormula {
+///     PredicateCall { predicate: b'R'.into(), terms: vec![s, t] }.into()
+/// };
+///
+/// // Something is related to everything: ∃x.∀y.R(x, y).
+/// let hub: GenericFormula = Existential {
+///     left: x,
+///     right: Universal { left: y, right: r(x.into(), y.into()) }.into(),
+/// }
+/// .into();
+/// // Nothing is related to itself: ∀x.¬R(x, x).
+/// let irreflexive: GenericFormula = Universal {
+///     left: x,
+///     right: Negation { right: r(x.into(), x.into()) }.into(),
+/// }
+/// .into();
+///
+/// match decide(&[hub.clone()]).unwrap() {
+///     EprResult::Satisfiable(model) => assert!(model.models(&hub)),
+///     result => panic!("{:?}", result),
+/// }
+/// assert!(matches!(decide(&[hub, irreflexive]), Ok(EprResult::Unsatisfiable(_))));
+/// ```
+pub fn decide(formulas: &[GenericFormula]) -> Result<EprResult, EprError> {
+    let mut clauses = ClauseNormalForm::new();
+    let mut skolem = Vec::new();
+    for (i, formula) in formulas.iter().enumerate() {
+        let prenex = PrenexNormalFormula::from(universal_closure(formula.clone()));
+        if !prenex.is_epr() {
+            return Err(EprError::NotEpr(i));
+        }
+        let (snf, state) = SkolemNormalFormula::with_state(prenex);
+        skolem.extend(state.symbols());
+        let cnf: ClauseNormalForm = ConjunctiveNormalFormula::from(snf).into();
+        clauses.extend(
+            cnf.iter()
+                .map(|c| c.iter().map(literal_with_constants).collect()),
+        );
+    }
+
+    let mut signature = Signature::default();
+    clauses
+        .iter()
+        .for_each(|c| signature.extend_from_literals(c));
+    let equality = clauses
+        .iter()
+        .flatten()
+        .any(|l| matches!(l.atom(), GenericAtomicFormula::Equality(_)));
+    if equality {
+        clauses.extend(equality_axioms(&signature));
+    }
+
+    let universe = HerbrandUniverse::new(&signature);
+    let elements: Vec<Constant> = universe
+        .clone()
+        .map(|t| match t {
+            GenericTerm::Constant(c) => c,
+            _ => unreachable!("the universe of a function-free signature is its constants"),
+        })
+        .collect();
+    let instances: Vec<GroundInstance> =
+        GroundInstances::with_universe(&clauses, universe).collect();
+    let ground: ClauseNormalForm = instances.iter().map(|i| i.literals.clone()).collect();
+
+    let solution = SatSolver::default()
+        .solve(&ground)
+        .expect("the instances are ground");
+    Ok(match solution {
+        SatResult::Satisfiable(assignment) => {
+            let mut model = decode(&signature, &elements, &assignment);
+            for symbol in skolem {
+                model.constants.remove(&symbol);
+            }
+            EprResult::Satisfiable(model)
+        }
+        SatResult::Unsatisfiable(core) => EprResult::Unsatisfiable(Refutation {
+            instances: core.into_iter().map(|i| instances[i].clone()).collect(),
+            clauses,
+        }),
+    })
+}
+
+/// Read a structure off a model of the ground instances. Constants which are
+/// equal in the model are identified, with the first of them as the element.
+fn decode(
+    signature: &Signature,
+    elements: &[Constant],
+    assignment: &HashMap<GenericAtomicFormula, bool>,
+) -> Structure<Constant> {
+    let holds = |atom: &GenericAtomicFormula| assignment.get(atom) == Some(&true);
+
+    let mut representatives: HashMap<Constant, Constant> = HashMap::new();
+    let mut domain = Vec::new();
+    for c in elements {
+        let representative = *domain
+            .iter()
+            .find(|d: &&Constant| {
+                holds(
+                    &Equality {
+                        left: (**d).into(),
+                        right: (*c).into(),
+                    }
+                    .into(),
+                )
+            })
+            .unwrap_or(c);
+        if representative == *c {
+            domain.push(*c);
+        }
+        representatives.insert(*c, representative);
+    }
+
+    let mut structure = Structure::new(domain);
+    for label in signature.constants.keys() {
+        let c = Constant { label: *label };
+        structure.set_constant(*label, representatives[&c]);
+    }
+    for predicate in signature.predicates.keys() {
+        structure.predicates.entry(*predicate).or_default();
+    }
+    for (atom, value) in assignment {
+        if let (GenericAtomicFormula::Predicate(p), true) = (atom, value) {
+            let arguments = p
+                .terms
+                .iter()
+                .map(|t| match t {
+                    GenericTerm::Constant(c) => representatives[c],
+                    _ => unreachable!("the atoms are ground and function-free"),
+                })
+                .collect();
+            structure.set_predicate(p.predicate, arguments, true);
+        }
+    }
+    structure
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::PrenexNormalFormula,
+        sat::{SatResult, SatSolver},
+        Constant, Equality, Existential, FunctionCall, GenericFormula, GenericTerm, Implication,
+        Negation, PredicateCall, Universal, Variable,
+    };
+
+    use super::{decide, EprError, EprResult};
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    fn not(right: GenericFormula) -> GenericFormula {
+        Negation { right }.into()
+    }
+
+    #[test]
+    fn test_detection() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let exists_forall: GenericFormula = Existential {
+            left: x,
+            right: Universal {
+                left: y,
+                right: p(b'R', vec![x.into(), y.into()]),
+            }
+            .into(),
+        }
+        .into();
+        let forall_exists: GenericFormula = Universal {
+            left: y,
+            right: Existential {
+                left: x,
+                right: p(b'R', vec![x.into(), y.into()]),
+            }
+            .into(),
+        }
+        .into();
+        let function: GenericFormula = Universal {
+            left: x,
+            right: p(
+                b'P',
+                vec![FunctionCall {
+                    function: b'f'.into(),
+                    terms: vec![x.into()],
+                }
+                .into()],
+            ),
+        }
+        .into();
+
+        assert!(PrenexNormalFormula::from(exists_forall.clone()).is_epr());
+        assert!(!PrenexNormalFormula::from(forall_exists.clone()).is_epr());
+        assert!(!PrenexNormalFormula::from(function).is_epr());
+        assert_eq!(
+            decide(&[exists_forall, forall_exists]),
+            Err(EprError::NotEpr(1))
+        );
+    }
+
+    #[test]
+    fn test_refutation() {
+        let x = Variable::new(b'x'.into());
+        let a: GenericTerm = Constant { label: b'a'.into() }.into();
+        let axioms = vec![
+            Universal {
+                left: x,
+                right: Implication {
+                    left: p(b'P', vec![x.into()]),
+                    right: p(b'Q', vec![x.into()]),
+                }
+                .into(),
+            }
+            .into(),
+            p(b'P', vec![a.clone()]),
+            not(p(b'Q', vec![a])),
+            p(b'S', vec![]),
+        ];
+
+        let EprResult::Unsatisfiable(refutation) = decide(&axioms).unwrap() else {
+            panic!()
+        };
+        // The instance of the implication at a, P(a) and ¬Q(a).
+        assert_eq!(refutation.instances.len(), 3);
+        let ground = refutation
+            .instances
+            .iter()
+            .map(|i| i.literals.clone())
+            .collect();
+        assert!(matches!(
+            SatSolver::default().solve(&ground),
+            Ok(SatResult::Unsatisfiable(_))
+        ));
+
+        let EprResult::Satisfiable(model) = decide(&axioms[..2]).unwrap() else {
+            panic!()
+        };
+        assert!(axioms[..2].iter().all(|a| model.models(a)));
+    }
+
+    #[test]
+    fn test_equality() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let a: GenericTerm = Constant { label: b'a'.into() }.into();
+        let b: GenericTerm = Constant { label: b'b'.into() }.into();
+        let equal: GenericFormula = Equality {
+            left: a.clone(),
+            right: b.clone(),
+        }
+        .into();
+        // ∃x.∀y.x = y: there is exactly one element.
+        let singleton: GenericFormula = Existential {
+            left: x,
+            right: Universal {
+                left: y,
+                right: Equality {
+                    left: x.into(),
+                    right: y.into(),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+
+        let axioms = vec![equal.clone(), p(b'P', vec![a.clone()])];
+        let EprResult::Satisfiable(model) = decide(&axioms).unwrap() else {
+            panic!()
+        };
+        assert_eq!(model.domain.len(), 1);
+        assert!(axioms.iter().all(|a| model.models(a)));
+
+        let contradiction = vec![
+            equal,
+            p(b'P', vec![a.clone()]),
+            not(p(b'P', vec![b.clone()])),
+        ];
+        assert!(matches!(
+            decide(&contradiction),
+            Ok(EprResult::Unsatisfiable(_))
+        ));
+
+        let distinct = not(Equality { left: a, right: b }.into());
+        assert!(matches!(
+            decide(&[singleton.clone(), distinct.clone()]),
+            Ok(EprResult::Unsatisfiable(_))
+        ));
+        let EprResult::Satisfiable(model) = decide(std::slice::from_ref(&distinct)).unwrap() else {
+            panic!()
+        };
+        assert_eq!(model.domain.len(), 2);
+        assert!(model.models(&distinct));
+    }
+}
@@ -0,0 +1,628 @@
+use super::{
+    normal_forms::{ClauseNormalForm, Literal},
+    tableaux::{negation_normal_form, universal_closure},
+    GenericAtomicFormula, GenericFormula, Signature, Variable,
+};
+
+/// The syntactic fragments a formula or set of clauses belongs to.
+///
+/// The fragments are defined up to the equivalences of negation-normal form:
+/// a quantifier is universal or existential according to its polarity, so
+/// `¬∃x.P(x)` is universal, and `(∀x.P(x)) ⇒ Q()` is existential.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fragments {
+    /// There are no quantifiers or terms, only nullary predicates.
+    pub propositional: bool,
+    /// There are no functions, and every predicate has at most one argument.
+    pub monadic: bool,
+    /// Every clause of the clause normal form has at most one positive
+    /// literal.
+    pub horn: bool,
+    /// Every clause of the clause normal form has exactly one positive
+    /// literal.
+    pub definite: bool,
+    /// There are no functions, and no existential quantifier is in the scope
+    /// of a universal one, the Bernays–Schönfinkel fragment.
+    pub epr: bool,
+    /// There are no existential quantifiers.
+    pub universal: bool,
+    /// There are no universal quantifiers.
+    pub existential: bool,
+    /// There are no functions, and every quantifier is guarded: of the form
+    /// `∀x̄.(G ⇒ φ)` or `∃x̄.(G ∧ φ)` for an atom `G` containing `x̄` and the
+    /// free variables of `φ`.
+    pub guarded: bool,
+    /// There are no functions, and at most two variables.
+    pub two_variable: bool,
+    /// The largest number of alternations between universal and existential
+    /// quantifiers along a path through the formula.
+    pub alternation_depth: usize,
+}
+
+impl Fragments {
+    /// Whether satisfiability is decidable for one of the fragments.
+    pub fn is_decidable(&self) -> bool {
+        self.propositional || self.monadic || self.epr || self.guarded || self.two_variable
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Formulas
+////////////////////////////////////////////////////////////////////////////////
+
+/// Classify a formula by the syntactic fragments it belongs to. Free
+/// variables are treated as universally quantified.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   fragments::classify, Existential, GenericFormula, Implication, PredicateCall,
+/// #   Universal, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let y = Variable::new(b'y'.into());
+/// let r: GenericFormula = PredicateCall {
+///     predicate: b'R'.into(),
+///     terms: vec![x.into(), y.into()],
+/// }
+/// .into();
+///
+/// // ∀x.∃y.R(x, y)
+/// let serial: GenericFormula = Universal {
+///     left: x,
+///     right: Existential { left: y, right: r.clone() }.into(),
+/// }
+/// .into();
+/// let fragments = classify(&serial);
+/// assert!(fragments.horn && fragments.two_variable && fragments.is_decidable());
+/// assert!(!fragments.epr && !fragments.monadic && !fragments.guarded);
+/// assert_eq!(fragments.alternation_depth, 1);
+///
+/// // (∃x.∃y.R(x, y)) ⇒ R(x, x) is universal.
+/// let antecedent: GenericFormula = Existential {
+///     left: x,
+///     right: Existential { left: y, right: r }.into(),
+/// }
+/// .into();
+/// let consequent: GenericFormula = PredicateCall {
+///     predicate: b'R'.into(),
+///     terms: vec![x.into(), x.into()],
+/// }
+/// .into();
+/// let fragments = classify(&Implication { left: antecedent, right: consequent }.into());
+/// assert!(fragments.universal && fragments.epr);
+/// ```
+pub fn classify(formula: &GenericFormula) -> Fragments {
+    let signature = Signature::from(formula);
+    let nnf = negation_normal_form(universal_closure(formula.clone()));
+    let function_free = signature.functions.is_empty();
+
+    let (universal, existential) = quantifiers(&nnf);
+    let (max_positive, min_positive) = positive_literals(&nnf);
+    let mut variables = Vec::new();
+    collect_variables(&nnf, &mut variables);
+
+    Fragments {
+        propositional: !universal
+            && !existential
+            && variables.is_empty()
+            && function_free
+            && signature.constants.is_empty()
+            && signature.predicates.values().all(|p| p.arity == 0)
+            && !has_equality(&nnf),
+        monadic: function_free && signature.predicates.values().all(|p| p.arity <= 1),
+        horn: max_positive <= 1,
+        definite: max_positive <= 1 && min_positive >= 1,
+        epr: function_free && !existential_under_universal(&nnf, false),
+        universal: !existential,
+        existential: !universal,
+        guarded: function_free && guarded(&nnf),
+        two_variable: function_free && variables.len() <= 2,
+        alternation_depth: alternation_depth(&nnf, None),
+    }
+}
+
+/// Whether a formula in negation-normal form has universal and existential
+/// quantifiers.
+fn quantifiers(formula: &GenericFormula) -> (bool, bool) {
+    match formula {
+        GenericFormula::Universal(x) => (true, quantifiers(&x.right).1),
+        GenericFormula::Existential(x) => (quantifiers(&x.right).0, true),
+        GenericFormula::Conjunction(x) => or(quantifiers(&x.left), quantifiers(&x.right)),
+        GenericFormula::Disjunction(x) => or(quantifiers(&x.left), quantifiers(&x.right)),
+        GenericFormula::Implication(x) => or(quantifiers(&x.left), quantifiers(&x.right)),
+        GenericFormula::Negation(x) => quantifiers(&x.right),
+        GenericFormula::Atomic(_) => (false, false),
+    }
+}
+
+fn or(left: (bool, bool), right: (bool, bool)) -> (bool, bool) {
+    (left.0 || right.0, left.1 || right.1)
+}
+
+/// The largest and smallest numbers of positive literals in a clause of the
+/// clause normal form of a formula in negation-normal form. A formula with
+/// no clauses has a smallest number of [`usize::MAX`].
+fn positive_literals(formula: &GenericFormula) -> (usize, usize) {
+    match formula {
+        GenericFormula::Atomic(GenericAtomicFormula::True) => (0, usize::MAX),
+        GenericFormula::Atomic(GenericAtomicFormula::False) => (0, 0),
+        GenericFormula::Atomic(_) => (1, 1),
+        GenericFormula::Negation(_) => (0, 0),
+        GenericFormula::Universal(x) => positive_literals(&x.right),
+        GenericFormula::Existential(x) => positive_literals(&x.right),
+        GenericFormula::Conjunction(x) => {
+            let (left, right) = (positive_literals(&x.left), positive_literals(&x.right));
+            (left.0.max(right.0), left.1.min(right.1))
+        }
+        // Each clause of a disjunction joins a clause from each side.
+        GenericFormula::Disjunction(x) => {
+            let (left, right) = (positive_literals(&x.left), positive_literals(&x.right));
+            (left.0 + right.0, left.1.saturating_add(right.1))
+        }
+        GenericFormula::Implication(_) => unreachable!("the formula is in negation-normal form"),
+    }
+}
+
+/// Add every variable in a formula, bound or free, to a list.
+fn collect_variables(formula: &GenericFormula, variables: &mut Vec<Variable>) {
+    let mut add = |v: Variable| {
+        if !variables.contains(&v) {
+            variables.push(v);
+        }
+    };
+    match formula {
+        GenericFormula::Atomic(x) => x.variables().into_iter().for_each(add),
+        GenericFormula::Universal(x) => {
+            add(x.left);
+            collect_variables(&x.right, variables);
+        }
+        GenericFormula::Existential(x) => {
+            add(x.left);
+            collect_variables(&x.right, variables);
+        }
+        GenericFormula::Conjunction(x) => {
+            collect_variables(&x.left, variables);
+            collect_variables(&x.right, variables);
+        }
+        GenericFormula::Disjunction(x) => {
+            collect_variables(&x.left, variables);
+            collect_variables(&x.right, variables);
+        }
+        GenericFormula::Implication(x) => {
+            collect_variables(&x.left, variables);
+            collect_variables(&x.right, variables);
+        }
+        GenericFormula::Negation(x) => collect_variables(&x.right, variables),
+    }
+}
+
+fn has_equality(formula: &GenericFormula) -> bool {
+    match formula {
+        GenericFormula::Atomic(x) => matches!(x, GenericAtomicFormula::Equality(_)),
+        GenericFormula::Universal(x) => has_equality(&x.right),
+        GenericFormula::Existential(x) => has_equality(&x.right),
+        GenericFormula::Conjunction(x) => has_equality(&x.left) || has_equality(&x.right),
+        GenericFormula::Disjunction(x) => has_equality(&x.left) || has_equality(&x.right),
+        GenericFormula::Implication(x) => has_equality(&x.left) || has_equality(&x.right),
+        GenericFormula::Negation(x) => has_equality(&x.right),
+    }
+}
+
+fn existential_under_universal(formula: &GenericFormula, under: bool) -> bool {
+    match formula {
+        GenericFormula::Atomic(_) | GenericFormula::Negation(_) => false,
+        GenericFormula::Universal(x) => existential_under_universal(&x.right, true),
+        GenericFormula::Existential(x) => under || existential_under_universal(&x.right, under),
+        GenericFormula::Conjunction(x) => {
+            existential_under_universal(&x.left, under)
+                || existential_under_universal(&x.right, under)
+        }
+        GenericFormula::Disjunction(x) => {
+            existential_under_universal(&x.left, under)
+                || existential_under_universal(&x.right, under)
+        }
+        GenericFormula::Implication(_) => unreachable!("the formula is in negation-normal form"),
+    }
+}
+
+/// The alternation depth of a formula in negation-normal form, below a
+/// quantifier which is universal (`Some(true)`) or existential.
+fn alternation_depth(formula: &GenericFormula, outer: Option<bool>) -> usize {
+    let quantifier = |universal: bool, right: &GenericFormula| {
+        let switch = usize::from(outer.is_some_and(|o| o != universal));
+        switch + alternation_depth(right, Some(universal))
+    };
+    match formula {
+        GenericFormula::Atomic(_) | GenericFormula::Negation(_) => 0,
+        GenericFormula::Universal(x) => quantifier(true, &x.right),
+        GenericFormula::Existential(x) => quantifier(false, &x.right),
+        GenericFormula::Conjunction(x) => {
+            alternation_depth(&x.left, outer).max(alternation_depth(&x.right, outer))
+        }
+        GenericFormula::Disjunction(x) => {
+            alternation_depth(&x.left, outer).max(alternation_depth(&x.right, outer))
+        }
+        GenericFormula::Implication(_) => unreachable!("the formula is in negation-normal form"),
+    }
+}
+
+/// Whether every quantifier in a formula in negation-normal form is guarded:
+/// of the form `∀x̄.(¬G ∨ φ)` or `∃x̄.(G ∧ φ)`, up to the bracketing and
+/// order of the operands.
+fn guarded(formula: &GenericFormula) -> bool {
+    match formula {
+        GenericFormula::Atomic(_) | GenericFormula::Negation(_) => true,
+        GenericFormula::Conjunction(x) => guarded(&x.left) && guarded(&x.right),
+        GenericFormula::Disjunction(x) => guarded(&x.left) && guarded(&x.right),
+        GenericFormula::Universal(_) | GenericFormula::Existential(_) => {
+            let universal = matches!(formula, GenericFormula::Universal(_));
+            // Take the whole block of quantifiers of the same kind.
+            let mut block = Vec::new();
+            let mut body = formula;
+            loop {
+                match body {
+                    GenericFormula::Universal(x) if universal => {
+                        block.push(x.left);
+                        body = &x.right;
+                    }
+                    GenericFormula::Existential(x) if !universal => {
+                        block.push(x.left);
+                        body = &x.right;
+                    }
+                    _ => break,
+                }
+            }
+
+            // The guard may be any operand of the disjunction or
+            // conjunction under the block, however it is bracketed.
+            let mut operands = Vec::new();
+            flatten(body, universal, &mut operands);
+            let guard = |i: usize| {
+                let atom = match (operands[i], universal) {
+                    (GenericFormula::Negation(x), true) => match &x.right {
+                        GenericFormula::Atomic(atom) => atom,
+                        _ => return false,
+                    },
+                    (GenericFormula::Atomic(atom), false) => atom,
+                    _ => return false,
+                };
+                let covered = atom.variables();
+                let rest = || {
+                    operands
+                        .iter()
+                        .enumerate()
+                        .filter(move |(j, _)| *j != i)
+                        .map(|(_, f)| *f)
+                };
+                block
+                    .iter()
+                    .copied()
+                    .chain(rest().flat_map(|f| f.free_variables()))
+                    .all(|v| covered.contains(&v))
+                    && rest().all(guarded)
+            };
+            (0..operands.len()).any(guard)
+        }
+        GenericFormula::Implication(_) => unreachable!("the formula is in negation-normal form"),
+    }
+}
+
+/// Add the operands of a nest of disjunctions (or conjunctions) to a list.
+fn flatten<'a>(
+    formula: &'a GenericFormula,
+    disjunction: bool,
+    operands: &mut Vec<&'a GenericFormula>,
+) {
+    match formula {
+        GenericFormula::Disjunction(x) if disjunction => {
+            flatten(&x.left, disjunction, operands);
+            flatten(&x.right, disjunction, operands);
+        }
+        GenericFormula::Conjunction(x) if !disjunction => {
+            flatten(&x.left, disjunction, operands);
+            flatten(&x.right, disjunction, operands);
+        }
+        _ => operands.push(formula),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Clauses
+////////////////////////////////////////////////////////////////////////////////
+
+/// Classify a set of clauses by the syntactic fragments it belongs to.
+///
+/// The variables of each clause are universally quantified, so the clauses
+/// are always universal, and existential only when they are ground. A clause
+/// is guarded when a negative literal contains all of its variables.
+pub fn classify_clauses(clauses: &ClauseNormalForm) -> Fragments {
+    let mut signature = Signature::default();
+    clauses
+        .iter()
+        .for_each(|c| signature.extend_from_literals(c));
+    let function_free = signature.functions.is_empty();
+    let ground = clauses.iter().flatten().all(|l| l.atom().is_ground());
+    let variables = |clause: &[Literal]| {
+        let mut variables = Vec::new();
+        for literal in clause {
+            for v in literal.atom().variables() {
+                if !variables.contains(&v) {
+                    variables.push(v);
+                }
+            }
+        }
+        variables
+    };
+    let positive = |clause: &[Literal]| clause.iter().filter(|l| !l.is_negated()).count();
+
+    Fragments {
+        propositional: ground
+            && function_free
+            && signature.constants.is_empty()
+            && clauses.iter().flatten().all(|l| {
+                matches!(
+                    l.atom(),
+                    GenericAtomicFormula::Predicate(_)
+                        | GenericAtomicFormula::True
+                        | GenericAtomicFormula::False
+                )
+            }),
+        monadic: function_free && signature.predicates.values().all(|p| p.arity <= 1),
+        horn: clauses.iter().all(|c| positive(c) <= 1),
+        definite: clauses.iter().all(|c| positive(c) == 1),
+        epr: function_free,
+        universal: true,
+        existential: ground,
+        guarded: function_free
+            && clauses.iter().all(|c| {
+                let all = variables(c);
+                all.is_empty()
+                    || c.iter().any(|l| {
+                        let covered = l.atom().variables();
+                        l.is_negated() && all.iter().all(|v| covered.contains(v))
+                    })
+            }),
+        two_variable: function_free && clauses.iter().all(|c| variables(c).len() <= 2),
+        alternation_depth: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::Literal, Conjunction, Constant, Disjunction, Equality, Existential,
+        FunctionCall, GenericFormula, GenericTerm, Implication, Negation, PredicateCall, Universal,
+        Variable,
+    };
+
+    use super::{classify, classify_clauses};
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_propositional_and_horn() {
+        // (A ∧ B ⇒ C) ∧ ¬D
+        let formula: GenericFormula = Conjunction {
+            left: Implication {
+                left: Conjunction {
+                    left: p(b'A', vec![]),
+                    right: p(b'B', vec![]),
+                }
+                .into(),
+                right: p(b'C', vec![]),
+            }
+            .into(),
+            right: Negation {
+                right: p(b'D', vec![]),
+            }
+            .into(),
+        }
+        .into();
+        let fragments = classify(&formula);
+        assert!(fragments.propositional && fragments.horn && fragments.is_decidable());
+        assert!(!fragments.definite);
+
+        // A ∨ B is not Horn, but A ∨ ¬B is definite.
+        let a_or_b: GenericFormula = Disjunction {
+            left: p(b'A', vec![]),
+            right: p(b'B', vec![]),
+        }
+        .into();
+        assert!(!classify(&a_or_b).horn);
+        let a_or_not_b: GenericFormula = Disjunction {
+            left: p(b'A', vec![]),
+            right: Negation {
+                right: p(b'B', vec![]),
+            }
+            .into(),
+        }
+        .into();
+        assert!(classify(&a_or_not_b).definite);
+
+        let constant: GenericTerm = Constant { label: b'a'.into() }.into();
+        let equality: GenericFormula = Equality {
+            left: constant.clone(),
+            right: constant,
+        }
+        .into();
+        assert!(!classify(&equality).propositional);
+    }
+
+    #[test]
+    fn test_quantifier_fragments() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let z = Variable::new(b'z'.into());
+        let r = |s: Variable, t: Variable| p(b'R', vec![s.into(), t.into()]);
+
+        // ∀x.∀y.(R(x, y) ⇒ ∃z.R(y, z)) is guarded, but neither EPR nor
+        // two-variable.
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Universal {
+                left: y,
+                right: Implication {
+                    left: r(x, y),
+                    right: Existential {
+                        left: z,
+                        right: r(y, z),
+                    }
+                    .into(),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        let fragments = classify(&formula);
+        assert!(fragments.guarded && !fragments.epr && !fragments.two_variable);
+        assert!(!fragments.universal && !fragments.existential && !fragments.monadic);
+        assert_eq!(fragments.alternation_depth, 1);
+
+        // Quantifying it by ∃w is unguarded.
+        let formula: GenericFormula = Existential {
+            left: Variable::new(b'w'.into()),
+            right: formula,
+        }
+        .into();
+        let fragments = classify(&formula);
+        assert!(!fragments.guarded);
+        assert_eq!(fragments.alternation_depth, 2);
+
+        // ∀x.∀y.R(x, y) is universal, EPR and two-variable, but not guarded.
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Universal {
+                left: y,
+                right: r(x, y),
+            }
+            .into(),
+        }
+        .into();
+        let fragments = classify(&formula);
+        assert!(fragments.universal && fragments.epr && fragments.two_variable);
+        assert!(!fragments.guarded);
+        assert_eq!(fragments.alternation_depth, 0);
+
+        // ∀x.P(f(x)) is monadic in its predicates, but has a function.
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: p(
+                b'P',
+                vec![FunctionCall {
+                    function: b'f'.into(),
+                    terms: vec![x.into()],
+                }
+                .into()],
+            ),
+        }
+        .into();
+        let fragments = classify(&formula);
+        assert!(!fragments.monadic && !fragments.epr && !fragments.is_decidable());
+        assert!(fragments.definite);
+    }
+
+    #[test]
+    fn test_free_variables_and_nested_guards() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let r = |s: Variable, t: Variable| p(b'R', vec![s.into(), t.into()]);
+
+        // ∃y.R(x, y) with x free is classified as its closure ∀x.∃y.R(x, y).
+        let open: GenericFormula = Existential {
+            left: y,
+            right: r(x, y),
+        }
+        .into();
+        let closed: GenericFormula = Universal {
+            left: x,
+            right: open.clone(),
+        }
+        .into();
+        assert_eq!(classify(&open), classify(&closed));
+        assert!(!classify(&open).epr);
+        assert_eq!(classify(&open).alternation_depth, 1);
+
+        // ∀x.∀y.((¬R(x, y) ∨ P(x)) ∨ P(y)) is guarded by R(x, y), though it
+        // is not a direct operand of the outer disjunction.
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Universal {
+                left: y,
+                right: Disjunction {
+                    left: Disjunction {
+                        left: Negation { right: r(x, y) }.into(),
+                        right: p(b'P', vec![x.into()]),
+                    }
+                    .into(),
+                    right: p(b'P', vec![y.into()]),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        assert!(classify(&formula).guarded);
+
+        // With R(x, x) as the guard, P(y) is not covered.
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Universal {
+                left: y,
+                right: Disjunction {
+                    left: Disjunction {
+                        left: Negation { right: r(x, x) }.into(),
+                        right: p(b'P', vec![x.into()]),
+                    }
+                    .into(),
+                    right: p(b'P', vec![y.into()]),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        assert!(!classify(&formula).guarded);
+    }
+
+    #[test]
+    fn test_clauses() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let r = |s: Variable, t: Variable| {
+            Literal::Atom(
+                PredicateCall {
+                    predicate: b'R'.into(),
+                    terms: vec![s.into(), t.into()],
+                }
+                .into(),
+            )
+        };
+        let q = |s: Variable| {
+            Literal::Atom(
+                PredicateCall {
+                    predicate: b'Q'.into(),
+                    terms: vec![s.into()],
+                }
+                .into(),
+            )
+        };
+
+        // ¬R(x, y) ∨ Q(y), and Q(x) ∨ Q(y).
+        let clauses = vec![vec![r(x, y).negate(), q(y)], vec![q(x), q(y)]];
+        let fragments = classify_clauses(&clauses);
+        assert!(fragments.universal && fragments.epr && fragments.two_variable);
+        assert!(!fragments.horn && !fragments.guarded && !fragments.existential);
+
+        let fragments = classify_clauses(&clauses[..1].to_vec());
+        assert!(fragments.definite && fragments.guarded);
+    }
+}
@@ -13,7 +13,7 @@ mod atomic {
     pub use predicate_call::PredicateCall;
 
     /// A dynamically-typed atomic formula.
-    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     pub enum GenericAtomicFormula {
         /// An equality, such as x = y
         Equality(Equality<GenericTerm, GenericTerm>),
@@ -47,6 +47,35 @@ mod atomic {
         }
     }
 
+    impl GenericAtomicFormula {
+        /// Whether the atomic formula contains no variables.
+        pub fn is_ground(&self) -> bool {
+            match self {
+                Self::Equality(e) => e.left.is_ground() && e.right.is_ground(),
+                Self::Predicate(p) => p.terms.iter().all(GenericTerm::is_ground),
+                Self::True | Self::False => true,
+            }
+        }
+
+        /// The variables of the atomic formula, in order of first occurrence.
+        pub fn variables(&self) -> Vec<Variable> {
+            let mut variables = Vec::new();
+            self.collect_variables(&mut variables);
+            variables
+        }
+
+        pub(crate) fn collect_variables(&self, out: &mut Vec<Variable>) {
+            match self {
+                Self::Equality(e) => {
+                    e.left.collect_variables(out);
+                    e.right.collect_variables(out);
+                }
+                Self::Predicate(p) => p.terms.iter().for_each(|t| t.collect_variables(out)),
+                Self::True | Self::False => {}
+            }
+        }
+    }
+
     impl Replace for GenericAtomicFormula {
         fn replace(&mut self, old: Variable, right: Variable) {
             match self {
@@ -84,7 +113,7 @@ mod terms {
     use super::Replace;
 
     /// A dynamically typed formula term.
-    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     pub enum GenericTerm {
         /// A variable
         Variable(Variable),
@@ -94,6 +123,45 @@ mod terms {
         FunctionCall(Box<FunctionCall<GenericTerm>>),
     }
 
+    impl GenericTerm {
+        /// Whether the term contains no variables.
+        pub fn is_ground(&self) -> bool {
+            match self {
+                Self::Variable(_) => false,
+                Self::Constant(_) => true,
+                Self::FunctionCall(f) => f.terms.iter().all(Self::is_ground),
+            }
+        }
+
+        /// Whether a variable occurs in the term.
+        pub fn contains(&self, variable: &Variable) -> bool {
+            match self {
+                Self::Variable(v) => v == variable,
+                Self::Constant(_) => false,
+                Self::FunctionCall(f) => f.terms.iter().any(|t| t.contains(variable)),
+            }
+        }
+
+        /// The variables of the term, in order of first occurrence.
+        pub fn variables(&self) -> Vec<Variable> {
+            let mut variables = Vec::new();
+            self.collect_variables(&mut variables);
+            variables
+        }
+
+        pub(crate) fn collect_variables(&self, out: &mut Vec<Variable>) {
+            match self {
+                Self::Variable(v) => {
+                    if !out.contains(v) {
+                        out.push(*v)
+                    }
+                }
+                Self::Constant(_) => {}
+                Self::FunctionCall(f) => f.terms.iter().for_each(|t| t.collect_variables(out)),
+            }
+        }
+    }
+
     impl Replace for GenericTerm {
         fn replace(&mut self, old: Variable, right: Variable) {
             match self {
@@ -223,6 +291,53 @@ mod formula {
         }
     }
 
+    impl GenericFormula {
+        /// The variables which occur free in the formula, in order of first
+        /// occurrence.
+        pub fn free_variables(&self) -> Vec<Variable> {
+            let mut variables = Vec::new();
+            self.collect_free_variables(&mut Vec::new(), &mut variables);
+            variables
+        }
+
+        fn collect_free_variables(&self, bound: &mut Vec<Variable>, out: &mut Vec<Variable>) {
+            match self {
+                Self::Atomic(x) => {
+                    let mut variables = Vec::new();
+                    x.collect_variables(&mut variables);
+                    for v in variables {
+                        if !bound.contains(&v) && !out.contains(&v) {
+                            out.push(v);
+                        }
+                    }
+                }
+                Self::Universal(x) => {
+                    bound.push(x.left);
+                    x.right.collect_free_variables(bound, out);
+                    bound.pop();
+                }
+                Self::Existential(x) => {
+                    bound.push(x.left);
+                    x.right.collect_free_variables(bound, out);
+                    bound.pop();
+                }
+                Self::Conjunction(x) => {
+                    x.left.collect_free_variables(bound, out);
+                    x.right.collect_free_variables(bound, out);
+                }
+                Self::Disjunction(x) => {
+                    x.left.collect_free_variables(bound, out);
+                    x.right.collect_free_variables(bound, out);
+                }
+                Self::Implication(x) => {
+                    x.left.collect_free_variables(bound, out);
+                    x.right.collect_free_variables(bound, out);
+                }
+                Self::Negation(x) => x.right.collect_free_variables(bound, out),
+            }
+        }
+    }
+
     impl Replace for GenericFormula {
         fn replace(&mut self, old: Variable, right: Variable) {
             match self {
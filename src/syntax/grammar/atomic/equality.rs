@@ -3,7 +3,7 @@ use std::fmt::Display;
 use crate::syntax::{grammar::terms::Variable, Replace};
 
 /// A syntax node for an equality between to other nodes.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Equality<Left, Right> {
     /// The left item of the equality
     pub left: Left,
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use crate::syntax::{grammar::terms::Variable, Replace};
 
 /// A syntax node for a predicate call.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PredicateCall<T> {
     /// The label of the predicate being called.
     pub predicate: u64,
@@ -5,7 +5,7 @@ use crate::syntax::Replace;
 use super::variable::Variable;
 
 /// A syntax node for a constant.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Constant {
     /// The label of the constant.
     pub label: u64,
@@ -5,7 +5,7 @@ use crate::syntax::Replace;
 use super::variable::Variable;
 
 /// A syntax node for a function call, such as `f(x, y)`
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FunctionCall<T> {
     /// The label of the function - the 'f' in `f(x, y)`
     pub function: u64,
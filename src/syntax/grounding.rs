@@ -0,0 +1,409 @@
+use std::fmt::Display;
+
+use super::{
+    metrics::size,
+    unification::{Substitute, Substitution},
+    Conjunction, Constant, Disjunction, GenericAtomicFormula, GenericFormula, Implication,
+    Negation, Variable,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////
+
+/// The reasons grounding can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroundingError {
+    /// The grounded formula would be larger than the size budget.
+    TooLarge(usize),
+}
+
+impl Display for GroundingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLarge(budget) => f.write_fmt(format_args!(
+                "grounding exceeds the size budget of {}",
+                budget
+            )),
+        }
+    }
+}
+
+impl std::error::Error for GroundingError {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Grounder
+////////////////////////////////////////////////////////////////////////////////
+
+/// Grounding of quantified formulas over a finite set of constants.
+///
+/// A universal formula `∀x.φ` becomes the conjunction of `φ[x ↦ c]` for each
+/// constant `c` of the domain, and an existential formula the disjunction.
+/// Over an empty domain they become `⊤` and `⊥`. Free variables are left in
+/// place, so the result of grounding a sentence is ground.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Grounder {
+    /// Whether to simplify `⊤` and `⊥` away while expanding, and replace
+    /// equalities between identical terms with `⊤`. This skips expanding the
+    /// rest of a universal formula once an instance is `⊥`, and of an
+    /// existential formula once an instance is `⊤`.
+    pub simplify: bool,
+    /// The greatest size, counted as in
+    /// [`Metrics::size`](super::metrics::Metrics::size), of the grounded
+    /// formula. Expansion stops as soon as it is exceeded.
+    pub max_size: Option<usize>,
+}
+
+/// The kinds of binary connective.
+#[derive(Clone, Copy)]
+enum Connective {
+    And,
+    Or,
+    Implies,
+}
+
+impl Grounder {
+    /// Ground a formula over a domain of constants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use first_order_logic::syntax::{
+    /// #   grounding::Grounder, Constant, Equality, Existential, GenericAtomicFormula,
+    /// #   GenericFormula, Universal, Variable,
+    /// # };
+    /// let x = Variable::new(b'x'.into());
+    /// let y = Variable::new(b'y'.into());
+    /// let domain = [Constant { label: 1 }, Constant { label: 2 }];
+    ///
+    /// // ∀x.∃y.x = y
+    /// let formula: GenericFormula = Universal {
+    ///     left: x,
+    ///     right: Existential {
+    ///         left: y,
+    ///         right: Equality { left: x.into(), right: y.into() }.into(),
+    ///     }
+    ///     .into(),
+    /// }
+    /// .into();
+    ///
+    /// let grounder = Grounder { simplify: true, ..Default::default() };
+    /// assert_eq!(
+    ///     grounder.ground(&formula, &domain),
+    ///     Ok(GenericAtomicFormula::True.into())
+    /// );
+    /// ```
+    pub fn ground(
+        &self,
+        formula: &GenericFormula,
+        domain: &[Constant],
+    ) -> Result<GenericFormula, GroundingError> {
+        self.expand(formula, domain, &mut Substitution::new())
+            .map(|(formula, _)| formula)
+    }
+
+    /// Ground a formula with its bound variables replaced by the constants
+    /// in `bindings`, returning it with its size.
+    fn expand(
+        &self,
+        formula: &GenericFormula,
+        domain: &[Constant],
+        bindings: &mut Substitution,
+    ) -> Result<(GenericFormula, usize), GroundingError> {
+        match formula {
+            GenericFormula::Atomic(x) => {
+                let atom = match x.substitute(bindings) {
+                    GenericAtomicFormula::Equality(e) if self.simplify && e.left == e.right => {
+                        GenericAtomicFormula::True
+                    }
+                    atom => atom,
+                };
+                self.sized(atom.into())
+            }
+            GenericFormula::Universal(x) => {
+                self.expand_quantifier(x.left, &x.right, domain, bindings, Connective::And)
+            }
+            GenericFormula::Existential(x) => {
+                self.expand_quantifier(x.left, &x.right, domain, bindings, Connective::Or)
+            }
+            GenericFormula::Conjunction(x) => {
+                self.expand_binary(&x.left, &x.right, domain, bindings, Connective::And)
+            }
+            GenericFormula::Disjunction(x) => {
+                self.expand_binary(&x.left, &x.right, domain, bindings, Connective::Or)
+            }
+            GenericFormula::Implication(x) => {
+                self.expand_binary(&x.left, &x.right, domain, bindings, Connective::Implies)
+            }
+            GenericFormula::Negation(x) => {
+                let (right, s) = self.expand(&x.right, domain, bindings)?;
+                match right {
+                    GenericFormula::Atomic(GenericAtomicFormula::True) if self.simplify => {
+                        self.sized(GenericAtomicFormula::False.into())
+                    }
+                    GenericFormula::Atomic(GenericAtomicFormula::False) if self.simplify => {
+                        self.sized(GenericAtomicFormula::True.into())
+                    }
+                    right => self.check((Negation { right }.into(), s + 1)),
+                }
+            }
+        }
+    }
+
+    fn expand_binary(
+        &self,
+        left: &GenericFormula,
+        right: &GenericFormula,
+        domain: &[Constant],
+        bindings: &mut Substitution,
+        connective: Connective,
+    ) -> Result<(GenericFormula, usize), GroundingError> {
+        let left = self.expand(left, domain, bindings)?;
+        if self.absorbs(&left.0, connective) {
+            return match connective {
+                Connective::Implies => self.sized(GenericAtomicFormula::True.into()),
+                Connective::And | Connective::Or => Ok(left),
+            };
+        }
+        let right = self.expand(right, domain, bindings)?;
+        self.combine(left, right, connective)
+    }
+
+    /// Expand a quantifier into the conjunction or disjunction of its
+    /// instances.
+    fn expand_quantifier(
+        &self,
+        variable: Variable,
+        body: &GenericFormula,
+        domain: &[Constant],
+        bindings: &mut Substitution,
+        connective: Connective,
+    ) -> Result<(GenericFormula, usize), GroundingError> {
+        let previous = bindings.remove(&variable);
+        let result = self.instances(variable, body, domain, bindings, connective);
+        bindings.remove(&variable);
+        if let Some(term) = previous {
+            bindings.insert(variable, term);
+        }
+        result
+    }
+
+    fn instances(
+        &self,
+        variable: Variable,
+        body: &GenericFormula,
+        domain: &[Constant],
+        bindings: &mut Substitution,
+        connective: Connective,
+    ) -> Result<(GenericFormula, usize), GroundingError> {
+        let mut result: Option<(GenericFormula, usize)> = None;
+        for c in domain {
+            bindings.insert(variable, (*c).into());
+            let instance = self.expand(body, domain, bindings)?;
+            let combined = match result {
+                None => instance,
+                Some(acc) => self.combine(acc, instance, connective)?,
+            };
+            if self.absorbs(&combined.0, connective) {
+                return Ok(combined);
+            }
+            result = Some(combined);
+        }
+        match result {
+            Some(result) => Ok(result),
+            None => self.sized(neutral(connective).into()),
+        }
+    }
+
+    /// Whether `left` fixes the value of `left ∘ right` whatever `right` is,
+    /// when simplifying.
+    fn absorbs(&self, left: &GenericFormula, connective: Connective) -> bool {
+        let value = match connective {
+            Connective::And | Connective::Implies => GenericAtomicFormula::False,
+            Connective::Or => GenericAtomicFormula::True,
+        };
+        self.simplify && matches!(left, GenericFormula::Atomic(x) if *x == value)
+    }
+
+    fn combine(
+        &self,
+        (left, l): (GenericFormula, usize),
+        (right, r): (GenericFormula, usize),
+        connective: Connective,
+    ) -> Result<(GenericFormula, usize), GroundingError> {
+        use GenericAtomicFormula::{False, True};
+        if self.simplify {
+            match (connective, &left, &right) {
+                (Connective::And, GenericFormula::Atomic(True), _)
+                | (Connective::Or, GenericFormula::Atomic(False), _)
+                | (Connective::Implies, GenericFormula::Atomic(True), _) => return Ok((right, r)),
+                (Connective::And, _, GenericFormula::Atomic(True))
+                | (Connective::Or, _, GenericFormula::Atomic(False)) => return Ok((left, l)),
+                (Connective::And, _, GenericFormula::Atomic(False))
+                | (Connective::Or, _, GenericFormula::Atomic(True))
+                | (Connective::Implies, _, GenericFormula::Atomic(True)) => {
+                    return self.sized(right)
+                }
+                (Connective::Implies, _, GenericFormula::Atomic(False)) => {
+                    return self.check((Negation { right: left }.into(), l + 1))
+                }
+                _ => {}
+            }
+        }
+        let formula = match connective {
+            Connective::And => Conjunction { left, right }.into(),
+            Connective::Or => Disjunction { left, right }.into(),
+            Connective::Implies => Implication { left, right }.into(),
+        };
+        self.check((formula, l + r + 1))
+    }
+
+    fn sized(&self, formula: GenericFormula) -> Result<(GenericFormula, usize), GroundingError> {
+        let s = size(&formula);
+        self.check((formula, s))
+    }
+
+    fn check(
+        &self,
+        result: (GenericFormula, usize),
+    ) -> Result<(GenericFormula, usize), GroundingError> {
+        match self.max_size {
+            Some(budget) if result.1 > budget => Err(GroundingError::TooLarge(budget)),
+            _ => Ok(result),
+        }
+    }
+}
+
+/// The value of an empty conjunction or disjunction.
+fn neutral(connective: Connective) -> GenericAtomicFormula {
+    match connective {
+        Connective::Or => GenericAtomicFormula::False,
+        Connective::And | Connective::Implies => GenericAtomicFormula::True,
+    }
+}
+
+/// Ground a formula over a domain of constants, without simplification or a
+/// size budget. See [`Grounder`].
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   grounding::ground, Conjunction, Constant, GenericFormula, PredicateCall, Universal,
+/// #   Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let p = |t| -> GenericFormula { PredicateCall { predicate: b'P'.into(), terms: vec![t] }.into() };
+/// let (a, b) = (Constant { label: 1 }, Constant { label: 2 });
+///
+/// let formula: GenericFormula = Universal { left: x, right: p(x.into()) }.into();
+/// let grounded: GenericFormula = Conjunction { left: p(a.into()), right: p(b.into()) }.into();
+/// assert_eq!(ground(&formula, &[a, b]), grounded);
+/// ```
+pub fn ground(formula: &GenericFormula, domain: &[Constant]) -> GenericFormula {
+    Grounder::default()
+        .ground(formula, domain)
+        .expect("there is no size budget")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        metrics::Measure, propositional::equivalent, Conjunction, Constant, Disjunction,
+        Existential, GenericAtomicFormula, GenericFormula, GenericTerm, Implication, Negation,
+        PredicateCall, Universal, Variable,
+    };
+
+    use super::{ground, Grounder, GroundingError};
+
+    fn r(left: GenericTerm, right: GenericTerm) -> GenericFormula {
+        PredicateCall {
+            predicate: b'R'.into(),
+            terms: vec![left, right],
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_simplification_preserves_meaning() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let domain: Vec<Constant> = (1..=3).map(|label| Constant { label }).collect();
+
+        // ∀x.(∃y.(R(x, y) ∨ ⊥) ⇒ ∀x.(R(x, x) ∧ ¬⊥)). The atoms are treated
+        // propositionally, so an equality x = x cannot be used here.
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Implication {
+                left: Existential {
+                    left: y,
+                    right: Disjunction {
+                        left: r(x.into(), y.into()),
+                        right: GenericAtomicFormula::False.into(),
+                    }
+                    .into(),
+                }
+                .into(),
+                right: Universal {
+                    left: x,
+                    right: Conjunction {
+                        left: r(x.into(), x.into()),
+                        right: Negation {
+                            right: GenericAtomicFormula::False.into(),
+                        }
+                        .into(),
+                    }
+                    .into(),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+
+        let expanded = ground(&formula, &domain);
+        assert!(expanded.free_variables().is_empty());
+        let simplified = Grounder {
+            simplify: true,
+            max_size: None,
+        }
+        .ground(&formula, &domain)
+        .unwrap();
+        assert!(simplified.metrics().size < expanded.metrics().size);
+        assert!(equivalent(&expanded, &simplified).unwrap());
+
+        assert_eq!(
+            ground(&formula, &[]),
+            GenericFormula::from(GenericAtomicFormula::True)
+        );
+    }
+
+    #[test]
+    fn test_size_budget() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let domain: Vec<Constant> = (1..=10).map(|label| Constant { label }).collect();
+
+        // ∀x.∀y.R(x, y) has 100 atoms of size three, and 99 conjunctions.
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Universal {
+                left: y,
+                right: r(x.into(), y.into()),
+            }
+            .into(),
+        }
+        .into();
+        assert_eq!(ground(&formula, &domain).metrics().size, 399);
+
+        let grounder = |max_size| Grounder {
+            simplify: false,
+            max_size: Some(max_size),
+        };
+        assert!(grounder(399).ground(&formula, &domain).is_ok());
+        assert_eq!(
+            grounder(398).ground(&formula, &domain),
+            Err(GroundingError::TooLarge(398))
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use super::{
+    normal_forms::{ClauseNormalForm, Literal},
+    unification::Substitution,
+    Constant, FunctionCall, GenericTerm, Signature, Variable,
+};
+
+/// Advance an odometer over tuples of `length` digits in `0..base`, starting
+/// from all zeros. Returns false when every tuple has been visited.
+pub(crate) fn advance(tuple: &mut Option<Vec<usize>>, length: usize, base: usize) -> bool {
+    match tuple {
+        None => {
+            *tuple = Some(vec![0; length]);
+            length == 0 || base > 0
+        }
+        Some(digits) => {
+            for digit in digits.iter_mut().rev() {
+                *digit += 1;
+                if *digit < base {
+                    return true;
+                }
+                *digit = 0;
+            }
+            false
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Herbrand Universe
+////////////////////////////////////////////////////////////////////////////////
+
+/// A lazy enumeration of the Herbrand universe of a signature: every ground
+/// term built from its constants and functions.
+///
+/// Terms are produced in order of depth, so every term is eventually
+/// produced: the constants first, then the terms with function calls nested
+/// one deep, and so on. If the signature has no constants, a fresh one is
+/// added, as the Herbrand universe is never empty. The universe is finite
+/// exactly when there are no functions.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   herbrand::HerbrandUniverse, Constant, FunctionCall, Signature,
+/// # };
+/// let a = Constant { label: b'a'.into() };
+/// let mut signature = Signature::default();
+/// signature.extend_from_term(&FunctionCall { function: b'f'.into(), terms: vec![a.into()] }.into());
+///
+/// let terms: Vec<String> = HerbrandUniverse::new(&signature)
+///     .take(3)
+///     .map(|t| t.to_string())
+///     .collect();
+/// assert_eq!(terms, ["97", "f(97)", "f(f(97))"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HerbrandUniverse {
+    /// The functions of the signature with at least one argument.
+    functions: Vec<(u64, usize)>,
+    /// Every term generated so far, in order of depth.
+    terms: Vec<GenericTerm>,
+    /// The number of terms which have been returned.
+    emitted: usize,
+    /// The terms of the layer being generated have arguments from
+    /// `terms[..available]`, at least one of which is from
+    /// `terms[previous..available]`.
+    previous: usize,
+    available: usize,
+    /// The depth of the layer being generated.
+    depth: usize,
+    max_depth: Option<usize>,
+    function: usize,
+    tuple: Option<Vec<usize>>,
+}
+
+impl HerbrandUniverse {
+    /// Enumerate the Herbrand universe of a signature.
+    pub fn new(signature: &Signature) -> Self {
+        let mut terms: Vec<GenericTerm> = signature
+            .constants
+            .keys()
+            .map(|label| Constant { label: *label }.into())
+            .collect();
+        if terms.is_empty() {
+            terms.push(
+                Constant {
+                    label: rand::random(),
+                }
+                .into(),
+            );
+        }
+        let functions = signature
+            .functions
+            .iter()
+            .filter(|(_, symbol)| symbol.arity > 0)
+            .map(|(label, symbol)| (*label, symbol.arity))
+            .collect();
+        Self {
+            functions,
+            available: terms.len(),
+            terms,
+            emitted: 0,
+            previous: 0,
+            depth: 1,
+            max_depth: None,
+            function: 0,
+            tuple: None,
+        }
+    }
+
+    /// Only enumerate the terms with function calls nested at most `depth`
+    /// deep.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Generate the next term of the universe, if there is one.
+    fn generate(&mut self) -> Option<GenericTerm> {
+        loop {
+            if self.max_depth.is_some_and(|max| self.depth > max) {
+                return None;
+            }
+            if self.function >= self.functions.len() {
+                // The layer is finished. An empty layer means there are no
+                // functions, and so no deeper terms either.
+                if self.terms.len() == self.available {
+                    return None;
+                }
+                self.previous = self.available;
+                self.available = self.terms.len();
+                self.depth += 1;
+                self.function = 0;
+                self.tuple = None;
+                continue;
+            }
+
+            let (function, arity) = self.functions[self.function];
+            if !advance(&mut self.tuple, arity, self.available) {
+                self.function += 1;
+                self.tuple = None;
+                continue;
+            }
+            let tuple = self.tuple.as_ref().unwrap();
+            if tuple.iter().all(|i| *i < self.previous) {
+                // Every argument is shallower than this layer.
+                continue;
+            }
+            let term: GenericTerm = FunctionCall {
+                function,
+                terms: tuple.iter().map(|i| self.terms[*i].clone()).collect(),
+            }
+            .into();
+            self.terms.push(term.clone());
+            return Some(term);
+        }
+    }
+}
+
+impl Iterator for HerbrandUniverse {
+    type Item = GenericTerm;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.emitted < self.terms.len() {
+            self.emitted += 1;
+            return Some(self.terms[self.emitted - 1].clone());
+        }
+        let term = self.generate()?;
+        self.emitted += 1;
+        Some(term)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Ground Instances
+////////////////////////////////////////////////////////////////////////////////
+
+/// A ground instance of a clause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroundInstance {
+    /// The index of the clause it is an instance of.
+    pub clause: usize,
+    /// The substitution of ground terms for the variables of the clause.
+    pub substitution: Substitution,
+    /// The literals of the instance.
+    pub literals: Vec<Literal>,
+}
+
+/// A lazy, fair enumeration of the ground instances of a set of clauses over
+/// its Herbrand universe.
+///
+/// The enumeration works in stages. Stage `n` uses the first `n` terms of the
+/// Herbrand universe, and produces the instances of every clause which use
+/// the `n`th term, so every ground instance of every clause is eventually
+/// produced, and each exactly once.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   herbrand::GroundInstances, normal_forms::Literal, Constant, FunctionCall,
+/// #   PredicateCall, Signature, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let a = Constant { label: b'a'.into() };
+/// let p = |t| Literal::Atom(PredicateCall { predicate: b'P'.into(), terms: vec![t] }.into());
+/// let clauses = vec![
+///     vec![p(a.into())],
+///     vec![p(FunctionCall { function: b'f'.into(), terms: vec![x.into()] }.into()).negate()],
+/// ];
+/// let mut signature = Signature::default();
+/// signature.extend_from_literals(clauses.iter().flatten());
+///
+/// let instances: Vec<String> = GroundInstances::new(&clauses, &signature)
+///     .take(3)
+///     .map(|i| format!("{}: {}", i.clause, i.literals[0]))
+///     .collect();
+/// assert_eq!(instances, ["0: P(97)", "1: ¬(P(f(97)))", "1: ¬(P(f(f(97))))"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GroundInstances<'a> {
+    clauses: &'a ClauseNormalForm,
+    /// The variables of each clause.
+    variables: Vec<Vec<Variable>>,
+    universe: HerbrandUniverse,
+    /// The terms of the universe used so far.
+    terms: Vec<GenericTerm>,
+    clause: usize,
+    tuple: Option<Vec<usize>>,
+}
+
+impl<'a> GroundInstances<'a> {
+    /// Enumerate the ground instances of some clauses over the Herbrand
+    /// universe of their signature.
+    pub fn new(clauses: &'a ClauseNormalForm, signature: &Signature) -> Self {
+        Self::with_universe(clauses, HerbrandUniverse::new(signature))
+    }
+
+    /// Enumerate the ground instances of some clauses over a given universe,
+    /// such as one with a maximum depth.
+    pub fn with_universe(clauses: &'a ClauseNormalForm, universe: HerbrandUniverse) -> Self {
+        let variables = clauses
+            .iter()
+            .map(|clause| {
+                let mut variables = Vec::new();
+                for literal in clause {
+                    for v in literal.atom().variables() {
+                        if !variables.contains(&v) {
+                            variables.push(v);
+                        }
+                    }
+                }
+                variables
+            })
+            .collect();
+        Self {
+            clauses,
+            variables,
+            universe,
+            terms: Vec::new(),
+            clause: clauses.len(),
+            tuple: None,
+        }
+    }
+}
+
+impl Iterator for GroundInstances<'_> {
+    type Item = GroundInstance;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.clause >= self.clauses.len() {
+                // Start the next stage.
+                self.terms.push(self.universe.next()?);
+                self.clause = 0;
+                self.tuple = None;
+            }
+
+            let n = self.terms.len();
+            let variables = &self.variables[self.clause];
+            if !advance(&mut self.tuple, variables.len(), n) {
+                self.clause += 1;
+                self.tuple = None;
+                continue;
+            }
+            let tuple = self.tuple.as_ref().unwrap();
+            let new = if variables.is_empty() {
+                // Ground clauses are their own only instance.
+                n == 1
+            } else {
+                tuple.contains(&(n - 1))
+            };
+            if !new {
+                continue;
+            }
+
+            let substitution: Substitution = variables
+                .iter()
+                .zip(tuple)
+                .map(|(v, i)| (*v, self.terms[*i].clone()))
+                .collect();
+            let literals = self.clauses[self.clause]
+                .iter()
+                .map(|l| substitution.apply(l))
+                .collect();
+            return Some(GroundInstance {
+                clause: self.clause,
+                substitution,
+                literals,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::syntax::{
+        normal_forms::Literal, Constant, FunctionCall, GenericTerm, PredicateCall, Signature,
+        Variable,
+    };
+
+    use super::{GroundInstances, HerbrandUniverse};
+
+    /// The signature with constants `a` and `b`, and functions `f/1` and
+    /// `g/2`.
+    fn signature() -> Signature {
+        let a: GenericTerm = Constant { label: b'a'.into() }.into();
+        let b: GenericTerm = Constant { label: b'b'.into() }.into();
+        let mut signature = Signature::default();
+        signature.extend_from_term(
+            &FunctionCall {
+                function: b'g'.into(),
+                terms: vec![
+                    a,
+                    FunctionCall {
+                        function: b'f'.into(),
+                        terms: vec![b],
+                    }
+                    .into(),
+                ],
+            }
+            .into(),
+        );
+        signature
+    }
+
+    fn depth(term: &GenericTerm) -> usize {
+        match term {
+            GenericTerm::FunctionCall(f) => 1 + f.terms.iter().map(depth).max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn test_universe_by_depth() {
+        let terms: Vec<GenericTerm> = HerbrandUniverse::new(&signature()).max_depth(2).collect();
+
+        // Depth 0: a, b. Depth 1: f(a), f(b) and the 4 g(s, t).
+        // Depth 2: f of the 6 terms of depth 1, and the 8² - 2² g(s, t) with
+        // an argument of depth 1.
+        let counts: Vec<usize> = (0..=2)
+            .map(|d| terms.iter().filter(|t| depth(t) == d).count())
+            .collect();
+        assert_eq!(counts, [2, 6, 66]);
+        assert!(terms.windows(2).all(|w| depth(&w[0]) <= depth(&w[1])));
+        assert_eq!(terms.iter().collect::<HashSet<_>>().len(), terms.len());
+
+        // Without functions, the universe is finite.
+        let mut constants = Signature::default();
+        constants.name_constant(1, "c");
+        assert_eq!(HerbrandUniverse::new(&constants).count(), 1);
+        assert_eq!(HerbrandUniverse::new(&Signature::default()).count(), 1);
+    }
+
+    #[test]
+    fn test_ground_instances_are_fair() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let r = |s: GenericTerm, t: GenericTerm| {
+            Literal::Atom(
+                PredicateCall {
+                    predicate: b'R'.into(),
+                    terms: vec![s, t],
+                }
+                .into(),
+            )
+        };
+        let clauses = vec![vec![r(x.into(), y.into())], vec![r(x.into(), x.into())]];
+        let signature = signature();
+
+        // R(g(a, b), f(a)) uses the 6th and 3rd terms, so is produced by
+        // stage 6, along with all 36 instances of the first clause.
+        let target = r(
+            FunctionCall {
+                function: b'g'.into(),
+                terms: vec![
+                    Constant { label: b'a'.into() }.into(),
+                    Constant { label: b'b'.into() }.into(),
+                ],
+            }
+            .into(),
+            FunctionCall {
+                function: b'f'.into(),
+                terms: vec![Constant { label: b'a'.into() }.into()],
+            }
+            .into(),
+        );
+        let instances: Vec<_> = GroundInstances::new(&clauses, &signature)
+            .take(36 + 6)
+            .collect();
+        assert!(instances
+            .iter()
+            .any(|i| i.clause == 0 && i.literals == vec![target.clone()]));
+        assert_eq!(instances.iter().filter(|i| i.clause == 1).count(), 6);
+        let distinct: HashSet<_> = instances
+            .iter()
+            .map(|i| (i.clause, i.literals.clone()))
+            .collect();
+        assert_eq!(distinct.len(), instances.len());
+
+        // A finite universe gives finitely many instances.
+        let mut constants = Signature::default();
+        constants.name_constant(1, "c");
+        constants.name_constant(2, "d");
+        assert_eq!(GroundInstances::new(&clauses, &constants).count(), 4 + 2);
+    }
+}
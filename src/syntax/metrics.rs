@@ -0,0 +1,370 @@
+use super::{
+    normal_forms::{
+        Clause, ConjunctiveNormalFormula, PrenexNormalFormula, PrenexNormalQuantifier,
+        SkolemNormalFormula,
+    },
+    tableaux::negation_normal_form,
+    GenericAtomicFormula, GenericFormula, GenericTerm,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+// Metrics
+////////////////////////////////////////////////////////////////////////////////
+
+/// Measures of the size and shape of a formula.
+///
+/// The clause counts are those of the clause normal form obtained by
+/// distributing disjunctions over conjunctions, so they can be checked
+/// before converting a formula to a
+/// [`ConjunctiveNormalFormula`](super::normal_forms::ConjunctiveNormalFormula),
+/// which may be exponentially larger. They saturate at [`usize::MAX`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// The number of nodes in the syntax tree: connectives, quantifiers,
+    /// atoms and terms.
+    pub size: usize,
+    /// The greatest depth of a term, where variables and constants have a
+    /// depth of one. Zero if there are no terms.
+    pub term_depth: usize,
+    /// The greatest number of nested connectives, not counting quantifiers.
+    pub connective_depth: usize,
+    /// The greatest number of nested quantifiers.
+    pub quantifier_rank: usize,
+    /// The number of alternations between universal and existential
+    /// quantifiers in the prefix of the prenex normal form.
+    pub alternations: usize,
+    /// The number of clauses in the clause normal form.
+    pub clauses: usize,
+    /// The number of literals in the clause normal form.
+    pub literals: usize,
+    /// The greatest arity of a Skolem function introduced by Skolemising the
+    /// formula, with each quantifier in its scope.
+    pub skolem_arity: usize,
+}
+
+/// A formula type which can be measured.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   metrics::Measure, Conjunction, Disjunction, GenericFormula, PredicateCall,
+/// # };
+/// let p = |label: u64| -> GenericFormula { PredicateCall { predicate: label, terms: vec![] }.into() };
+/// let pair = |left, right| -> GenericFormula { Conjunction { left, right }.into() };
+///
+/// // (A ∧ B) ∨ (C ∧ D) ∨ (E ∧ F) has eight clauses of three literals.
+/// let formula: GenericFormula = Disjunction {
+///     left: Disjunction { left: pair(p(1), p(2)), right: pair(p(3), p(4)) }.into(),
+///     right: pair(p(5), p(6)),
+/// }
+/// .into();
+/// let metrics = formula.metrics();
+/// assert_eq!((metrics.clauses, metrics.literals), (8, 24));
+/// assert_eq!(metrics.connective_depth, 3);
+/// ```
+pub trait Measure {
+    /// Measure the formula.
+    fn metrics(&self) -> Metrics;
+}
+
+impl Measure for GenericFormula {
+    fn metrics(&self) -> Metrics {
+        let nnf = negation_normal_form(self.clone());
+        let (clauses, literals) = clause_counts(&nnf);
+        Metrics {
+            size: size(self),
+            term_depth: term_depth(self),
+            connective_depth: connective_depth(self),
+            quantifier_rank: quantifier_rank(self),
+            alternations: alternations(&PrenexNormalFormula::from(self.clone())),
+            clauses,
+            literals,
+            skolem_arity: skolem_arity(&nnf, 0),
+        }
+    }
+}
+
+macro_rules! impl_measure_by_conversion {
+    ($($t:ty),*) => {
+        $(
+            impl Measure for $t {
+                fn metrics(&self) -> Metrics {
+                    GenericFormula::from(self.clone()).metrics()
+                }
+            }
+        )*
+    };
+}
+
+impl_measure_by_conversion!(
+    PrenexNormalFormula,
+    SkolemNormalFormula,
+    ConjunctiveNormalFormula,
+    Clause
+);
+
+////////////////////////////////////////////////////////////////////////////////
+// Algorithms
+////////////////////////////////////////////////////////////////////////////////
+
+fn term_size(term: &GenericTerm) -> usize {
+    match term {
+        GenericTerm::Variable(_) | GenericTerm::Constant(_) => 1,
+        GenericTerm::FunctionCall(f) => 1 + f.terms.iter().map(term_size).sum::<usize>(),
+    }
+}
+
+fn depth_of_term(term: &GenericTerm) -> usize {
+    match term {
+        GenericTerm::Variable(_) | GenericTerm::Constant(_) => 1,
+        GenericTerm::FunctionCall(f) => 1 + f.terms.iter().map(depth_of_term).max().unwrap_or(0),
+    }
+}
+
+/// The terms of an atom.
+fn terms(atom: &GenericAtomicFormula) -> Vec<&GenericTerm> {
+    match atom {
+        GenericAtomicFormula::Equality(e) => vec![&e.left, &e.right],
+        GenericAtomicFormula::Predicate(p) => p.terms.iter().collect(),
+        GenericAtomicFormula::True | GenericAtomicFormula::False => Vec::new(),
+    }
+}
+
+/// Fold a formula, combining the values of the subformulas of each
+/// connective and quantifier.
+fn fold<T>(
+    formula: &GenericFormula,
+    atom: &impl Fn(&GenericAtomicFormula) -> T,
+    quantifier: &impl Fn(T) -> T,
+    connective: &impl Fn(T, Option<T>) -> T,
+) -> T {
+    let recurse = |f: &GenericFormula| fold(f, atom, quantifier, connective);
+    match formula {
+        GenericFormula::Atomic(x) => atom(x),
+        GenericFormula::Universal(x) => quantifier(recurse(&x.right)),
+        GenericFormula::Existential(x) => quantifier(recurse(&x.right)),
+        GenericFormula::Conjunction(x) => connective(recurse(&x.left), Some(recurse(&x.right))),
+        GenericFormula::Disjunction(x) => connective(recurse(&x.left), Some(recurse(&x.right))),
+        GenericFormula::Implication(x) => connective(recurse(&x.left), Some(recurse(&x.right))),
+        GenericFormula::Negation(x) => connective(recurse(&x.right), None),
+    }
+}
+
+pub(crate) fn size(formula: &GenericFormula) -> usize {
+    fold(
+        formula,
+        &|a| 1 + terms(a).into_iter().map(term_size).sum::<usize>(),
+        &|s| s + 1,
+        &|l, r| l + r.unwrap_or(0) + 1,
+    )
+}
+
+fn term_depth(formula: &GenericFormula) -> usize {
+    fold(
+        formula,
+        &|a| terms(a).into_iter().map(depth_of_term).max().unwrap_or(0),
+        &|d| d,
+        &|l, r| l.max(r.unwrap_or(0)),
+    )
+}
+
+fn connective_depth(formula: &GenericFormula) -> usize {
+    fold(formula, &|_| 0, &|d| d, &|l, r| l.max(r.unwrap_or(0)) + 1)
+}
+
+fn quantifier_rank(formula: &GenericFormula) -> usize {
+    fold(formula, &|_| 0, &|d| d + 1, &|l, r| l.max(r.unwrap_or(0)))
+}
+
+/// The number of alternations between universal and existential quantifiers
+/// in the prefix of a formula in prenex normal form.
+fn alternations(formula: &PrenexNormalFormula) -> usize {
+    formula
+        .quantifiers
+        .windows(2)
+        .filter(|w| {
+            matches!(w[0], PrenexNormalQuantifier::Universal(_))
+                != matches!(w[1], PrenexNormalQuantifier::Universal(_))
+        })
+        .count()
+}
+
+/// The numbers of clauses and literals in the clause normal form of a
+/// formula in negation-normal form.
+fn clause_counts(formula: &GenericFormula) -> (usize, usize) {
+    match formula {
+        GenericFormula::Atomic(GenericAtomicFormula::True) => (0, 0),
+        GenericFormula::Atomic(GenericAtomicFormula::False) => (1, 0),
+        GenericFormula::Atomic(_) | GenericFormula::Negation(_) => (1, 1),
+        GenericFormula::Universal(x) => clause_counts(&x.right),
+        GenericFormula::Existential(x) => clause_counts(&x.right),
+        GenericFormula::Conjunction(x) => {
+            let (left, right) = (clause_counts(&x.left), clause_counts(&x.right));
+            (
+                left.0.saturating_add(right.0),
+                left.1.saturating_add(right.1),
+            )
+        }
+        // Each clause of a disjunction joins a clause from each side.
+        GenericFormula::Disjunction(x) => {
+            let (left, right) = (clause_counts(&x.left), clause_counts(&x.right));
+            (
+                left.0.saturating_mul(right.0),
+                left.1
+                    .saturating_mul(right.0)
+                    .saturating_add(right.1.saturating_mul(left.0)),
+            )
+        }
+        GenericFormula::Implication(_) => unreachable!("the formula is in negation-normal form"),
+    }
+}
+
+/// The greatest number of universal quantifiers an existential quantifier is
+/// in the scope of, in a formula in negation-normal form.
+fn skolem_arity(formula: &GenericFormula, universals: usize) -> usize {
+    match formula {
+        GenericFormula::Atomic(_) | GenericFormula::Negation(_) => 0,
+        GenericFormula::Universal(x) => skolem_arity(&x.right, universals + 1),
+        GenericFormula::Existential(x) => universals.max(skolem_arity(&x.right, universals)),
+        GenericFormula::Conjunction(x) => {
+            skolem_arity(&x.left, universals).max(skolem_arity(&x.right, universals))
+        }
+        GenericFormula::Disjunction(x) => {
+            skolem_arity(&x.left, universals).max(skolem_arity(&x.right, universals))
+        }
+        GenericFormula::Implication(_) => unreachable!("the formula is in negation-normal form"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::{ConjunctiveNormalFormula, PrenexNormalFormula, SkolemNormalFormula},
+        Conjunction, Disjunction, Existential, FunctionCall, GenericFormula, GenericTerm,
+        Implication, Negation, PredicateCall, Universal, Variable,
+    };
+
+    use super::Measure;
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_metrics() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let z = Variable::new(b'z'.into());
+        let f = |t: GenericTerm| -> GenericTerm {
+            FunctionCall {
+                function: b'f'.into(),
+                terms: vec![t],
+            }
+            .into()
+        };
+
+        // ∀x.¬(∀y.P(x, y) ⇒ ∃z.P(f(f(z)), x))
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Negation {
+                right: Implication {
+                    left: Universal {
+                        left: y,
+                        right: p(b'P', vec![x.into(), y.into()]),
+                    }
+                    .into(),
+                    right: Existential {
+                        left: z,
+                        right: p(b'P', vec![f(f(z.into())), x.into()]),
+                    }
+                    .into(),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+
+        let metrics = formula.metrics();
+        // Three quantifiers, two connectives, two atoms and six terms.
+        assert_eq!(metrics.size, 13);
+        assert_eq!(metrics.term_depth, 3);
+        assert_eq!(metrics.connective_depth, 2);
+        assert_eq!(metrics.quantifier_rank, 2);
+        // ∀x.∀y.∀z.(P(x, y) ∧ ¬P(f(f(z)), x)): the negation flips both
+        // inner quantifiers.
+        assert_eq!(metrics.alternations, 0);
+        assert_eq!(metrics.skolem_arity, 0);
+        assert_eq!((metrics.clauses, metrics.literals), (2, 2));
+
+        let prenex = PrenexNormalFormula::from(formula.clone());
+        assert_eq!(prenex.metrics().alternations, 0);
+        assert_eq!(prenex.metrics().quantifier_rank, 3);
+
+        // ∀x.∃y.(P(x, y) ∨ P(y, x)) ∨ ∀z.P(z, z)
+        let formula: GenericFormula = Disjunction {
+            left: Universal {
+                left: x,
+                right: Existential {
+                    left: y,
+                    right: Disjunction {
+                        left: p(b'P', vec![x.into(), y.into()]),
+                        right: p(b'P', vec![y.into(), x.into()]),
+                    }
+                    .into(),
+                }
+                .into(),
+            }
+            .into(),
+            right: Universal {
+                left: z,
+                right: p(b'P', vec![z.into(), z.into()]),
+            }
+            .into(),
+        }
+        .into();
+        let metrics = formula.metrics();
+        // The prenex normal form puts ∀z innermost: ∀x.∃y.∀z.
+        assert_eq!(metrics.alternations, 2);
+        assert_eq!(metrics.skolem_arity, 1);
+        assert_eq!((metrics.clauses, metrics.literals), (1, 3));
+
+        let snf = SkolemNormalFormula::from(formula);
+        assert_eq!(snf.metrics().quantifier_rank, 0);
+        let cnf = ConjunctiveNormalFormula::from(snf);
+        let metrics = cnf.metrics();
+        assert_eq!((metrics.clauses, metrics.literals), (1, 3));
+        assert_eq!(metrics.skolem_arity, 0);
+    }
+
+    #[test]
+    fn test_alternations_agree_with_prenex_form() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+
+        // (∀x.P(x)) ∧ (∃y.Q(y)) has no alternations along any path, but its
+        // prenex normal form has one.
+        let formula: GenericFormula = Conjunction {
+            left: Universal {
+                left: x,
+                right: p(b'P', vec![x.into()]),
+            }
+            .into(),
+            right: Existential {
+                left: y,
+                right: p(b'Q', vec![y.into()]),
+            }
+            .into(),
+        }
+        .into();
+        let prenex = PrenexNormalFormula::from(formula.clone());
+        assert_eq!(formula.metrics().alternations, 1);
+        assert_eq!(prenex.metrics().alternations, 1);
+        assert_eq!(SkolemNormalFormula::from(prenex).metrics().alternations, 0);
+    }
+}
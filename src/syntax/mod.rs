@@ -35,4 +35,203 @@ mod grammar;
 /// direction.
 pub mod normal_forms;
 
+/// Export of ground CNFs as propositional problems in the DIMACS format.
+///
+/// A ground [`ConjunctiveNormalFormula`](normal_forms::ConjunctiveNormalFormula)
+/// has no variables, so each of its atoms can be treated as a propositional
+/// variable. [`DimacsEncoding`](dimacs::DimacsEncoding) numbers the atoms,
+/// writes the standard `p cnf` text, and reads a SAT solver's model back into
+/// an assignment over the original atoms.
+pub mod dimacs;
+
+/// Rendering of formulae as LaTeX and MathML.
+///
+/// Every formula type, including the normal forms, can be rendered through
+/// the [`Render`](render::Render) trait. Symbol names are looked up in a
+/// [`Signature`], and parentheses are only written where the precedence of the
+/// connectives requires them (`¬` binds tightest, then `∧`, `∨` and `⇒`).
+pub mod render;
+
+/// First-order unification, matching and anti-unification.
+///
+/// [`unify`](unification::unify) computes the most general unifier of two
+/// terms, atoms or literals as a [`Substitution`](unification::Substitution),
+/// which can be applied to any syntax node implementing
+/// [`Substitute`](unification::Substitute) and composed with other
+/// substitutions. [`match_term`](unification::match_term) is the one-way
+/// version, which only binds the variables of a pattern, and
+/// [`anti_unify`](unification::anti_unify) computes the least general
+/// generalisation of two nodes.
+pub mod unification;
+
+/// A resolution-based refutation prover.
+///
+/// [`ResolutionProver`](resolution::ResolutionProver) saturates a
+/// [`ClauseNormalForm`](normal_forms::ClauseNormalForm) with binary resolution
+/// and factoring, and either derives the empty clause (returning the
+/// [`Proof`](resolution::Proof)), saturates, or runs out of resources.
+/// Equality is built in, either through paramodulation or by adding the
+/// [`equality_axioms`](resolution::equality_axioms) of the input's signature.
+pub mod resolution;
+
+/// Simplification orderings on terms, for orienting equations.
+///
+/// [`KnuthBendixOrdering`](ordering::KnuthBendixOrdering) and
+/// [`LexicographicPathOrdering`](ordering::LexicographicPathOrdering) are
+/// both [`TermOrdering`](ordering::TermOrdering)s, configured by a
+/// [`Precedence`](ordering::Precedence) on symbols (and, for the Knuth–Bendix
+/// ordering, symbol weights). Every term ordering extends to literals and
+/// clauses by the multiset extension.
+pub mod ordering;
+
+/// Term rewriting and Knuth–Bendix completion.
+///
+/// A [`RewriteSystem`](rewriting::RewriteSystem) normalises terms with its
+/// [`RewriteRule`](rewriting::RewriteRule)s under an innermost or outermost
+/// [`Strategy`](rewriting::Strategy). [`complete`](rewriting::complete) turns
+/// a set of equations into a convergent system, using a
+/// [`TermOrdering`](ordering::TermOrdering) to orient them.
+pub mod rewriting;
+
+/// Congruence closure for ground equations.
+///
+/// [`CongruenceClosure`](congruence::CongruenceClosure) decides whether an
+/// equation between ground terms follows from a set of ground equations, and
+/// explains each equality by the equations it follows from. Disequations can
+/// also be asserted, and a [`Conflict`](congruence::Conflict) is reported if
+/// the equations contradict one of them.
+pub mod congruence;
+
+/// A free-variable semantic tableaux prover.
+///
+/// [`TableauProver`](tableaux::TableauProver) works on formulas in
+/// [negation-normal form](tableaux::negation_normal_form), and either closes
+/// the tableau, recording it as a [`TableauProof`](tableaux::TableauProof),
+/// or falls back to a search of ground tableaux over small finite domains,
+/// which returns a saturated open branch as a
+/// [`Countermodel`](tableaux::Countermodel). The fallback only handles
+/// formulas without function symbols or equality.
+pub mod tableaux;
+
+/// Natural deduction proofs.
+///
+/// A [`Proof`](natural_deduction::Proof) is a tree of introduction and
+/// elimination [rules](natural_deduction::Rule), with labelled assumptions
+/// which the rules discharge. Checking a proof reports the first invalid
+/// step as a [`ProofError`](natural_deduction::ProofError).
+pub mod natural_deduction;
+
+/// The sequent calculus LK.
+///
+/// A [`Derivation`](sequent::Derivation) is a tree of LK
+/// [rules](sequent::Rule), including cut and the structural rules, over
+/// [`Sequent`](sequent::Sequent)s. Derivations can be checked, printed as
+/// text or LaTeX, and found for the cut-free fragment by the bounded
+/// [`SequentProver`](sequent::SequentProver).
+pub mod sequent;
+
+/// Export of formulas and proofs in the TSTP format.
+///
+/// [`ClausifiedProblem`](tstp::ClausifiedProblem) converts a problem to
+/// clauses while remembering where each clause came from, so that a
+/// refutation of them can be written as a TSTP derivation, including the
+/// Skolemisation steps, for independent checkers to verify. Closed
+/// [tableaux](tableaux::TableauProof::to_tstp) and
+/// [sequent derivations](sequent::Derivation::to_tstp), and
+/// [natural deduction proofs](natural_deduction::Proof::to_tstp), can be
+/// exported too.
+///
+/// The other procedures have no TSTP export: the [SAT](sat) solver gives an
+/// unsatisfiable core rather than a derivation, the [EPR](epr) decision
+/// procedure gives unsatisfiable ground instances without a refutation of
+/// them, and neither [completion](rewriting) nor the explanations of
+/// [congruence closure](congruence) are written as TSTP.
+pub mod tstp;
+
+/// Herbrand universes and ground instances.
+///
+/// [`HerbrandUniverse`](herbrand::HerbrandUniverse) lazily enumerates the
+/// ground terms of a signature by depth, and
+/// [`GroundInstances`](herbrand::GroundInstances) fairly enumerates the ground
+/// instances of a set of clauses over it.
+pub mod herbrand;
+
+/// A CDCL SAT solver for ground clauses.
+///
+/// [`SatSolver`](sat::SatSolver) treats each ground atom as a propositional
+/// variable, and either finds a model of a set of clauses or an unsatisfiable
+/// core of them.
+pub mod sat;
+
+/// Finite structures and model checking.
+///
+/// A [`Structure`](structure::Structure) interprets constants, functions and
+/// predicates over a finite domain, and evaluates formulas under an
+/// [`Assignment`](structure::Assignment) of elements to their free variables.
+pub mod structure;
+
+/// A MACE-style finite model finder.
+///
+/// [`ModelFinder`](model_finder::ModelFinder) searches domains of increasing
+/// size for a [`Structure`](structure::Structure) satisfying a set of axioms,
+/// by grounding their flattened clauses and handing them to the
+/// [`SatSolver`](sat::SatSolver).
+pub mod model_finder;
+
+/// A decision procedure for the Bernays–Schönfinkel (EPR) fragment.
+///
+/// Sentences with an `∃*∀*` quantifier prefix and no function symbols have a
+/// finite Herbrand universe, so [`decide`](epr::decide) can ground them
+/// completely and either find a model or a refutation with the
+/// [`SatSolver`](sat::SatSolver).
+pub mod epr;
+
+/// Classification of formulas by syntactic fragment.
+///
+/// [`classify`](fragments::classify) and
+/// [`classify_clauses`](fragments::classify_clauses) report the
+/// [`Fragments`](fragments::Fragments) a formula or set of clauses belongs
+/// to, such as Horn, monadic or guarded, so that a decision procedure can be
+/// chosen for it.
+pub mod fragments;
+
+/// Size and shape metrics of formulas.
+///
+/// Every formula type, including the normal forms, can be measured through
+/// the [`Measure`](metrics::Measure) trait, which reports its
+/// [`Metrics`](metrics::Metrics), such as its quantifier rank or the number of
+/// clauses its clause normal form will have.
+pub mod metrics;
+
+/// Truth-table reasoning for quantifier-free, ground formulas.
+///
+/// Each ground atom is treated as a propositional variable, and
+/// [`is_satisfiable`](propositional::is_satisfiable),
+/// [`is_tautology`](propositional::is_tautology),
+/// [`equivalent`](propositional::equivalent) and
+/// [`entails`](propositional::entails) are decided by truth tables, or by the
+/// [`SatSolver`](sat::SatSolver) when there are many atoms.
+pub mod propositional;
+
+/// Grounding of quantified formulas over a finite set of constants.
+///
+/// [`ground`](grounding::ground) expands universal formulas into
+/// conjunctions and existential formulas into disjunctions of their
+/// instances, so that the result can be handed to
+/// [`propositional`] reasoning. A [`Grounder`](grounding::Grounder) can also
+/// simplify while expanding and stop at a size budget.
+pub mod grounding;
+
+/// Bottom-up evaluation of Datalog programs.
+///
+/// A [`Program`](datalog::Program) of function-free rules is evaluated
+/// semi-naively against a [`Database`](datalog::Database) of facts, giving
+/// its least model, which can then be queried for the bindings of a
+/// conjunction of atoms. Rules may have negated literals in their bodies if
+/// their [`DependencyGraph`](datalog::DependencyGraph) can be stratified.
+pub mod datalog;
+
+mod signature;
+pub use signature::{Signature, Symbol};
+
 pub use grammar::*;
@@ -0,0 +1,573 @@
+use std::collections::HashMap;
+
+use super::{
+    herbrand::advance,
+    normal_forms::{ClauseNormalForm, ConjunctiveNormalFormula, Literal, SkolemNormalFormula},
+    sat::{SatResult, SatSolver},
+    structure::Structure,
+    Constant, Equality, FunctionCall, GenericAtomicFormula, GenericFormula, GenericTerm, Negation,
+    PredicateCall, Signature, Variable,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+// Flattening
+////////////////////////////////////////////////////////////////////////////////
+
+/// A symbol interpreted by a table: a constant, or a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TableSymbol {
+    Constant(u64),
+    Function(u64),
+}
+
+/// A literal whose arguments are all variables, numbered within their clause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FlatLiteral {
+    /// `P(x̄)`, or its negation.
+    Predicate {
+        predicate: u64,
+        arguments: Vec<usize>,
+        positive: bool,
+    },
+    /// `f(x̄) = y`, or its negation.
+    Value {
+        symbol: TableSymbol,
+        arguments: Vec<usize>,
+        value: usize,
+        positive: bool,
+    },
+    /// `x = y`, or its negation.
+    Equal {
+        left: usize,
+        right: usize,
+        positive: bool,
+    },
+    /// `⊤` or `⊥`.
+    Truth(bool),
+}
+
+/// A flattened clause, and the number of variables in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FlatClause {
+    literals: Vec<FlatLiteral>,
+    variables: usize,
+}
+
+/// Flattens a clause by naming each of its non-variable subterms `t` with a
+/// new variable `v`, and adding the literal `t ≠ v`, so that every symbol is
+/// applied to variables only. Equal subterms share a name.
+#[derive(Default)]
+struct Flattener {
+    variables: HashMap<Variable, usize>,
+    terms: HashMap<GenericTerm, usize>,
+    count: usize,
+    literals: Vec<FlatLiteral>,
+}
+
+impl Flattener {
+    fn flatten(clause: &[Literal]) -> FlatClause {
+        let mut flattener = Self::default();
+        clause.iter().for_each(|l| flattener.literal(l));
+        FlatClause {
+            literals: flattener.literals,
+            variables: flattener.count,
+        }
+    }
+
+    /// The variable standing for a term, defining it if the term is not a
+    /// variable.
+    fn name(&mut self, term: &GenericTerm) -> usize {
+        if let GenericTerm::Variable(v) = term {
+            if let Some(i) = self.variables.get(v) {
+                return *i;
+            }
+            self.count += 1;
+            self.variables.insert(*v, self.count - 1);
+            return self.count - 1;
+        }
+        if let Some(i) = self.terms.get(term) {
+            return *i;
+        }
+        let (symbol, arguments) = self.application(term);
+        self.count += 1;
+        let value = self.count - 1;
+        self.literals.push(FlatLiteral::Value {
+            symbol,
+            arguments,
+            value,
+            positive: false,
+        });
+        self.terms.insert(term.clone(), value);
+        value
+    }
+
+    /// The symbol at the head of a term which is not a variable, and the
+    /// variables naming its arguments.
+    fn application(&mut self, term: &GenericTerm) -> (TableSymbol, Vec<usize>) {
+        match term {
+            GenericTerm::Constant(c) => (TableSymbol::Constant(c.label), Vec::new()),
+            GenericTerm::FunctionCall(f) => (
+                TableSymbol::Function(f.function),
+                f.terms.iter().map(|t| self.name(t)).collect(),
+            ),
+            GenericTerm::Variable(_) => unreachable!("variables are not applications"),
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal) {
+        let positive = !literal.is_negated();
+        let flat = match literal.atom() {
+            GenericAtomicFormula::True => FlatLiteral::Truth(positive),
+            GenericAtomicFormula::False => FlatLiteral::Truth(!positive),
+            GenericAtomicFormula::Predicate(p) => FlatLiteral::Predicate {
+                predicate: p.predicate,
+                arguments: p.terms.iter().map(|t| self.name(t)).collect(),
+                positive,
+            },
+            GenericAtomicFormula::Equality(e) => {
+                let (term, other) = match &e.left {
+                    GenericTerm::Variable(_) => (&e.right, &e.left),
+                    _ => (&e.left, &e.right),
+                };
+                if let GenericTerm::Variable(_) = term {
+                    FlatLiteral::Equal {
+                        left: self.name(term),
+                        right: self.name(other),
+                        positive,
+                    }
+                } else {
+                    let value = self.name(other);
+                    let (symbol, arguments) = self.application(term);
+                    FlatLiteral::Value {
+                        symbol,
+                        arguments,
+                        value,
+                        positive,
+                    }
+                }
+            }
+        };
+        self.literals.push(flat);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Encoding
+////////////////////////////////////////////////////////////////////////////////
+
+/// Every tuple of a length over a domain.
+fn tuples(length: usize, size: usize) -> Vec<Vec<usize>> {
+    let mut tuple = None;
+    let mut out = Vec::new();
+    while advance(&mut tuple, length, size) {
+        out.push(tuple.clone().unwrap());
+    }
+    out
+}
+
+/// The constant standing for a domain element in the propositional atoms.
+fn element(e: usize) -> GenericTerm {
+    Constant { label: e as u64 }.into()
+}
+
+/// The propositional atom `P(ē)`.
+fn predicate_atom(predicate: u64, arguments: &[usize]) -> GenericAtomicFormula {
+    PredicateCall {
+        predicate,
+        terms: arguments.iter().map(|e| element(*e)).collect(),
+    }
+    .into()
+}
+
+/// The propositional atom `f(ē) = e`.
+fn value_atom(symbol: TableSymbol, arguments: &[usize], value: usize) -> GenericAtomicFormula {
+    let left = match symbol {
+        TableSymbol::Constant(label) => Constant { label }.into(),
+        TableSymbol::Function(function) => FunctionCall {
+            function,
+            terms: arguments.iter().map(|e| element(*e)).collect(),
+        }
+        .into(),
+    };
+    Equality {
+        left,
+        right: element(value),
+    }
+    .into()
+}
+
+fn literal(atom: GenericAtomicFormula, positive: bool) -> Literal {
+    if positive {
+        Literal::Atom(atom)
+    } else {
+        Literal::Negated(Negation { right: atom })
+    }
+}
+
+/// Add every instance of a flat clause over a domain which is not already
+/// satisfied by its equalities between variables.
+fn ground(clause: &FlatClause, size: usize, out: &mut ClauseNormalForm) {
+    let mut values = None;
+    'instances: while advance(&mut values, clause.variables, size) {
+        let values = values.as_ref().unwrap();
+        let mut instance = Vec::new();
+        for flat in &clause.literals {
+            match flat {
+                FlatLiteral::Truth(true) => continue 'instances,
+                FlatLiteral::Truth(false) => {}
+                FlatLiteral::Equal {
+                    left,
+                    right,
+                    positive,
+                } => {
+                    if (values[*left] == values[*right]) == *positive {
+                        continue 'instances;
+                    }
+                }
+                FlatLiteral::Predicate {
+                    predicate,
+                    arguments,
+                    positive,
+                } => {
+                    let arguments: Vec<usize> = arguments.iter().map(|a| values[*a]).collect();
+                    instance.push(literal(predicate_atom(*predicate, &arguments), *positive));
+                }
+                FlatLiteral::Value {
+                    symbol,
+                    arguments,
+                    value,
+                    positive,
+                } => {
+                    let arguments: Vec<usize> = arguments.iter().map(|a| values[*a]).collect();
+                    let atom = value_atom(*symbol, &arguments, values[*value]);
+                    instance.push(literal(atom, *positive));
+                }
+            }
+        }
+        out.push(instance);
+    }
+}
+
+/// The symbols interpreted by tables, with their arities.
+fn table_symbols(signature: &Signature) -> Vec<(TableSymbol, usize)> {
+    signature
+        .constants
+        .keys()
+        .map(|c| (TableSymbol::Constant(*c), 0))
+        .chain(
+            signature
+                .functions
+                .iter()
+                .map(|(f, s)| (TableSymbol::Function(*f), s.arity)),
+        )
+        .collect()
+}
+
+/// Require every table to have exactly one value for each tuple of
+/// arguments.
+fn table_axioms(symbols: &[(TableSymbol, usize)], size: usize, out: &mut ClauseNormalForm) {
+    for (symbol, arity) in symbols {
+        for arguments in tuples(*arity, size) {
+            out.push(
+                (0..size)
+                    .map(|v| literal(value_atom(*symbol, &arguments, v), true))
+                    .collect(),
+            );
+            for v in 0..size {
+                for w in v + 1..size {
+                    out.push(vec![
+                        literal(value_atom(*symbol, &arguments, v), false),
+                        literal(value_atom(*symbol, &arguments, w), false),
+                    ]);
+                }
+            }
+        }
+    }
+}
+
+/// Break the symmetry between domain elements by numbering the values of the
+/// nullary symbols in order of their first appearance: the `i`th symbol is
+/// either equal to an earlier one, or takes the least element no earlier
+/// symbol does. Any model can be permuted into this form.
+fn symmetry_axioms(symbols: &[(TableSymbol, usize)], size: usize, out: &mut ClauseNormalForm) {
+    let nullary: Vec<TableSymbol> = symbols
+        .iter()
+        .filter(|(_, arity)| *arity == 0)
+        .map(|(s, _)| *s)
+        .collect();
+    for (i, symbol) in nullary.iter().enumerate() {
+        for d in 1..size {
+            let mut clause = vec![literal(value_atom(*symbol, &[], d), false)];
+            clause.extend(
+                nullary[..i]
+                    .iter()
+                    .map(|s| literal(value_atom(*s, &[], d - 1), true)),
+            );
+            out.push(clause);
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Model Finder
+////////////////////////////////////////////////////////////////////////////////
+
+/// A MACE-style finite model finder.
+///
+/// For each domain size in turn, the clauses are flattened so that every
+/// function is applied to variables only, and grounded over the domain. Each
+/// predicate, and the graph of each function and constant, becomes a set of
+/// propositional variables, which a [`SatSolver`] searches for a model of the
+/// ground clauses together with axioms making each function total and
+/// single-valued. Equalities between variables are decided while grounding.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   model_finder::ModelFinder, Equality, FunctionCall, GenericFormula, Negation,
+/// #   Universal, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let f = FunctionCall { function: b'f'.into(), terms: vec![x.into()] };
+///
+/// // f has no fixed points: ∀x.¬(f(x) = x).
+/// let no_fixed_points: GenericFormula = Universal {
+///     left: x,
+///     right: Negation { right: Equality { left: f.into(), right: x.into() }.into() }.into(),
+/// }
+/// .into();
+///
+/// let model = ModelFinder::default().find(&[no_fixed_points.clone()]).unwrap();
+/// assert_eq!(model.domain, vec![0, 1]);
+/// assert!(model.models(&no_fixed_points));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelFinder {
+    /// The largest domain size to search.
+    pub max_domain: usize,
+    /// Whether to add clauses breaking the symmetry between domain elements.
+    pub symmetry_breaking: bool,
+    /// The solver for the ground problems.
+    pub solver: SatSolver,
+}
+
+impl Default for ModelFinder {
+    fn default() -> Self {
+        Self {
+            max_domain: 6,
+            symmetry_breaking: true,
+            solver: SatSolver::default(),
+        }
+    }
+}
+
+impl ModelFinder {
+    /// Search for a finite model of some axioms, with the smallest domain
+    /// possible. Free variables are treated as universally quantified.
+    ///
+    /// The structure interprets the symbols in the axioms, over the domain
+    /// `0..n`. Returns [`None`] if there is no model with at most
+    /// [`max_domain`](Self::max_domain) elements.
+    pub fn find(&self, axioms: &[GenericFormula]) -> Option<Structure<usize>> {
+        let mut clauses = ClauseNormalForm::new();
+        let mut skolem = Vec::new();
+        for axiom in axioms {
+            let (snf, state) = SkolemNormalFormula::with_state(axiom.clone());
+            skolem.extend(state.symbols());
+            clauses.extend(ClauseNormalForm::from(ConjunctiveNormalFormula::from(snf)));
+        }
+
+        let mut model = self.find_for_clauses(&clauses)?;
+        for symbol in skolem {
+            model.functions.remove(&symbol);
+        }
+        Some(model)
+    }
+
+    /// Search for a finite model of a set of clauses, with the smallest
+    /// domain possible. Variables are universally quantified in each clause.
+    pub fn find_for_clauses(&self, clauses: &ClauseNormalForm) -> Option<Structure<usize>> {
+        let mut signature = Signature::default();
+        clauses
+            .iter()
+            .for_each(|c| signature.extend_from_literals(c));
+        let symbols = table_symbols(&signature);
+        let flat: Vec<FlatClause> = clauses.iter().map(|c| Flattener::flatten(c)).collect();
+
+        (1..=self.max_domain).find_map(|size| {
+            let mut ground_clauses = ClauseNormalForm::new();
+            flat.iter()
+                .for_each(|c| ground(c, size, &mut ground_clauses));
+            table_axioms(&symbols, size, &mut ground_clauses);
+            if self.symmetry_breaking {
+                symmetry_axioms(&symbols, size, &mut ground_clauses);
+            }
+
+            match self.solver.solve(&ground_clauses) {
+                Ok(SatResult::Satisfiable(assignment)) => {
+                    Some(decode(&signature, &symbols, size, &assignment))
+                }
+                Ok(SatResult::Unsatisfiable(_)) => None,
+                Err(e) => unreachable!("grounded clauses are ground: {}", e),
+            }
+        })
+    }
+}
+
+/// Read a structure off a model of the ground clauses.
+fn decode(
+    signature: &Signature,
+    symbols: &[(TableSymbol, usize)],
+    size: usize,
+    assignment: &HashMap<GenericAtomicFormula, bool>,
+) -> Structure<usize> {
+    let holds = |atom: &GenericAtomicFormula| assignment.get(atom) == Some(&true);
+
+    let mut structure = Structure::new((0..size).collect());
+    for (symbol, arity) in symbols {
+        for arguments in tuples(*arity, size) {
+            let value = (0..size)
+                .find(|v| holds(&value_atom(*symbol, &arguments, *v)))
+                .expect("the table axioms give every symbol a value");
+            match symbol {
+                TableSymbol::Constant(c) => structure.set_constant(*c, value),
+                TableSymbol::Function(f) => structure.set_function(*f, arguments, value),
+            }
+        }
+    }
+    for (predicate, symbol) in &signature.predicates {
+        structure.predicates.entry(*predicate).or_default();
+        for arguments in tuples(symbol.arity, size) {
+            if holds(&predicate_atom(*predicate, &arguments)) {
+                structure.set_predicate(*predicate, arguments, true);
+            }
+        }
+    }
+    structure
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        Conjunction, Constant, Equality, Existential, GenericFormula, GenericTerm, Implication,
+        Negation, PredicateCall, Universal, Variable,
+    };
+
+    use super::ModelFinder;
+
+    fn r(left: GenericTerm, right: GenericTerm) -> GenericFormula {
+        PredicateCall {
+            predicate: b'R'.into(),
+            terms: vec![left, right],
+        }
+        .into()
+    }
+
+    fn distinct(left: GenericTerm, right: GenericTerm) -> GenericFormula {
+        Negation {
+            right: Equality { left, right }.into(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_distinct_constants() {
+        let constants: Vec<GenericTerm> = (1..=3).map(|label| Constant { label }.into()).collect();
+        let axioms = vec![
+            distinct(constants[0].clone(), constants[1].clone()),
+            distinct(constants[1].clone(), constants[2].clone()),
+            distinct(constants[0].clone(), constants[2].clone()),
+        ];
+
+        for symmetry_breaking in [true, false] {
+            let finder = ModelFinder {
+                symmetry_breaking,
+                ..ModelFinder::default()
+            };
+            let model = finder.find(&axioms).unwrap();
+            assert_eq!(model.domain.len(), 3);
+            assert!(axioms.iter().all(|a| model.models(a)));
+            if symmetry_breaking {
+                // The constants are numbered in order.
+                assert_eq!(model.constants[&1], 0);
+                assert_eq!(model.constants[&2], 1);
+                assert_eq!(model.constants[&3], 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_order() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let z = Variable::new(b'z'.into());
+        let irreflexive: GenericFormula = Universal {
+            left: x,
+            right: Negation {
+                right: r(x.into(), x.into()),
+            }
+            .into(),
+        }
+        .into();
+        let transitive: GenericFormula = Universal {
+            left: x,
+            right: Universal {
+                left: y,
+                right: Universal {
+                    left: z,
+                    right: Implication {
+                        left: Conjunction {
+                            left: r(x.into(), y.into()),
+                            right: r(y.into(), z.into()),
+                        }
+                        .into(),
+                        right: r(x.into(), z.into()),
+                    }
+                    .into(),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        let serial: GenericFormula = Universal {
+            left: x,
+            right: Existential {
+                left: y,
+                right: r(x.into(), y.into()),
+            }
+            .into(),
+        }
+        .into();
+        let three: Vec<GenericTerm> = (1..=3).map(|label| Constant { label }.into()).collect();
+        let chain = vec![
+            r(three[0].clone(), three[1].clone()),
+            r(three[1].clone(), three[2].clone()),
+        ];
+
+        let finder = ModelFinder {
+            max_domain: 4,
+            ..ModelFinder::default()
+        };
+
+        // A strict order with a chain of three elements.
+        let mut axioms = vec![irreflexive, transitive];
+        axioms.extend(chain);
+        let model = finder.find(&axioms).unwrap();
+        assert_eq!(model.domain.len(), 3);
+        assert!(axioms.iter().all(|a| model.models(a)));
+
+        // A serial strict order is infinite.
+        axioms.push(serial.clone());
+        assert_eq!(finder.find(&axioms), None);
+
+        // Without transitivity there is a cycle, and the Skolem function for
+        // seriality is not part of the model.
+        let axioms = vec![axioms[0].clone(), serial];
+        let model = finder.find(&axioms).unwrap();
+        assert_eq!(model.domain.len(), 2);
+        assert!(model.functions.is_empty());
+        assert!(axioms.iter().all(|a| model.models(a)));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::syntax::{GenericAtomicFormula, GenericFormula, GenericTerm, Variable};
+
+/// Whether two formulas are equal up to the renaming of bound variables.
+pub(crate) fn alpha_equivalent(left: &GenericFormula, right: &GenericFormula) -> bool {
+    formulas(left, right, &mut Vec::new())
+}
+
+/// `bound` holds the pairs of variables bound so far, innermost last.
+fn formulas(
+    left: &GenericFormula,
+    right: &GenericFormula,
+    bound: &mut Vec<(Variable, Variable)>,
+) -> bool {
+    match (left, right) {
+        (GenericFormula::Atomic(l), GenericFormula::Atomic(r)) => atoms(l, r, bound),
+        (GenericFormula::Universal(l), GenericFormula::Universal(r)) => {
+            bound.push((l.left, r.left));
+            let result = formulas(&l.right, &r.right, bound);
+            bound.pop();
+            result
+        }
+        (GenericFormula::Existential(l), GenericFormula::Existential(r)) => {
+            bound.push((l.left, r.left));
+            let result = formulas(&l.right, &r.right, bound);
+            bound.pop();
+            result
+        }
+        (GenericFormula::Conjunction(l), GenericFormula::Conjunction(r)) => {
+            formulas(&l.left, &r.left, bound) && formulas(&l.right, &r.right, bound)
+        }
+        (GenericFormula::Disjunction(l), GenericFormula::Disjunction(r)) => {
+            formulas(&l.left, &r.left, bound) && formulas(&l.right, &r.right, bound)
+        }
+        (GenericFormula::Implication(l), GenericFormula::Implication(r)) => {
+            formulas(&l.left, &r.left, bound) && formulas(&l.right, &r.right, bound)
+        }
+        (GenericFormula::Negation(l), GenericFormula::Negation(r)) => {
+            formulas(&l.right, &r.right, bound)
+        }
+        _ => false,
+    }
+}
+
+fn atoms(
+    left: &GenericAtomicFormula,
+    right: &GenericAtomicFormula,
+    bound: &[(Variable, Variable)],
+) -> bool {
+    match (left, right) {
+        (GenericAtomicFormula::Equality(l), GenericAtomicFormula::Equality(r)) => {
+            terms(&l.left, &r.left, bound) && terms(&l.right, &r.right, bound)
+        }
+        (GenericAtomicFormula::Predicate(l), GenericAtomicFormula::Predicate(r)) => {
+            l.predicate == r.predicate
+                && l.terms.len() == r.terms.len()
+                && l.terms
+                    .iter()
+                    .zip(&r.terms)
+                    .all(|(l, r)| terms(l, r, bound))
+        }
+        (GenericAtomicFormula::True, GenericAtomicFormula::True)
+        | (GenericAtomicFormula::False, GenericAtomicFormula::False) => true,
+        _ => false,
+    }
+}
+
+fn terms(left: &GenericTerm, right: &GenericTerm, bound: &[(Variable, Variable)]) -> bool {
+    match (left, right) {
+        (GenericTerm::Variable(l), GenericTerm::Variable(r)) => {
+            // Both must be bound by the same quantifier, or both be the same
+            // free variable.
+            let l_binder = bound.iter().rposition(|(x, _)| x == l);
+            let r_binder = bound.iter().rposition(|(_, y)| y == r);
+            match (l_binder, r_binder) {
+                (Some(i), Some(j)) => i == j,
+                (None, None) => l == r,
+                _ => false,
+            }
+        }
+        (GenericTerm::Constant(l), GenericTerm::Constant(r)) => l == r,
+        (GenericTerm::FunctionCall(l), GenericTerm::FunctionCall(r)) => {
+            l.function == r.function
+                && l.terms.len() == r.terms.len()
+                && l.terms
+                    .iter()
+                    .zip(&r.terms)
+                    .all(|(l, r)| terms(l, r, bound))
+        }
+        _ => false,
+    }
+}
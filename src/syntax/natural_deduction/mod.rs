@@ -0,0 +1,736 @@
+use std::fmt::Display;
+
+use super::{
+    unification::Substitution, Equality, GenericAtomicFormula, GenericFormula, GenericTerm,
+    Variable,
+};
+
+mod alpha;
+
+pub(crate) use alpha::alpha_equivalent;
+
+////////////////////////////////////////////////////////////////////////////////
+// Rules
+////////////////////////////////////////////////////////////////////////////////
+
+/// The label of an assumption, used to say which assumptions a rule
+/// discharges.
+pub type AssumptionLabel = usize;
+
+/// An inference rule of natural deduction.
+///
+/// The premises a rule expects are listed in order. `A[x := t]` is the
+/// formula `A` with the term `t` substituted for `x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Assume the conclusion. The assumption must be discharged by a later
+    /// rule, or be one of the premises of the proof.
+    Assumption(AssumptionLabel),
+    /// `⊤`, from no premises.
+    TrueIntro,
+    /// Anything, from `⊥`.
+    FalseElim,
+    /// `A ∧ B`, from `A` and `B`.
+    ConjunctionIntro,
+    /// `A`, from `A ∧ B`.
+    ConjunctionElimLeft,
+    /// `B`, from `A ∧ B`.
+    ConjunctionElimRight,
+    /// `A ∨ B`, from `A`.
+    DisjunctionIntroLeft,
+    /// `A ∨ B`, from `B`.
+    DisjunctionIntroRight,
+    /// `C`, from `A ∨ B`, `C` assuming `A`, and `C` assuming `B`.
+    DisjunctionElim {
+        /// The label of the assumption `A`, which is discharged.
+        left: AssumptionLabel,
+        /// The label of the assumption `B`, which is discharged.
+        right: AssumptionLabel,
+    },
+    /// `A ⇒ B`, from `B` assuming `A`, which is discharged.
+    ImplicationIntro(AssumptionLabel),
+    /// `B`, from `A ⇒ B` and `A`.
+    ImplicationElim,
+    /// `¬A`, from `⊥` assuming `A`, which is discharged.
+    NegationIntro(AssumptionLabel),
+    /// `⊥`, from `¬A` and `A`.
+    NegationElim,
+    /// `A`, from `⊥` assuming `¬A`, which is discharged. This is the only
+    /// classical rule.
+    ReductioAdAbsurdum(AssumptionLabel),
+    /// `∀x.A`, from `A[x := a]`.
+    ///
+    /// The eigenvariable `a` must not be free in `∀x.A`, or in any open
+    /// assumption of the premise.
+    UniversalIntro {
+        /// The variable standing for an arbitrary element.
+        eigenvariable: Variable,
+    },
+    /// `A[x := t]`, from `∀x.A`.
+    UniversalElim {
+        /// The term the universal is instantiated with.
+        term: GenericTerm,
+    },
+    /// `∃x.A`, from `A[x := t]`.
+    ExistentialIntro {
+        /// The witness.
+        term: GenericTerm,
+    },
+    /// `C`, from `∃x.A` and `C` assuming `A[x := a]`, which is discharged.
+    ///
+    /// The eigenvariable `a` must not be free in `C`, in `∃x.A`, or in any
+    /// other open assumption of the second premise.
+    ExistentialElim {
+        /// The label of the assumption `A[x := a]`.
+        label: AssumptionLabel,
+        /// The variable standing for the witness.
+        eigenvariable: Variable,
+    },
+    /// `t = t`, from no premises.
+    EqualityIntro,
+    /// `A[x := t]`, from `s = t` and `A[x := s]`.
+    EqualityElim {
+        /// The variable marking the positions to rewrite.
+        variable: Variable,
+        /// The formula `A`.
+        formula: GenericFormula,
+    },
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Assumption(l) => f.write_fmt(format_args!("assumption {}", l)),
+            Self::TrueIntro => f.write_str("⊤I"),
+            Self::FalseElim => f.write_str("⊥E"),
+            Self::ConjunctionIntro => f.write_str("∧I"),
+            Self::ConjunctionElimLeft => f.write_str("∧E₁"),
+            Self::ConjunctionElimRight => f.write_str("∧E₂"),
+            Self::DisjunctionIntroLeft => f.write_str("∨I₁"),
+            Self::DisjunctionIntroRight => f.write_str("∨I₂"),
+            Self::DisjunctionElim { left, right } => {
+                f.write_fmt(format_args!("∨E {}, {}", left, right))
+            }
+            Self::ImplicationIntro(l) => f.write_fmt(format_args!("⇒I {}", l)),
+            Self::ImplicationElim => f.write_str("⇒E"),
+            Self::NegationIntro(l) => f.write_fmt(format_args!("¬I {}", l)),
+            Self::NegationElim => f.write_str("¬E"),
+            Self::ReductioAdAbsurdum(l) => f.write_fmt(format_args!("RAA {}", l)),
+            Self::UniversalIntro { eigenvariable } => {
+                f.write_fmt(format_args!("∀I {}", eigenvariable))
+            }
+            Self::UniversalElim { term } => f.write_fmt(format_args!("∀E {}", term)),
+            Self::ExistentialIntro { term } => f.write_fmt(format_args!("∃I {}", term)),
+            Self::ExistentialElim {
+                label,
+                eigenvariable,
+            } => f.write_fmt(format_args!("∃E {}, {}", label, eigenvariable)),
+            Self::EqualityIntro => f.write_str("=I"),
+            Self::EqualityElim { .. } => f.write_str("=E"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////
+
+/// The ways a step of a proof can be invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofErrorKind {
+    /// The rule was given the wrong number of premises.
+    WrongPremiseCount {
+        /// The number of premises the rule takes.
+        expected: usize,
+        /// The number of premises given.
+        found: usize,
+    },
+    /// A formula does not have the form the rule requires, such as a
+    /// conjunction for `∧E₁`.
+    UnexpectedForm(GenericFormula),
+    /// A formula is not the one the rule requires.
+    Mismatch {
+        /// The formula required by the rule.
+        expected: GenericFormula,
+        /// The formula given.
+        found: GenericFormula,
+    },
+    /// The eigenvariable of a quantifier rule occurs where it must not.
+    Eigenvariable(Variable),
+    /// An assumption was discharged with a label belonging to a different
+    /// formula.
+    WrongDischarge {
+        /// The label of the assumption.
+        label: AssumptionLabel,
+        /// The formula the rule discharges.
+        expected: GenericFormula,
+        /// The formula assumed with that label.
+        found: GenericFormula,
+    },
+    /// An assumption is neither discharged nor one of the premises.
+    OpenAssumption(AssumptionLabel, GenericFormula),
+}
+
+/// The first invalid step of a proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofError {
+    /// The path to the invalid step from the conclusion: the index of the
+    /// premise taken at each step.
+    pub path: Vec<usize>,
+    /// What is wrong with the step.
+    pub kind: ProofErrorKind,
+}
+
+impl Display for ProofError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("step ")?;
+        if self.path.is_empty() {
+            f.write_str("(conclusion)")?;
+        } else {
+            let path: Vec<String> = self.path.iter().map(|i| i.to_string()).collect();
+            f.write_str(&path.join("."))?;
+        }
+        f.write_str(": ")?;
+        match &self.kind {
+            ProofErrorKind::WrongPremiseCount { expected, found } => f.write_fmt(format_args!(
+                "expected {} premises, found {}",
+                expected, found
+            )),
+            ProofErrorKind::UnexpectedForm(x) => {
+                f.write_fmt(format_args!("{} does not have the required form", x))
+            }
+            ProofErrorKind::Mismatch { expected, found } => {
+                f.write_fmt(format_args!("expected {}, found {}", expected, found))
+            }
+            ProofErrorKind::Eigenvariable(x) => {
+                f.write_fmt(format_args!("eigenvariable {} is not fresh", x))
+            }
+            ProofErrorKind::WrongDischarge {
+                label,
+                expected,
+                found,
+            } => f.write_fmt(format_args!(
+                "assumption {} is {}, but {} is discharged",
+                label, found, expected
+            )),
+            ProofErrorKind::OpenAssumption(label, x) => {
+                f.write_fmt(format_args!("assumption {} of {} is open", label, x))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Proofs
+////////////////////////////////////////////////////////////////////////////////
+
+/// A natural deduction proof, as a tree of rule applications.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   natural_deduction::{Proof, Rule}, Conjunction, GenericFormula, Implication,
+/// #   PredicateCall,
+/// # };
+/// let p: GenericFormula = PredicateCall { predicate: b'P'.into(), terms: vec![] }.into();
+/// let q: GenericFormula = PredicateCall { predicate: b'Q'.into(), terms: vec![] }.into();
+/// let p_and_q: GenericFormula = Conjunction { left: p.clone(), right: q.clone() }.into();
+///
+/// // [P ∧ Q]¹
+/// // ──────── ∧E₂
+/// //    Q
+/// // ──────────── ⇒I¹
+/// // P ∧ Q ⇒ Q
+/// let goal: GenericFormula = Implication { left: p_and_q.clone(), right: q.clone() }.into();
+/// let proof = Proof::new(
+///     goal.clone(),
+///     Rule::ImplicationIntro(1),
+///     vec![Proof::new(
+///         q,
+///         Rule::ConjunctionElimRight,
+///         vec![Proof::new(p_and_q, Rule::Assumption(1), vec![])],
+///     )],
+/// );
+///
+/// assert!(proof.check(&[], &goal).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    /// The formula proved.
+    pub conclusion: GenericFormula,
+    /// The rule used in the last step.
+    pub rule: Rule,
+    /// The proofs of the premises of the rule.
+    pub premises: Vec<Proof>,
+}
+
+/// An assumption which has not been discharged.
+struct Open {
+    label: AssumptionLabel,
+    formula: GenericFormula,
+    path: Vec<usize>,
+}
+
+fn substitute(variable: Variable, term: GenericTerm, formula: &GenericFormula) -> GenericFormula {
+    Substitution::from_iter([(variable, term)]).apply(formula)
+}
+
+impl Proof {
+    /// Create a proof from a conclusion, a rule and the proofs of its
+    /// premises.
+    pub fn new(conclusion: GenericFormula, rule: Rule, premises: Vec<Proof>) -> Self {
+        Self {
+            conclusion,
+            rule,
+            premises,
+        }
+    }
+
+    /// Check that the proof is valid, and proves `conclusion` from the
+    /// premises. Every undischarged assumption must be one of the premises.
+    ///
+    /// Formulas are compared up to the renaming of bound variables. Steps are
+    /// checked premises first, so the error is for the first invalid step in
+    /// reading order.
+    pub fn check(
+        &self,
+        premises: &[GenericFormula],
+        conclusion: &GenericFormula,
+    ) -> Result<(), ProofError> {
+        let open = self.check_step(&mut Vec::new())?;
+        if let Some(open) = open
+            .into_iter()
+            .find(|o| !premises.iter().any(|p| alpha_equivalent(p, &o.formula)))
+        {
+            return Err(ProofError {
+                path: open.path,
+                kind: ProofErrorKind::OpenAssumption(open.label, open.formula),
+            });
+        }
+        if !alpha_equivalent(&self.conclusion, conclusion) {
+            return Err(ProofError {
+                path: Vec::new(),
+                kind: ProofErrorKind::Mismatch {
+                    expected: conclusion.clone(),
+                    found: self.conclusion.clone(),
+                },
+            });
+        }
+        Ok(())
+    }
+
+    /// Check a step and its premises, returning its open assumptions.
+    fn check_step(&self, path: &mut Vec<usize>) -> Result<Vec<Open>, ProofError> {
+        let mut opens = Vec::with_capacity(self.premises.len());
+        for (i, premise) in self.premises.iter().enumerate() {
+            path.push(i);
+            opens.push(premise.check_step(path)?);
+            path.pop();
+        }
+
+        let error = |kind| ProofError {
+            path: path.clone(),
+            kind,
+        };
+        let expect = |expected: &GenericFormula, found: &GenericFormula| {
+            if alpha_equivalent(expected, found) {
+                Ok(())
+            } else {
+                Err(error(ProofErrorKind::Mismatch {
+                    expected: expected.clone(),
+                    found: found.clone(),
+                }))
+            }
+        };
+        let unexpected =
+            |formula: &GenericFormula| error(ProofErrorKind::UnexpectedForm(formula.clone()));
+        let discharge = |opens: Vec<Open>, label: AssumptionLabel, formula: &GenericFormula| {
+            let mut remaining = Vec::with_capacity(opens.len());
+            for open in opens {
+                if open.label != label {
+                    remaining.push(open);
+                } else if !alpha_equivalent(&open.formula, formula) {
+                    return Err(error(ProofErrorKind::WrongDischarge {
+                        label,
+                        expected: formula.clone(),
+                        found: open.formula,
+                    }));
+                }
+            }
+            Ok(remaining)
+        };
+
+        let expected_premises = match self.rule {
+            Rule::Assumption(_) | Rule::TrueIntro | Rule::EqualityIntro => 0,
+            Rule::FalseElim
+            | Rule::ConjunctionElimLeft
+            | Rule::ConjunctionElimRight
+            | Rule::DisjunctionIntroLeft
+            | Rule::DisjunctionIntroRight
+            | Rule::ImplicationIntro(_)
+            | Rule::NegationIntro(_)
+            | Rule::ReductioAdAbsurdum(_)
+            | Rule::UniversalIntro { .. }
+            | Rule::UniversalElim { .. }
+            | Rule::ExistentialIntro { .. } => 1,
+            Rule::ConjunctionIntro
+            | Rule::ImplicationElim
+            | Rule::NegationElim
+            | Rule::ExistentialElim { .. }
+            | Rule::EqualityElim { .. } => 2,
+            Rule::DisjunctionElim { .. } => 3,
+        };
+        if self.premises.len() != expected_premises {
+            return Err(error(ProofErrorKind::WrongPremiseCount {
+                expected: expected_premises,
+                found: self.premises.len(),
+            }));
+        }
+
+        let conclusion = &self.conclusion;
+        let premise = |i: usize| &self.premises[i].conclusion;
+        let falsum: GenericFormula = GenericAtomicFormula::False.into();
+        let mut opens = opens.into_iter();
+        let mut next_open = || opens.next().unwrap();
+
+        let open = match &self.rule {
+            Rule::Assumption(label) => vec![Open {
+                label: *label,
+                formula: conclusion.clone(),
+                path: path.clone(),
+            }],
+            Rule::TrueIntro => {
+                expect(&GenericAtomicFormula::True.into(), conclusion)?;
+                vec![]
+            }
+            Rule::FalseElim => {
+                expect(&falsum, premise(0))?;
+                next_open()
+            }
+            Rule::ConjunctionIntro => match conclusion {
+                GenericFormula::Conjunction(x) => {
+                    expect(&x.left, premise(0))?;
+                    expect(&x.right, premise(1))?;
+                    let mut open = next_open();
+                    open.extend(next_open());
+                    open
+                }
+                _ => return Err(unexpected(conclusion)),
+            },
+            Rule::ConjunctionElimLeft | Rule::ConjunctionElimRight => match premise(0) {
+                GenericFormula::Conjunction(x) => {
+                    let side = if self.rule == Rule::ConjunctionElimLeft {
+                        &x.left
+                    } else {
+                        &x.right
+                    };
+                    expect(side, conclusion)?;
+                    next_open()
+                }
+                p => return Err(unexpected(p)),
+            },
+            Rule::DisjunctionIntroLeft | Rule::DisjunctionIntroRight => match conclusion {
+                GenericFormula::Disjunction(x) => {
+                    let side = if self.rule == Rule::DisjunctionIntroLeft {
+                        &x.left
+                    } else {
+                        &x.right
+                    };
+                    expect(side, premise(0))?;
+                    next_open()
+                }
+                _ => return Err(unexpected(conclusion)),
+            },
+            Rule::DisjunctionElim { left, right } => match premise(0) {
+                GenericFormula::Disjunction(x) => {
+                    expect(conclusion, premise(1))?;
+                    expect(conclusion, premise(2))?;
+                    let mut open = next_open();
+                    open.extend(discharge(next_open(), *left, &x.left)?);
+                    open.extend(discharge(next_open(), *right, &x.right)?);
+                    open
+                }
+                p => return Err(unexpected(p)),
+            },
+            Rule::ImplicationIntro(label) => match conclusion {
+                GenericFormula::Implication(x) => {
+                    expect(&x.right, premise(0))?;
+                    discharge(next_open(), *label, &x.left)?
+                }
+                _ => return Err(unexpected(conclusion)),
+            },
+            Rule::ImplicationElim => match premise(0) {
+                GenericFormula::Implication(x) => {
+                    expect(&x.right, conclusion)?;
+                    expect(&x.left, premise(1))?;
+                    let mut open = next_open();
+                    open.extend(next_open());
+                    open
+                }
+                p => return Err(unexpected(p)),
+            },
+            Rule::NegationIntro(label) => match conclusion {
+                GenericFormula::Negation(x) => {
+                    expect(&falsum, premise(0))?;
+                    discharge(next_open(), *label, &x.right)?
+                }
+                _ => return Err(unexpected(conclusion)),
+            },
+            Rule::NegationElim => match premise(0) {
+                GenericFormula::Negation(x) => {
+                    expect(&falsum, conclusion)?;
+                    expect(&x.right, premise(1))?;
+                    let mut open = next_open();
+                    open.extend(next_open());
+                    open
+                }
+                p => return Err(unexpected(p)),
+            },
+            Rule::ReductioAdAbsurdum(label) => {
+                expect(&falsum, premise(0))?;
+                let negated = super::Negation {
+                    right: conclusion.clone(),
+                }
+                .into();
+                discharge(next_open(), *label, &negated)?
+            }
+            Rule::UniversalIntro { eigenvariable } => match conclusion {
+                GenericFormula::Universal(x) => {
+                    expect(
+                        &substitute(x.left, (*eigenvariable).into(), &x.right),
+                        premise(0),
+                    )?;
+                    let open = next_open();
+                    if conclusion.free_variables().contains(eigenvariable)
+                        || open
+                            .iter()
+                            .any(|o| o.formula.free_variables().contains(eigenvariable))
+                    {
+                        return Err(error(ProofErrorKind::Eigenvariable(*eigenvariable)));
+                    }
+                    open
+                }
+                _ => return Err(unexpected(conclusion)),
+            },
+            Rule::UniversalElim { term } => match premise(0) {
+                GenericFormula::Universal(x) => {
+                    expect(&substitute(x.left, term.clone(), &x.right), conclusion)?;
+                    next_open()
+                }
+                p => return Err(unexpected(p)),
+            },
+            Rule::ExistentialIntro { term } => match conclusion {
+                GenericFormula::Existential(x) => {
+                    expect(&substitute(x.left, term.clone(), &x.right), premise(0))?;
+                    next_open()
+                }
+                _ => return Err(unexpected(conclusion)),
+            },
+            Rule::ExistentialElim {
+                label,
+                eigenvariable,
+            } => match premise(0) {
+                GenericFormula::Existential(x) => {
+                    expect(conclusion, premise(1))?;
+                    let mut open = next_open();
+                    let witness = substitute(x.left, (*eigenvariable).into(), &x.right);
+                    let rest = discharge(next_open(), *label, &witness)?;
+                    if conclusion.free_variables().contains(eigenvariable)
+                        || premise(0).free_variables().contains(eigenvariable)
+                        || rest
+                            .iter()
+                            .any(|o| o.formula.free_variables().contains(eigenvariable))
+                    {
+                        return Err(error(ProofErrorKind::Eigenvariable(*eigenvariable)));
+                    }
+                    open.extend(rest);
+                    open
+                }
+                p => return Err(unexpected(p)),
+            },
+            Rule::EqualityIntro => match conclusion {
+                GenericFormula::Atomic(GenericAtomicFormula::Equality(Equality {
+                    left,
+                    right,
+                })) if left == right => {
+                    vec![]
+                }
+                _ => return Err(unexpected(conclusion)),
+            },
+            Rule::EqualityElim { variable, formula } => match premise(0) {
+                GenericFormula::Atomic(GenericAtomicFormula::Equality(e)) => {
+                    expect(&substitute(*variable, e.left.clone(), formula), premise(1))?;
+                    expect(&substitute(*variable, e.right.clone(), formula), conclusion)?;
+                    let mut open = next_open();
+                    open.extend(next_open());
+                    open
+                }
+                p => return Err(unexpected(p)),
+            },
+        };
+        Ok(open)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        Constant, Disjunction, Existential, GenericFormula, GenericTerm, Implication, Negation,
+        PredicateCall, Universal, Variable,
+    };
+
+    use super::{Proof, ProofErrorKind, Rule};
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    fn assume(label: usize, formula: &GenericFormula) -> Proof {
+        Proof::new(formula.clone(), Rule::Assumption(label), vec![])
+    }
+
+    #[test]
+    fn test_excluded_middle() {
+        let a = p(b'A', vec![]);
+        let not_a: GenericFormula = Negation { right: a.clone() }.into();
+        let lem: GenericFormula = Disjunction {
+            left: a.clone(),
+            right: not_a.clone(),
+        }
+        .into();
+        let not_lem: GenericFormula = Negation { right: lem.clone() }.into();
+        let falsum: GenericFormula = crate::syntax::GenericAtomicFormula::False.into();
+
+        // From ¬(A ∨ ¬A) and A, derive ⊥; so ¬A; so A ∨ ¬A; so ⊥; so A ∨ ¬A.
+        let not_a_proof = Proof::new(
+            not_a.clone(),
+            Rule::NegationIntro(2),
+            vec![Proof::new(
+                falsum.clone(),
+                Rule::NegationElim,
+                vec![
+                    assume(1, &not_lem),
+                    Proof::new(lem.clone(), Rule::DisjunctionIntroLeft, vec![assume(2, &a)]),
+                ],
+            )],
+        );
+        let proof = Proof::new(
+            lem.clone(),
+            Rule::ReductioAdAbsurdum(1),
+            vec![Proof::new(
+                falsum,
+                Rule::NegationElim,
+                vec![
+                    assume(1, &not_lem),
+                    Proof::new(lem.clone(), Rule::DisjunctionIntroRight, vec![not_a_proof]),
+                ],
+            )],
+        );
+
+        assert_eq!(proof.check(&[], &lem), Ok(()));
+    }
+
+    #[test]
+    fn test_quantifiers_and_eigenvariables() {
+        let x = Variable::new(b'x'.into());
+        let a = Variable::new(b'a'.into());
+        let px = p(b'P', vec![x.into()]);
+        let pa = p(b'P', vec![a.into()]);
+        let all: GenericFormula = Universal {
+            left: x,
+            right: px.clone(),
+        }
+        .into();
+        let some: GenericFormula = Existential {
+            left: x,
+            right: px.clone(),
+        }
+        .into();
+
+        // ∀x.P(x) ⊢ ∃x.P(x), instantiating with a constant.
+        let c: GenericTerm = Constant { label: b'c'.into() }.into();
+        let proof = Proof::new(
+            some.clone(),
+            Rule::ExistentialIntro { term: c.clone() },
+            vec![Proof::new(
+                p(b'P', vec![c.clone()]),
+                Rule::UniversalElim { term: c },
+                vec![assume(1, &all)],
+            )],
+        );
+        assert_eq!(proof.check(std::slice::from_ref(&all), &some), Ok(()));
+
+        // The assumption is open without the premise.
+        assert!(matches!(
+            proof.check(&[], &some).unwrap_err().kind,
+            ProofErrorKind::OpenAssumption(1, _)
+        ));
+
+        // P(a) ⊢ ∀x.P(x) is invalid: a is free in an open assumption.
+        let bad = Proof::new(
+            all.clone(),
+            Rule::UniversalIntro { eigenvariable: a },
+            vec![assume(1, &pa)],
+        );
+        let error = bad.check(std::slice::from_ref(&pa), &all).unwrap_err();
+        assert_eq!(error.path, Vec::<usize>::new());
+        assert_eq!(error.kind, ProofErrorKind::Eigenvariable(a));
+
+        // ∃x.P(x) ⊢ ∃x.P(x), through an eigenvariable.
+        let proof = Proof::new(
+            some.clone(),
+            Rule::ExistentialElim {
+                label: 2,
+                eigenvariable: a,
+            },
+            vec![
+                assume(1, &some),
+                Proof::new(
+                    some.clone(),
+                    Rule::ExistentialIntro { term: a.into() },
+                    vec![assume(2, &pa)],
+                ),
+            ],
+        );
+        assert_eq!(proof.check(std::slice::from_ref(&some), &some), Ok(()));
+    }
+
+    #[test]
+    fn test_first_invalid_step() {
+        let a = p(b'A', vec![]);
+        let b = p(b'B', vec![]);
+        let goal: GenericFormula = Implication {
+            left: a.clone(),
+            right: b.clone(),
+        }
+        .into();
+
+        // The premise claims to conclude B by assuming A.
+        let proof = Proof::new(
+            goal.clone(),
+            Rule::ImplicationIntro(1),
+            vec![Proof::new(
+                b.clone(),
+                Rule::ConjunctionElimLeft,
+                vec![assume(1, &a)],
+            )],
+        );
+        let error = proof.check(&[], &goal).unwrap_err();
+        assert_eq!(error.path, vec![0]);
+        assert_eq!(error.kind, ProofErrorKind::UnexpectedForm(a.clone()));
+
+        // Discharging the wrong formula.
+        let proof = Proof::new(goal.clone(), Rule::ImplicationIntro(1), vec![assume(1, &b)]);
+        assert!(matches!(
+            proof.check(&[], &goal).unwrap_err().kind,
+            ProofErrorKind::WrongDischarge { label: 1, .. }
+        ));
+    }
+}
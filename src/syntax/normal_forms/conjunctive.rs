@@ -3,7 +3,9 @@ use std::{
     ops::{BitAnd, BitOr},
 };
 
-use crate::syntax::{Conjunction, Disjunction, GenericAtomicFormula, Implication, Negation};
+use crate::syntax::{
+    Conjunction, Disjunction, GenericAtomicFormula, GenericFormula, Implication, Negation, Variable,
+};
 
 use super::{PrenexNormalFormulaTerm, SkolemNormalFormula};
 
@@ -23,7 +25,7 @@ impl From<ConjunctiveNormalFormula> for ClauseNormalForm {
 /// A formula in Conjunctive Normal Form.
 ///
 /// A formula of the form `(P ∨ Q ∨ ...) ∧ (R ∨ ...) ∧ ...`
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ConjunctiveNormalFormula {
     /// The clauses of the CNF.
     pub clauses: Vec<Clause>,
@@ -41,15 +43,22 @@ impl BitAnd for ConjunctiveNormalFormula {
     type Output = Self;
     fn bitand(self, rhs: Self) -> Self::Output {
         Self {
-            clauses: self
-                .clauses
-                .into_iter()
-                .chain(rhs.clauses.into_iter())
-                .collect(),
+            clauses: self.clauses.into_iter().chain(rhs.clauses).collect(),
         }
     }
 }
 
+/// Nest the clauses into conjunctions. An empty CNF is `⊤`.
+impl From<ConjunctiveNormalFormula> for GenericFormula {
+    fn from(f: ConjunctiveNormalFormula) -> Self {
+        f.clauses
+            .into_iter()
+            .map(GenericFormula::from)
+            .reduce(|left, right| Conjunction { left, right }.into())
+            .unwrap_or(GenericAtomicFormula::True.into())
+    }
+}
+
 impl Display for ConjunctiveNormalFormula {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.clauses.len() > 1 {
@@ -75,12 +84,39 @@ impl Display for ConjunctiveNormalFormula {
 /// A clause of literals.
 ///
 /// A flat disjunction of the form `A ∨ B ∨ ...`.
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Clause {
     /// The literals which make up the clause.
     pub literals: Vec<Literal>,
 }
 
+/// Nest the literals into disjunctions. An empty clause is `⊥`.
+impl From<Clause> for GenericFormula {
+    fn from(f: Clause) -> Self {
+        f.literals
+            .into_iter()
+            .map(GenericFormula::from)
+            .reduce(|left, right| Disjunction { left, right }.into())
+            .unwrap_or(GenericAtomicFormula::False.into())
+    }
+}
+
+impl Clause {
+    /// The variables of the clause, in order of first occurrence.
+    pub fn variables(&self) -> Vec<Variable> {
+        let mut variables = Vec::new();
+        self.literals
+            .iter()
+            .for_each(|l| l.atom().collect_variables(&mut variables));
+        variables
+    }
+
+    /// Whether the clause has no literals, and so is unsatisfiable.
+    pub fn is_empty(&self) -> bool {
+        self.literals.is_empty()
+    }
+}
+
 impl Display for Clause {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.literals.len() > 1 {
@@ -109,17 +145,13 @@ impl BitOr for Clause {
     type Output = Self;
     fn bitor(self, rhs: Self) -> Self::Output {
         Self {
-            literals: self
-                .literals
-                .into_iter()
-                .chain(rhs.literals.into_iter())
-                .collect(),
+            literals: self.literals.into_iter().chain(rhs.literals).collect(),
         }
     }
 }
 
 /// A logical literal - an atom or its negation.
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Literal {
     /// An atom
     Atom(GenericAtomicFormula),
@@ -135,6 +167,31 @@ impl Literal {
             Self::Negated(Negation { right: x }) => Self::Atom(x.clone()),
         }
     }
+
+    /// The atom underlying the literal, regardless of its sign.
+    pub fn atom(&self) -> &GenericAtomicFormula {
+        match self {
+            Self::Atom(x) => x,
+            Self::Negated(Negation { right: x }) => x,
+        }
+    }
+
+    /// Whether the literal is a negated atom.
+    pub fn is_negated(&self) -> bool {
+        matches!(self, Self::Negated(_))
+    }
+}
+
+impl From<Literal> for GenericFormula {
+    fn from(f: Literal) -> Self {
+        match f {
+            Literal::Atom(x) => x.into(),
+            Literal::Negated(x) => Negation {
+                right: x.right.into(),
+            }
+            .into(),
+        }
+    }
 }
 
 impl Display for Literal {
@@ -257,14 +314,25 @@ impl From<Implication<PrenexNormalFormulaTerm, PrenexNormalFormulaTerm>>
 
 #[doc(hidden)]
 impl From<Negation<ConjunctiveNormalFormula>> for ConjunctiveNormalFormula {
+    /// `¬(C₁ ∧ C₂ ∧ ...) ≡ ¬C₁ ∨ ¬C₂ ∨ ...`, where each `¬Cᵢ` is a conjunction of
+    /// negated literals.
     fn from(f: Negation<ConjunctiveNormalFormula>) -> Self {
-        let mut right = f.right;
-        for conjuncted in &mut right.clauses {
-            for disjuncted in &mut conjuncted.literals {
-                *disjuncted = disjuncted.negate()
-            }
-        }
-        right
+        let falsum = ConjunctiveNormalFormula {
+            clauses: vec![Clause { literals: vec![] }],
+        };
+
+        f.right.clauses.into_iter().fold(falsum, |left, clause| {
+            let right = ConjunctiveNormalFormula {
+                clauses: clause
+                    .literals
+                    .iter()
+                    .map(|l| Clause {
+                        literals: vec![l.negate()],
+                    })
+                    .collect(),
+            };
+            Disjunction { left, right }.into()
+        })
     }
 }
 
@@ -282,9 +350,73 @@ impl From<Negation<PrenexNormalFormulaTerm>> for ConjunctiveNormalFormula {
 mod tests {
     use crate::syntax::{
         normal_forms::{ConjunctiveNormalFormula, PrenexNormalFormula, SkolemNormalFormula},
-        Conjunction, Disjunction, Existential, GenericFormula, PredicateCall, Universal, Variable,
+        Conjunction, Disjunction, Existential, GenericAtomicFormula, GenericFormula, GenericTerm,
+        Negation, PredicateCall, Universal, Variable,
     };
 
+    #[test]
+    fn test_negated_conjunction() {
+        let var_x = Variable::new(b'x'.into());
+        let p = |predicate: u8| -> GenericFormula {
+            PredicateCall {
+                predicate: predicate.into(),
+                terms: vec![var_x.into()],
+            }
+            .into()
+        };
+
+        // ¬((P ∨ Q) ∧ R) ≡ (¬P ∨ ¬R) ∧ (¬Q ∨ ¬R)
+        let before: GenericFormula = Negation {
+            right: Conjunction {
+                left: Disjunction {
+                    left: p(b'P'),
+                    right: p(b'Q'),
+                }
+                .into(),
+                right: p(b'R'),
+            }
+            .into(),
+        }
+        .into();
+
+        let cnf = ConjunctiveNormalFormula::from(SkolemNormalFormula::from(before));
+        assert_eq!(cnf.clauses.len(), 2);
+        for clause in &cnf.clauses {
+            assert_eq!(clause.literals.len(), 2);
+            assert!(clause.literals.iter().all(|l| l.is_negated()));
+        }
+    }
+
+    #[test]
+    fn test_skolem_function_arguments() {
+        let var_x = Variable::new(b'x'.into());
+        let var_y = Variable::new(b'y'.into());
+
+        // ∀x.∃y.R(x, y) becomes R(x, f(x))
+        let before: GenericFormula = Universal {
+            left: var_x,
+            right: Existential {
+                left: var_y,
+                right: PredicateCall {
+                    predicate: b'R'.into(),
+                    terms: vec![var_x.into(), var_y.into()],
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+
+        let cnf = ConjunctiveNormalFormula::from(SkolemNormalFormula::from(before));
+        match cnf.clauses[0].literals[0].atom() {
+            GenericAtomicFormula::Predicate(p) => match &p.terms[1] {
+                GenericTerm::FunctionCall(f) => assert_eq!(f.terms, vec![var_x.into()]),
+                t => panic!("{}", t),
+            },
+            a => panic!("{}", a),
+        }
+    }
+
     #[test]
     fn test_cnf() {
         let var_x = Variable::new(b'x'.into());
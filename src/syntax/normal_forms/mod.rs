@@ -2,6 +2,6 @@ mod conjunctive;
 mod prenex;
 mod skolem;
 
-pub use conjunctive::{Clause, ConjunctiveNormalFormula, Literal};
+pub use conjunctive::{Clause, ClauseNormalForm, ConjunctiveNormalFormula, Literal};
 pub use prenex::{PrenexNormalFormula, PrenexNormalFormulaTerm, PrenexNormalQuantifier};
-pub use skolem::SkolemNormalFormula;
+pub use skolem::{SkolemNormalFormula, SkolemisationState};
@@ -1,7 +1,8 @@
 use std::{fmt::Display, ops::Not};
 
 use crate::syntax::{
-    Conjunction, Disjunction, GenericAtomicFormula, GenericFormula, Implication, Replace, Variable,
+    Conjunction, Disjunction, GenericAtomicFormula, GenericFormula, Implication, Replace,
+    Signature, Variable,
 };
 
 use super::super::grammar::{Existential, Negation, Universal};
@@ -11,6 +12,7 @@ use super::super::grammar::{Existential, Negation, Universal};
 ////////////////////////////////////////////////////////////////////////////////
 
 /// A formula in Prenex-Normal Form (PNF).
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PrenexNormalFormula {
     /// A reverse-ordered list of the PNF's leading quantifiers.
     pub quantifiers: Vec<PrenexNormalQuantifier>,
@@ -31,6 +33,19 @@ impl PrenexNormalFormula {
             q.replace(new_var)
         });
     }
+
+    /// Whether the formula is in the Bernays–Schönfinkel (EPR) fragment: its
+    /// quantifier prefix is `∃*∀*`, and it has no function symbols. Such
+    /// formulas can be decided with [`decide`](crate::syntax::epr::decide).
+    pub fn is_epr(&self) -> bool {
+        let prefix = self
+            .quantifiers
+            .iter()
+            .skip_while(|q| matches!(q, PrenexNormalQuantifier::Universal(_)))
+            .all(|q| matches!(q, PrenexNormalQuantifier::Existential(_)));
+        let matrix: GenericFormula = self.formula.clone().into();
+        prefix && Signature::from(&matrix).functions.is_empty()
+    }
 }
 
 impl Display for PrenexNormalFormula {
@@ -43,11 +58,31 @@ impl Display for PrenexNormalFormula {
     }
 }
 
+impl From<PrenexNormalFormula> for GenericFormula {
+    fn from(f: PrenexNormalFormula) -> Self {
+        f.quantifiers
+            .into_iter()
+            .fold(f.formula.into(), |formula, q| match q {
+                PrenexNormalQuantifier::Universal(left) => Universal {
+                    left,
+                    right: formula,
+                }
+                .into(),
+                PrenexNormalQuantifier::Existential(left) => Existential {
+                    left,
+                    right: formula,
+                }
+                .into(),
+            })
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // PrenexNormalFormulaTerm
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Similar to [GenericFormula], without quantifiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PrenexNormalFormulaTerm {
     /// Atomic Formula
     Atomic(GenericAtomicFormula),
@@ -113,6 +148,33 @@ impl From<Negation<PrenexNormalFormulaTerm>> for PrenexNormalFormulaTerm {
     }
 }
 
+impl From<PrenexNormalFormulaTerm> for GenericFormula {
+    fn from(f: PrenexNormalFormulaTerm) -> Self {
+        match f {
+            PrenexNormalFormulaTerm::Atomic(x) => x.into(),
+            PrenexNormalFormulaTerm::Conjunction(x) => Conjunction {
+                left: x.left.into(),
+                right: x.right.into(),
+            }
+            .into(),
+            PrenexNormalFormulaTerm::Disjunction(x) => Disjunction {
+                left: x.left.into(),
+                right: x.right.into(),
+            }
+            .into(),
+            PrenexNormalFormulaTerm::Implication(x) => Implication {
+                left: x.left.into(),
+                right: x.right.into(),
+            }
+            .into(),
+            PrenexNormalFormulaTerm::Negation(x) => Negation {
+                right: x.right.into(),
+            }
+            .into(),
+        }
+    }
+}
+
 impl Display for PrenexNormalFormulaTerm {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -132,7 +194,7 @@ impl Display for PrenexNormalFormulaTerm {
 /// A leading quantifier in a PNF formula.
 ///
 /// Used in [PrenexNormalFormula] in the leading quantifiers.
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PrenexNormalQuantifier {
     /// A universal quantifier over a variable
     Universal(Variable),
@@ -255,7 +317,7 @@ impl From<Conjunction<GenericFormula, GenericFormula>> for PrenexNormalFormula {
 
         loop {
             use PrenexNormalQuantifier::*;
-            match (left_quantifiers.peek(), right_quantifiers.peek().take()) {
+            match (left_quantifiers.peek(), right_quantifiers.peek()) {
                 (Some(Universal(u1)), Some(Universal(u2))) => {
                     // Merge Universals in conjunction
                     right_formula.replace(*u2, *u1);
@@ -346,18 +408,19 @@ impl From<Disjunction<GenericFormula, GenericFormula>> for PrenexNormalFormula {
 impl From<Implication<GenericFormula, GenericFormula>> for PrenexNormalFormula {
     fn from(f: Implication<GenericFormula, GenericFormula>) -> Self {
         let PrenexNormalFormula {
-            quantifiers: left_quantifiers,
+            quantifiers: mut left_quantifiers,
             formula: left_formula,
         } = f.left.into();
 
         let mut right: PrenexNormalFormula = f.right.into();
         right.replace_all_vars();
         let PrenexNormalFormula {
-            quantifiers: mut right_quantifiers,
+            quantifiers: right_quantifiers,
             formula: right_formula,
         } = right;
 
-        right_quantifiers.iter_mut().for_each(|q| *q = !&*q);
+        // The antecedent is negated: (∀x.P) ⇒ Q ≡ ∃x.(P ⇒ Q)
+        left_quantifiers.iter_mut().for_each(|q| *q = !&*q);
 
         let mut left_quantifiers_iter = left_quantifiers.into_iter().peekable();
 
@@ -427,10 +490,50 @@ impl From<GenericAtomicFormula> for PrenexNormalFormula {
 #[cfg(test)]
 mod tests {
     use crate::syntax::{
-        Conjunction, Disjunction, Existential, GenericFormula, PredicateCall, Universal, Variable,
+        Conjunction, Disjunction, Existential, GenericFormula, Implication, PredicateCall,
+        Universal, Variable,
     };
 
-    use super::PrenexNormalFormula;
+    use super::{PrenexNormalFormula, PrenexNormalQuantifier};
+
+    #[test]
+    fn test_implication_antecedent_quantifiers_flip() {
+        let var_x = Variable::new(b'x'.into());
+        let var_y = Variable::new(b'y'.into());
+
+        // (∀x.P(x)) ⇒ ∀y.Q(y) ≡ ∃x.∀y.(P(x) ⇒ Q(y))
+        let before: GenericFormula = Implication {
+            left: Universal {
+                left: var_x,
+                right: PredicateCall {
+                    predicate: b'P'.into(),
+                    terms: vec![var_x.into()],
+                }
+                .into(),
+            }
+            .into(),
+            right: Universal {
+                left: var_y,
+                right: PredicateCall {
+                    predicate: b'Q'.into(),
+                    terms: vec![var_y.into()],
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+
+        let after = PrenexNormalFormula::from(before);
+        assert_eq!(after.quantifiers.len(), 2);
+        assert!(after
+            .quantifiers
+            .contains(&PrenexNormalQuantifier::Existential(var_x)));
+        assert!(after
+            .quantifiers
+            .iter()
+            .any(|q| matches!(q, PrenexNormalQuantifier::Universal(_))));
+    }
 
     #[test]
     fn test_pnf() {
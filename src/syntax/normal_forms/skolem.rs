@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
 use crate::syntax::{
-    Conjunction, Constant, Disjunction, Equality, FunctionCall, GenericAtomicFormula, GenericTerm,
-    Implication, Negation, PredicateCall, Variable,
+    Conjunction, Constant, Disjunction, Equality, FunctionCall, GenericAtomicFormula,
+    GenericFormula, GenericTerm, Implication, Negation, PredicateCall, Variable,
 };
 
 use super::{PrenexNormalFormula, PrenexNormalFormulaTerm, PrenexNormalQuantifier};
@@ -13,30 +13,58 @@ use super::{PrenexNormalFormula, PrenexNormalFormulaTerm, PrenexNormalQuantifier
 
 /// A SNF is the same as a PNF, only without any quantifiers. Any variable in a
 /// SNF is treat as a free variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SkolemNormalFormula {
     /// The underlying terms of the formula (no quantifiers).
     pub terms: PrenexNormalFormulaTerm,
 }
 
+impl From<SkolemNormalFormula> for GenericFormula {
+    fn from(f: SkolemNormalFormula) -> Self {
+        f.terms.into()
+    }
+}
+
 impl<T: Into<PrenexNormalFormula>> From<T> for SkolemNormalFormula {
     fn from(f: T) -> Self {
+        Self::with_state(f).0
+    }
+}
+
+impl SkolemNormalFormula {
+    /// Skolemise a formula, also returning the state recording the Skolem
+    /// functions which were introduced.
+    pub fn with_state<T: Into<PrenexNormalFormula>>(f: T) -> (Self, SkolemisationState) {
         let prenex: PrenexNormalFormula = f.into();
         let skol_state = SkolemisationState::from(prenex.quantifiers);
 
         let terms = prenex.formula.skolemise(&skol_state);
 
-        Self { terms }
+        (Self { terms }, skol_state)
     }
 }
 
-struct SkolemisationState {
+/// The Skolem functions introduced while Skolemising a formula.
+///
+/// Each existentially quantified variable is replaced by a call to a fresh
+/// function, applied to the universally quantified variables outside of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkolemisationState {
     existential_vars: HashMap<Variable, FunctionCall<GenericTerm>>,
 }
 
 impl SkolemisationState {
+    /// The Skolem term replacing an existentially quantified variable.
     pub fn get_function(&self, var: &Variable) -> Option<&FunctionCall<GenericTerm>> {
         self.existential_vars.get(var)
     }
+
+    /// The labels of the Skolem functions, in increasing order.
+    pub fn symbols(&self) -> Vec<u64> {
+        let mut symbols: Vec<u64> = self.existential_vars.values().map(|f| f.function).collect();
+        symbols.sort_unstable();
+        symbols
+    }
 }
 
 impl From<Vec<PrenexNormalQuantifier>> for SkolemisationState {
@@ -46,7 +74,9 @@ impl From<Vec<PrenexNormalQuantifier>> for SkolemisationState {
             existential_vars: HashMap::new(),
         };
 
-        for quant in quants {
+        // The quantifiers are stored innermost first, and each existential
+        // depends on the universals outside of it.
+        for quant in quants.into_iter().rev() {
             match quant {
                 PrenexNormalQuantifier::Universal(v) => {
                     frees_accum.push(v);
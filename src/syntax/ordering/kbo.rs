@@ -0,0 +1,265 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::syntax::{GenericTerm, Variable};
+
+use super::{arguments, Head, Precedence, TermOrdering};
+
+/// The Knuth–Bendix ordering for a weighting of symbols and a precedence.
+///
+/// `s` is greater than `t` if every variable occurs at least as often in `s`
+/// as in `t`, and either `s` is heavier than `t`, or they are equally heavy
+/// and:
+/// - `t` is a variable properly contained in `s`, or
+/// - the head of `s` is greater than the head of `t` in the precedence, or
+/// - they have the same head, and the arguments of `s` are lexicographically
+///   greater than those of `t`.
+///
+/// The ordering is only well-founded if the weights are admissible: every
+/// constant must weigh at least `variable_weight`, which must be positive,
+/// and a function of arity one may only weigh zero if it is greatest in the
+/// precedence.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   ordering::{Head, KnuthBendixOrdering, Precedence, TermOrdering},
+/// #   FunctionCall, GenericTerm, Variable,
+/// # };
+/// let x: GenericTerm = Variable::new(b'x'.into()).into();
+/// let call = |function: u8, terms| -> GenericTerm {
+///     FunctionCall { function: function.into(), terms }.into()
+/// };
+///
+/// let mut kbo = KnuthBendixOrdering::default();
+/// assert!(kbo.greater(&call(b'f', vec![x.clone(), x.clone()]), &call(b'g', vec![x.clone()])));
+///
+/// // A unary function of weight zero, such as an inverse, which must be
+/// // greatest in the precedence.
+/// kbo.precedence = Precedence::new(vec![Head::Function(b'i'.into())]);
+/// kbo.weights.insert(Head::Function(b'i'.into()), 0);
+/// assert!(kbo.greater(&call(b'i', vec![call(b'i', vec![x.clone()])]), &call(b'i', vec![x])));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnuthBendixOrdering {
+    /// The weights of symbols.
+    pub weights: HashMap<Head, u64>,
+    /// The weight of symbols which are not in `weights`.
+    pub default_weight: u64,
+    /// The weight of every variable.
+    pub variable_weight: u64,
+    /// The precedence on symbols, used to compare terms of equal weight.
+    pub precedence: Precedence,
+}
+
+impl Default for KnuthBendixOrdering {
+    fn default() -> Self {
+        Self {
+            weights: HashMap::new(),
+            default_weight: 1,
+            variable_weight: 1,
+            precedence: Precedence::default(),
+        }
+    }
+}
+
+impl KnuthBendixOrdering {
+    /// The weight of a term: the sum of the weights of its symbols and
+    /// variables.
+    pub fn weight(&self, term: &GenericTerm) -> u64 {
+        match Head::of(term) {
+            None => self.variable_weight,
+            Some(head) => {
+                self.weights
+                    .get(&head)
+                    .copied()
+                    .unwrap_or(self.default_weight)
+                    + arguments(term).iter().map(|t| self.weight(t)).sum::<u64>()
+            }
+        }
+    }
+}
+
+fn count_variables(term: &GenericTerm, sign: i64, counts: &mut HashMap<Variable, i64>) {
+    match term {
+        GenericTerm::Variable(v) => *counts.entry(*v).or_default() += sign,
+        _ => arguments(term)
+            .iter()
+            .for_each(|t| count_variables(t, sign, counts)),
+    }
+}
+
+impl TermOrdering for KnuthBendixOrdering {
+    fn greater(&self, left: &GenericTerm, right: &GenericTerm) -> bool {
+        let f = match Head::of(left) {
+            Some(f) => f,
+            None => return false,
+        };
+
+        let mut counts = HashMap::new();
+        count_variables(left, 1, &mut counts);
+        count_variables(right, -1, &mut counts);
+        if counts.values().any(|c| *c < 0) {
+            return false;
+        }
+
+        match self.weight(left).cmp(&self.weight(right)) {
+            Ordering::Greater => return true,
+            Ordering::Less => return false,
+            Ordering::Equal => {}
+        }
+
+        let g = match right {
+            GenericTerm::Variable(v) => return left.contains(v),
+            _ => Head::of(right).unwrap(),
+        };
+        match self.precedence.compare(f, g) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => {
+                let (ss, ts) = (arguments(left), arguments(right));
+                match ss.iter().zip(ts.iter()).find(|(s, t)| s != t) {
+                    Some((s, t)) => self.greater(s, t),
+                    None => ss.len() > ts.len(),
+                }
+            }
+        }
+    }
+
+    fn precedence(&self) -> &Precedence {
+        &self.precedence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::syntax::{
+        normal_forms::{Clause, Literal},
+        ordering::multiset_extension,
+        Constant, FunctionCall, GenericTerm, PredicateCall, Variable,
+    };
+
+    use super::{Head, KnuthBendixOrdering, Precedence, TermOrdering};
+
+    fn x() -> GenericTerm {
+        Variable::new(b'x'.into()).into()
+    }
+
+    fn y() -> GenericTerm {
+        Variable::new(b'y'.into()).into()
+    }
+
+    fn a() -> GenericTerm {
+        Constant { label: b'a'.into() }.into()
+    }
+
+    fn call(function: u8, terms: Vec<GenericTerm>) -> GenericTerm {
+        FunctionCall {
+            function: function.into(),
+            terms,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_weights() {
+        let kbo = KnuthBendixOrdering::default();
+        assert_eq!(kbo.weight(&call(b'f', vec![x(), a()])), 3);
+
+        // f(x, a) > g(x), but f(x, a) and g(y) are incomparable.
+        assert!(kbo.greater(&call(b'f', vec![x(), a()]), &call(b'g', vec![x()])));
+        assert_eq!(
+            kbo.compare(&call(b'f', vec![x(), a()]), &call(b'g', vec![y()])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_precedence_and_lexicographic() {
+        let kbo = KnuthBendixOrdering {
+            precedence: Precedence::new(vec![
+                Head::Function(b'g'.into()),
+                Head::Function(b'f'.into()),
+            ]),
+            ..KnuthBendixOrdering::default()
+        };
+
+        // Equal weights: decided by the precedence, then by the arguments.
+        assert_eq!(
+            kbo.compare(&call(b'f', vec![x()]), &call(b'g', vec![x()])),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            kbo.compare(
+                &call(b'f', vec![call(b'g', vec![x()]), x()]),
+                &call(b'f', vec![x(), call(b'g', vec![x()])]),
+            ),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn test_zero_weight_unary_function() {
+        let mut kbo = KnuthBendixOrdering {
+            precedence: Precedence::new(vec![Head::Function(b'i'.into())]),
+            ..KnuthBendixOrdering::default()
+        };
+        kbo.weights.insert(Head::Function(b'i'.into()), 0);
+
+        // i(x) and x weigh the same, so i(x) > x by containment.
+        assert!(kbo.greater(&call(b'i', vec![x()]), &x()));
+        assert!(kbo.greater(
+            &call(b'i', vec![call(b'f', vec![x(), y()])]),
+            &call(b'f', vec![x(), y()])
+        ));
+    }
+
+    #[test]
+    fn test_multisets_literals_and_clauses() {
+        let kbo = KnuthBendixOrdering::default();
+        let compare = |l: &GenericTerm, r: &GenericTerm| kbo.compare(l, r);
+
+        // {x} < {f(x)}, and swapping the arguments flips the result.
+        let fx = || call(b'f', vec![x()]);
+        assert_eq!(
+            multiset_extension(&[x()], &[fx()], &compare),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            multiset_extension(&[fx()], &[x()], &compare),
+            Some(Ordering::Greater)
+        );
+        // {x, x} < {f(x)}, but {x, y} and {f(x)} are incomparable.
+        assert_eq!(
+            multiset_extension(&[x(), x()], &[fx()], &compare),
+            Some(Ordering::Less)
+        );
+        assert_eq!(multiset_extension(&[x(), y()], &[fx()], &compare), None);
+
+        let p = |t: GenericTerm| {
+            Literal::Atom(
+                PredicateCall {
+                    predicate: b'P'.into(),
+                    terms: vec![t],
+                }
+                .into(),
+            )
+        };
+        assert_eq!(
+            kbo.compare_literals(&p(x()), &p(fx())),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            kbo.compare_literals(&p(fx()), &p(x())),
+            Some(Ordering::Greater)
+        );
+
+        let clause = |literals| Clause { literals };
+        let small = clause(vec![p(x())]);
+        let big = clause(vec![p(x()), p(fx()).negate()]);
+        assert_eq!(kbo.compare_clauses(&small, &big), Some(Ordering::Less));
+        assert_eq!(kbo.compare_clauses(&big, &small), Some(Ordering::Greater));
+    }
+}
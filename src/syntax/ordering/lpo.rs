@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+
+use crate::syntax::GenericTerm;
+
+use super::{arguments, Head, Precedence, TermOrdering};
+
+/// The lexicographic path ordering for a precedence on symbols.
+///
+/// `s = f(s₁, …, sₙ)` is greater than `t` if:
+/// - some `sᵢ` is equal to or greater than `t`, or
+/// - `t = g(t₁, …, tₘ)`, `s` is greater than every `tⱼ`, and either `f` is
+///   greater than `g` in the precedence, or `f = g` and the arguments of `s`
+///   are lexicographically greater than those of `t`.
+///
+/// A term is greater than a variable if it properly contains it.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   ordering::{Head, LexicographicPathOrdering, Precedence, TermOrdering},
+/// #   FunctionCall, GenericTerm, Variable,
+/// # };
+/// let x: GenericTerm = Variable::new(b'x'.into()).into();
+/// let call = |function: u8, terms| -> GenericTerm {
+///     FunctionCall { function: function.into(), terms }.into()
+/// };
+///
+/// // With i > f, the inverse of a product is greater than the product of the
+/// // inverses.
+/// let lpo = LexicographicPathOrdering::new(Precedence::new(vec![
+///     Head::Function(b'f'.into()),
+///     Head::Function(b'i'.into()),
+/// ]));
+/// let left = call(b'i', vec![call(b'f', vec![x.clone(), x.clone()])]);
+/// let right = call(b'f', vec![call(b'i', vec![x.clone()]), call(b'i', vec![x])]);
+/// assert!(lpo.greater(&left, &right));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LexicographicPathOrdering {
+    /// The precedence on symbols.
+    pub precedence: Precedence,
+}
+
+impl LexicographicPathOrdering {
+    /// Create an ordering from a precedence.
+    pub fn new(precedence: Precedence) -> Self {
+        Self { precedence }
+    }
+}
+
+impl TermOrdering for LexicographicPathOrdering {
+    fn greater(&self, left: &GenericTerm, right: &GenericTerm) -> bool {
+        let f = match Head::of(left) {
+            Some(f) => f,
+            None => return false,
+        };
+        let g = match right {
+            GenericTerm::Variable(v) => return left.contains(v),
+            _ => Head::of(right).unwrap(),
+        };
+
+        let (ss, ts) = (arguments(left), arguments(right));
+        if ss.iter().any(|s| s == right || self.greater(s, right)) {
+            return true;
+        }
+
+        let dominates = || ts.iter().all(|t| self.greater(left, t));
+        match self.precedence.compare(f, g) {
+            Ordering::Greater => dominates(),
+            Ordering::Less => false,
+            Ordering::Equal => match ss.iter().zip(ts.iter()).find(|(s, t)| s != t) {
+                Some((s, t)) => self.greater(s, t) && dominates(),
+                None => ss.len() > ts.len() && dominates(),
+            },
+        }
+    }
+
+    fn precedence(&self) -> &Precedence {
+        &self.precedence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::syntax::{
+        normal_forms::{Clause, Literal},
+        Constant, Equality, FunctionCall, GenericTerm, Variable,
+    };
+
+    use super::{Head, LexicographicPathOrdering, Precedence, TermOrdering};
+
+    fn x() -> GenericTerm {
+        Variable::new(b'x'.into()).into()
+    }
+
+    fn y() -> GenericTerm {
+        Variable::new(b'y'.into()).into()
+    }
+
+    fn e() -> GenericTerm {
+        Constant { label: b'e'.into() }.into()
+    }
+
+    fn call(function: u8, terms: Vec<GenericTerm>) -> GenericTerm {
+        FunctionCall {
+            function: function.into(),
+            terms,
+        }
+        .into()
+    }
+
+    fn lpo() -> LexicographicPathOrdering {
+        // e < f < i
+        LexicographicPathOrdering::new(Precedence::new(vec![
+            Head::Constant(b'e'.into()),
+            Head::Function(b'f'.into()),
+            Head::Function(b'i'.into()),
+        ]))
+    }
+
+    #[test]
+    fn test_group_axioms_are_oriented() {
+        let lpo = lpo();
+
+        // f(e, x) > x, f(i(x), x) > e
+        assert!(lpo.greater(&call(b'f', vec![e(), x()]), &x()));
+        assert!(lpo.greater(&call(b'f', vec![call(b'i', vec![x()]), x()]), &e()));
+
+        // f(f(x, y), z) > f(x, f(y, z))
+        let z: GenericTerm = Variable::new(b'z'.into()).into();
+        assert_eq!(
+            lpo.compare(
+                &call(b'f', vec![call(b'f', vec![x(), y()]), z.clone()]),
+                &call(b'f', vec![x(), call(b'f', vec![y(), z])]),
+            ),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn test_incomparable() {
+        let lpo = lpo();
+        assert_eq!(lpo.compare(&x(), &y()), None);
+        assert_eq!(
+            lpo.compare(&call(b'f', vec![x(), e()]), &call(b'f', vec![e(), y()])),
+            None
+        );
+        assert!(!lpo.greater(&x(), &call(b'i', vec![x()])));
+    }
+
+    #[test]
+    fn test_literals_and_clauses() {
+        let lpo = lpo();
+        let eq = |left, right| Literal::Atom(Equality { left, right }.into());
+
+        let big = eq(call(b'i', vec![x()]), e());
+        let small = eq(call(b'f', vec![x(), x()]), e());
+        assert_eq!(lpo.compare_literals(&big, &small), Some(Ordering::Greater));
+        assert_eq!(lpo.compare_literals(&small, &big), Some(Ordering::Less));
+        assert_eq!(
+            lpo.compare_literals(&big.negate(), &big),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            lpo.compare_literals(&big, &big.negate()),
+            Some(Ordering::Less)
+        );
+
+        let clause = |literals| Clause { literals };
+        assert_eq!(
+            lpo.compare_clauses(
+                &clause(vec![big.clone()]),
+                &clause(vec![small.clone(), small.clone()])
+            ),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            lpo.compare_clauses(
+                &clause(vec![small.clone(), small.clone()]),
+                &clause(vec![big.clone()])
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            lpo.compare_clauses(
+                &clause(vec![small]),
+                &clause(vec![big.clone(), big.clone()])
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            lpo.compare_clauses(&clause(vec![big.clone()]), &clause(vec![big])),
+            Some(Ordering::Equal)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::cmp::Ordering;
+
+use super::{
+    normal_forms::{Clause, Literal},
+    GenericAtomicFormula, GenericTerm, PredicateCall,
+};
+
+mod kbo;
+mod lpo;
+
+pub use kbo::KnuthBendixOrdering;
+pub use lpo::LexicographicPathOrdering;
+
+////////////////////////////////////////////////////////////////////////////////
+// Precedence
+////////////////////////////////////////////////////////////////////////////////
+
+/// A symbol which can head a term or atom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Head {
+    /// A constant.
+    Constant(u64),
+    /// A function symbol.
+    Function(u64),
+    /// A predicate symbol.
+    Predicate(u64),
+}
+
+impl Head {
+    /// The head of a term, or `None` if the term is a variable.
+    pub fn of(term: &GenericTerm) -> Option<Self> {
+        match term {
+            GenericTerm::Variable(_) => None,
+            GenericTerm::Constant(c) => Some(Self::Constant(c.label)),
+            GenericTerm::FunctionCall(f) => Some(Self::Function(f.function)),
+        }
+    }
+}
+
+/// A total order on symbols.
+///
+/// The listed symbols are ordered from smallest to largest, and are all
+/// greater than any unlisted symbol. Unlisted symbols are ordered constants
+/// first, then functions, then predicates, and then by label.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Precedence {
+    /// Symbols, from smallest to largest.
+    pub order: Vec<Head>,
+}
+
+impl Precedence {
+    /// Create a precedence from symbols listed from smallest to largest.
+    pub fn new(order: Vec<Head>) -> Self {
+        Self { order }
+    }
+
+    /// Compare two symbols.
+    pub fn compare(&self, left: Head, right: Head) -> Ordering {
+        let rank = |h: Head| self.order.iter().position(|x| *x == h);
+        match (rank(left), rank(right)) {
+            (Some(l), Some(r)) => l.cmp(&r),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => left.cmp(&right),
+        }
+    }
+}
+
+/// The arguments of a term: none for variables and constants.
+pub(crate) fn arguments(term: &GenericTerm) -> &[GenericTerm] {
+    match term {
+        GenericTerm::FunctionCall(f) => &f.terms,
+        GenericTerm::Variable(_) | GenericTerm::Constant(_) => &[],
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// TermOrdering
+////////////////////////////////////////////////////////////////////////////////
+
+/// A simplification ordering on terms.
+///
+/// Such orderings are partial: two distinct terms may be incomparable, in
+/// which case [compare](TermOrdering::compare) returns `None`. They are
+/// stable under substitution, so if `s > t` then `sσ > tσ`.
+///
+/// The ordering on terms is extended to atoms, literals and clauses:
+/// - Atoms with a predicate are greater than any term, and are compared by
+///   the precedence of their predicates and then lexicographically by their
+///   arguments.
+/// - A literal is the multiset `{s, t}` for `s = t` and `{s, s, t, t}` for
+///   `¬(s = t)`, or `{A}` and `{A, A}` for an atom `A` with a predicate.
+///   Literals are compared with the multiset extension of the ordering.
+/// - A clause is the multiset of its literals.
+pub trait TermOrdering {
+    /// Whether `left` is strictly greater than `right`.
+    fn greater(&self, left: &GenericTerm, right: &GenericTerm) -> bool;
+
+    /// The precedence on symbols used by the ordering.
+    fn precedence(&self) -> &Precedence;
+
+    /// Compare two terms. Returns `None` if they are incomparable.
+    fn compare(&self, left: &GenericTerm, right: &GenericTerm) -> Option<Ordering> {
+        if left == right {
+            Some(Ordering::Equal)
+        } else if self.greater(left, right) {
+            Some(Ordering::Greater)
+        } else if self.greater(right, left) {
+            Some(Ordering::Less)
+        } else {
+            None
+        }
+    }
+
+    /// Compare two literals.
+    fn compare_literals(&self, left: &Literal, right: &Literal) -> Option<Ordering> {
+        multiset_extension(
+            &literal_elements(left),
+            &literal_elements(right),
+            &|l, r| compare_elements(self, l, r),
+        )
+    }
+
+    /// Compare two clauses.
+    fn compare_clauses(&self, left: &Clause, right: &Clause) -> Option<Ordering> {
+        let left: Vec<&Literal> = left.literals.iter().collect();
+        let right: Vec<&Literal> = right.literals.iter().collect();
+        multiset_extension(&left, &right, &|l, r| self.compare_literals(l, r))
+    }
+}
+
+/// Compare two multisets with the multiset extension of an ordering.
+///
+/// `M > N` if, after removing the elements they have in common, `M` is not
+/// empty and every remaining element of `N` is smaller than some remaining
+/// element of `M`.
+pub fn multiset_extension<T: PartialEq>(
+    left: &[T],
+    right: &[T],
+    compare: &dyn Fn(&T, &T) -> Option<Ordering>,
+) -> Option<Ordering> {
+    let mut left: Vec<&T> = left.iter().collect();
+    let mut right: Vec<&T> = right.iter().collect();
+    left.retain(|l| match right.iter().position(|r| r == l) {
+        Some(i) => {
+            right.swap_remove(i);
+            false
+        }
+        None => true,
+    });
+
+    let dominates = |bigger: &[&T], smaller: &[&T]| {
+        !bigger.is_empty()
+            && smaller.iter().all(|s| {
+                bigger
+                    .iter()
+                    .any(|b| compare(b, s) == Some(Ordering::Greater))
+            })
+    };
+
+    if left.is_empty() && right.is_empty() {
+        Some(Ordering::Equal)
+    } else if dominates(&left, &right) {
+        Some(Ordering::Greater)
+    } else if dominates(&right, &left) {
+        Some(Ordering::Less)
+    } else {
+        None
+    }
+}
+
+/// An element of the multiset representing a literal.
+#[derive(Clone, Copy, PartialEq)]
+enum Element<'a> {
+    Truth,
+    Term(&'a GenericTerm),
+    Atom(&'a PredicateCall<GenericTerm>),
+}
+
+fn literal_elements(literal: &Literal) -> Vec<Element<'_>> {
+    let copies = if literal.is_negated() { 2 } else { 1 };
+    let elements = match literal.atom() {
+        GenericAtomicFormula::Equality(e) => vec![Element::Term(&e.left), Element::Term(&e.right)],
+        GenericAtomicFormula::Predicate(p) => vec![Element::Atom(p)],
+        GenericAtomicFormula::True | GenericAtomicFormula::False => vec![Element::Truth],
+    };
+    elements.repeat(copies)
+}
+
+fn compare_elements<O: TermOrdering + ?Sized>(
+    ordering: &O,
+    left: &Element,
+    right: &Element,
+) -> Option<Ordering> {
+    match (left, right) {
+        (Element::Truth, Element::Truth) => Some(Ordering::Equal),
+        (Element::Truth, _) => Some(Ordering::Less),
+        (_, Element::Truth) => Some(Ordering::Greater),
+        (Element::Atom(_), Element::Term(_)) => Some(Ordering::Greater),
+        (Element::Term(_), Element::Atom(_)) => Some(Ordering::Less),
+        (Element::Term(l), Element::Term(r)) => ordering.compare(l, r),
+        (Element::Atom(l), Element::Atom(r)) => {
+            let precedence = ordering
+                .precedence()
+                .compare(Head::Predicate(l.predicate), Head::Predicate(r.predicate));
+            if precedence != Ordering::Equal {
+                return Some(precedence);
+            }
+            for (l, r) in l.terms.iter().zip(r.terms.iter()) {
+                match ordering.compare(l, r)? {
+                    Ordering::Equal => continue,
+                    o => return Some(o),
+                }
+            }
+            Some(l.terms.len().cmp(&r.terms.len()))
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{
+    normal_forms::{ClauseNormalForm, Literal},
+    sat::{SatResult, SatSolver},
+    Conjunction, GenericAtomicFormula, GenericFormula, Negation, PredicateCall,
+};
+
+/// Formulas with at most this many distinct atoms are decided by truth
+/// tables, and larger ones by the [`SatSolver`].
+const TRUTH_TABLE_ATOMS: usize = 12;
+
+////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////
+
+/// The reasons a formula cannot be treated as propositional.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropositionalError {
+    /// The formula has a quantifier.
+    Quantified(GenericFormula),
+    /// The formula has an atom with a variable in it.
+    NotGround(GenericAtomicFormula),
+}
+
+impl Display for PropositionalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Quantified(x) => f.write_fmt(format_args!("formula {} is quantified", x)),
+            Self::NotGround(x) => f.write_fmt(format_args!("atom {} is not ground", x)),
+        }
+    }
+}
+
+impl std::error::Error for PropositionalError {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Decision Procedures
+////////////////////////////////////////////////////////////////////////////////
+
+/// Whether a quantifier-free, ground formula is satisfiable, treating each
+/// distinct atom, including equalities, as a propositional variable.
+///
+/// Formulas with few atoms are decided by truth tables, and the others by
+/// the [`SatSolver`] on a definitional (Tseitin) clause form of the formula.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   propositional::{entails, equivalent, is_satisfiable, is_tautology},
+/// #   Conjunction, Disjunction, GenericFormula, Implication, Negation, PredicateCall,
+/// # };
+/// let a: GenericFormula = PredicateCall { predicate: b'A'.into(), terms: vec![] }.into();
+/// let b: GenericFormula = PredicateCall { predicate: b'B'.into(), terms: vec![] }.into();
+/// let not = |right| -> GenericFormula { Negation { right }.into() };
+///
+/// let implies: GenericFormula = Implication { left: a.clone(), right: b.clone() }.into();
+/// let or: GenericFormula = Disjunction { left: not(a.clone()), right: b.clone() }.into();
+/// let contradiction: GenericFormula = Conjunction { left: a.clone(), right: not(a.clone()) }.into();
+///
+/// assert!(equivalent(&implies, &or).unwrap());
+/// assert!(entails(&[implies, a.clone()], &b).unwrap());
+/// assert!(!is_satisfiable(&contradiction).unwrap());
+/// assert!(is_tautology(&not(contradiction)).unwrap());
+/// ```
+pub fn is_satisfiable(formula: &GenericFormula) -> Result<bool, PropositionalError> {
+    let mut atoms = Vec::new();
+    collect_atoms(formula, &mut atoms)?;
+    Ok(if atoms.len() <= TRUTH_TABLE_ATOMS {
+        truth_table(formula, &atoms)
+    } else {
+        let mut clauses = ClauseNormalForm::new();
+        let root = tseitin(formula, &mut clauses);
+        clauses.push(vec![root]);
+        match SatSolver::default().solve(&clauses) {
+            Ok(SatResult::Satisfiable(_)) => true,
+            Ok(SatResult::Unsatisfiable(_)) => false,
+            Err(e) => unreachable!("the atoms are ground: {}", e),
+        }
+    })
+}
+
+/// Whether a quantifier-free, ground formula is true under every assignment
+/// of truth values to its atoms. See [`is_satisfiable`].
+pub fn is_tautology(formula: &GenericFormula) -> Result<bool, PropositionalError> {
+    Ok(!is_satisfiable(&negate(formula.clone()))?)
+}
+
+/// Whether two quantifier-free, ground formulas are true under the same
+/// assignments of truth values to their atoms. See [`is_satisfiable`].
+pub fn equivalent(
+    left: &GenericFormula,
+    right: &GenericFormula,
+) -> Result<bool, PropositionalError> {
+    Ok(entails(std::slice::from_ref(left), right)? && entails(std::slice::from_ref(right), left)?)
+}
+
+/// Whether a quantifier-free, ground conclusion is true under every
+/// assignment of truth values to atoms which makes the premises true. See
+/// [`is_satisfiable`].
+pub fn entails(
+    premises: &[GenericFormula],
+    conclusion: &GenericFormula,
+) -> Result<bool, PropositionalError> {
+    let counterexample = premises
+        .iter()
+        .cloned()
+        .fold(negate(conclusion.clone()), |right, left| {
+            Conjunction { left, right }.into()
+        });
+    Ok(!is_satisfiable(&counterexample)?)
+}
+
+fn negate(right: GenericFormula) -> GenericFormula {
+    Negation { right }.into()
+}
+
+/// Add the distinct atoms of a formula to a list, other than `⊤` and `⊥`.
+fn collect_atoms(
+    formula: &GenericFormula,
+    atoms: &mut Vec<GenericAtomicFormula>,
+) -> Result<(), PropositionalError> {
+    match formula {
+        GenericFormula::Atomic(GenericAtomicFormula::True | GenericAtomicFormula::False) => {}
+        GenericFormula::Atomic(x) => {
+            if !x.is_ground() {
+                return Err(PropositionalError::NotGround(x.clone()));
+            }
+            if !atoms.contains(x) {
+                atoms.push(x.clone());
+            }
+        }
+        GenericFormula::Universal(_) | GenericFormula::Existential(_) => {
+            return Err(PropositionalError::Quantified(formula.clone()))
+        }
+        GenericFormula::Conjunction(x) => {
+            collect_atoms(&x.left, atoms)?;
+            collect_atoms(&x.right, atoms)?;
+        }
+        GenericFormula::Disjunction(x) => {
+            collect_atoms(&x.left, atoms)?;
+            collect_atoms(&x.right, atoms)?;
+        }
+        GenericFormula::Implication(x) => {
+            collect_atoms(&x.left, atoms)?;
+            collect_atoms(&x.right, atoms)?;
+        }
+        GenericFormula::Negation(x) => collect_atoms(&x.right, atoms)?,
+    }
+    Ok(())
+}
+
+/// The truth value of a quantifier-free formula under an assignment to its
+/// atoms.
+fn evaluate(formula: &GenericFormula, assignment: &HashMap<&GenericAtomicFormula, bool>) -> bool {
+    match formula {
+        GenericFormula::Atomic(GenericAtomicFormula::True) => true,
+        GenericFormula::Atomic(GenericAtomicFormula::False) => false,
+        GenericFormula::Atomic(x) => assignment[x],
+        GenericFormula::Conjunction(x) => {
+            evaluate(&x.left, assignment) && evaluate(&x.right, assignment)
+        }
+        GenericFormula::Disjunction(x) => {
+            evaluate(&x.left, assignment) || evaluate(&x.right, assignment)
+        }
+        GenericFormula::Implication(x) => {
+            !evaluate(&x.left, assignment) || evaluate(&x.right, assignment)
+        }
+        GenericFormula::Negation(x) => !evaluate(&x.right, assignment),
+        GenericFormula::Universal(_) | GenericFormula::Existential(_) => {
+            unreachable!("the formula is quantifier-free")
+        }
+    }
+}
+
+/// Whether some row of the truth table of a formula is true.
+fn truth_table(formula: &GenericFormula, atoms: &[GenericAtomicFormula]) -> bool {
+    (0..1u64 << atoms.len()).any(|row| {
+        let assignment = atoms
+            .iter()
+            .enumerate()
+            .map(|(i, atom)| (atom, row >> i & 1 == 1))
+            .collect();
+        evaluate(formula, &assignment)
+    })
+}
+
+/// Add clauses defining a fresh atom to be equivalent to a formula, and
+/// return a literal for it.
+fn tseitin(formula: &GenericFormula, clauses: &mut ClauseNormalForm) -> Literal {
+    match formula {
+        GenericFormula::Atomic(x) => Literal::Atom(x.clone()),
+        GenericFormula::Negation(x) => tseitin(&x.right, clauses).negate(),
+        GenericFormula::Conjunction(x) => {
+            let (left, right) = (tseitin(&x.left, clauses), tseitin(&x.right, clauses));
+            let p = fresh_literal();
+            // p ⇔ l ∧ r
+            clauses.push(vec![p.negate(), left.clone()]);
+            clauses.push(vec![p.negate(), right.clone()]);
+            clauses.push(vec![p.clone(), left.negate(), right.negate()]);
+            p
+        }
+        GenericFormula::Disjunction(x) => {
+            let (left, right) = (tseitin(&x.left, clauses), tseitin(&x.right, clauses));
+            define_disjunction(left, right, clauses)
+        }
+        GenericFormula::Implication(x) => {
+            let (left, right) = (tseitin(&x.left, clauses), tseitin(&x.right, clauses));
+            define_disjunction(left.negate(), right, clauses)
+        }
+        GenericFormula::Universal(_) | GenericFormula::Existential(_) => {
+            unreachable!("the formula is quantifier-free")
+        }
+    }
+}
+
+/// Add clauses defining a fresh atom to be equivalent to `l ∨ r`, and return
+/// a literal for it.
+fn define_disjunction(left: Literal, right: Literal, clauses: &mut ClauseNormalForm) -> Literal {
+    let p = fresh_literal();
+    clauses.push(vec![p.negate(), left.clone(), right.clone()]);
+    clauses.push(vec![p.clone(), left.negate()]);
+    clauses.push(vec![p.clone(), right.negate()]);
+    p
+}
+
+fn fresh_literal() -> Literal {
+    Literal::Atom(
+        PredicateCall {
+            predicate: rand::random(),
+            terms: Vec::new(),
+        }
+        .into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::syntax::{
+        normal_forms::{ConjunctiveNormalFormula, SkolemNormalFormula},
+        Conjunction, Constant, Disjunction, GenericFormula, Implication, Negation, PredicateCall,
+        Universal, Variable,
+    };
+
+    use super::{equivalent, is_satisfiable, is_tautology, tseitin, PropositionalError};
+
+    fn atom(label: u64) -> GenericFormula {
+        PredicateCall {
+            predicate: b'P'.into(),
+            terms: vec![Constant { label }.into()],
+        }
+        .into()
+    }
+
+    fn random_formula(rng: &mut StdRng, atoms: u64, depth: usize) -> GenericFormula {
+        if depth == 0 {
+            return atom(rng.gen_range(0..atoms));
+        }
+        let left = random_formula(rng, atoms, depth - 1);
+        let right = random_formula(rng, atoms, depth - 1);
+        match rng.gen_range(0..4) {
+            0 => Conjunction { left, right }.into(),
+            1 => Disjunction { left, right }.into(),
+            2 => Implication { left, right }.into(),
+            _ => Negation { right: left }.into(),
+        }
+    }
+
+    #[test]
+    fn test_cnf_conversion_is_equivalent() {
+        let mut rng = StdRng::seed_from_u64(47);
+        for _ in 0..50 {
+            let formula = random_formula(&mut rng, 4, 3);
+            let cnf: GenericFormula =
+                ConjunctiveNormalFormula::from(SkolemNormalFormula::from(formula.clone())).into();
+            assert!(equivalent(&formula, &cnf).unwrap(), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_sat_agrees_with_truth_tables() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..50 {
+            let formula = random_formula(&mut rng, 8, 5);
+            let mut clauses = Vec::new();
+            let root = tseitin(&formula, &mut clauses);
+            clauses.push(vec![root]);
+            let sat = matches!(
+                crate::syntax::sat::SatSolver::default().solve(&clauses),
+                Ok(crate::syntax::sat::SatResult::Satisfiable(_))
+            );
+            assert_eq!(sat, is_satisfiable(&formula).unwrap(), "{}", formula);
+        }
+
+        // Twenty atoms take the SAT solver path: the conjunction of the
+        // excluded middle for each is a tautology.
+        let formula = (0..20)
+            .map(|i| -> GenericFormula {
+                Disjunction {
+                    left: atom(i),
+                    right: Negation { right: atom(i) }.into(),
+                }
+                .into()
+            })
+            .reduce(|left, right| Conjunction { left, right }.into())
+            .unwrap();
+        assert!(is_tautology(&formula).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let x = Variable::new(b'x'.into());
+        let open: GenericFormula = PredicateCall {
+            predicate: b'P'.into(),
+            terms: vec![x.into()],
+        }
+        .into();
+        assert!(matches!(
+            is_satisfiable(&open),
+            Err(PropositionalError::NotGround(_))
+        ));
+        let quantified: GenericFormula = Universal {
+            left: x,
+            right: open,
+        }
+        .into();
+        assert!(matches!(
+            is_tautology(&quantified),
+            Err(PropositionalError::Quantified(_))
+        ));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::syntax::{GenericAtomicFormula, GenericTerm, Signature};
+
+use super::{with_tokens, Connective, Quantifier, Render, Token};
+
+/// Render a formula as LaTeX math-mode source.
+///
+/// Names are taken from the signature. Single-character and numeric names are
+/// written as-is; longer names are set upright (`\mathrm`) for symbols, and in
+/// italics (`\mathit`) for variables.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   render::latex, GenericFormula, Implication, PredicateCall, Signature, Universal,
+/// #   Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let formula: GenericFormula = Universal {
+///     left: x,
+///     right: Implication {
+///         left: PredicateCall { predicate: b'P'.into(), terms: vec![x.into()] }.into(),
+///         right: PredicateCall { predicate: b'Q'.into(), terms: vec![x.into()] }.into(),
+///     }
+///     .into(),
+/// }
+/// .into();
+///
+/// assert_eq!(
+///     latex(&formula, &Signature::default()),
+///     r"\forall x.\, P(x) \rightarrow Q(x)",
+/// );
+/// ```
+pub fn latex<R: Render + ?Sized>(formula: &R, signature: &Signature) -> String {
+    with_tokens(formula, signature, |tokens, signature| {
+        let mut out = String::new();
+        for token in tokens {
+            match token {
+                Token::Quantifier(q, v) => {
+                    out.push_str(match q {
+                        Quantifier::Universal => r"\forall ",
+                        Quantifier::Existential => r"\exists ",
+                    });
+                    out.push_str(&name(&signature.variable_name(v), r"\mathit"));
+                    out.push_str(r".\, ");
+                }
+                Token::Negation => out.push_str(r"\lnot "),
+                Token::Connective(c) => out.push_str(match c {
+                    Connective::Conjunction => r" \land ",
+                    Connective::Disjunction => r" \lor ",
+                    Connective::Implication => r" \rightarrow ",
+                }),
+                Token::Open => out.push('('),
+                Token::Close => out.push(')'),
+                Token::Atom(a) => atom(a, signature, &mut out),
+                Token::Begin | Token::End | Token::Break { .. } => {}
+            }
+        }
+        out
+    })
+}
+
+fn name(name: &str, font: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\backslash{}"),
+            '~' => escaped.push_str(r"\sim{}"),
+            '^' => escaped.push_str(r"\hat{}"),
+            '{' | '}' | '_' | '#' | '$' | '%' | '&' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    if name.chars().count() <= 1 || name.chars().all(|c| c.is_ascii_digit()) {
+        escaped
+    } else {
+        format!("{}{{{}}}", font, escaped)
+    }
+}
+
+fn arguments(terms: &[GenericTerm], signature: &Signature, out: &mut String) {
+    if terms.is_empty() {
+        return;
+    }
+    out.push('(');
+    let mut first = true;
+    for t in terms {
+        if !first {
+            out.push_str(", ");
+        }
+        first = false;
+        term(t, signature, out);
+    }
+    out.push(')');
+}
+
+fn term(t: &GenericTerm, signature: &Signature, out: &mut String) {
+    match t {
+        GenericTerm::Variable(v) => out.push_str(&name(&signature.variable_name(v), r"\mathit")),
+        GenericTerm::Constant(c) => out.push_str(&name(&signature.constant_name(c), r"\mathrm")),
+        GenericTerm::FunctionCall(f) => {
+            out.push_str(&name(&signature.function_name(f.function), r"\mathrm"));
+            arguments(&f.terms, signature, out);
+        }
+    }
+}
+
+fn atom(a: &GenericAtomicFormula, signature: &Signature, out: &mut String) {
+    match a {
+        GenericAtomicFormula::Equality(e) => {
+            term(&e.left, signature, out);
+            out.push_str(" = ");
+            term(&e.right, signature, out);
+        }
+        GenericAtomicFormula::Predicate(p) => {
+            out.push_str(&name(&signature.predicate_name(p.predicate), r"\mathrm"));
+            arguments(&p.terms, signature, out);
+        }
+        GenericAtomicFormula::True => out.push_str(r"\top"),
+        GenericAtomicFormula::False => out.push_str(r"\bot"),
+    }
+}
@@ -0,0 +1,148 @@
+use crate::syntax::{GenericAtomicFormula, GenericTerm, Signature};
+
+use super::{with_tokens, Connective, Quantifier, Render, Token};
+
+/// Render a formula as a presentation MathML `<math>` element.
+///
+/// Each parenthesised sub-formula is grouped in its own `<mrow>`.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   render::mathml, Constant, GenericFormula, PredicateCall, Signature,
+/// # };
+/// let formula: GenericFormula = PredicateCall {
+///     predicate: b'P'.into(),
+///     terms: vec![Constant { label: 1 }.into()],
+/// }
+/// .into();
+///
+/// let mut signature = Signature::default();
+/// signature.name_constant(1, "a");
+///
+/// assert_eq!(
+///     mathml(&formula, &signature),
+///     "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>\
+///     <mi>P</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>a</mi><mo>)</mo></mrow>\
+///     </mrow></math>",
+/// );
+/// ```
+pub fn mathml<R: Render + ?Sized>(formula: &R, signature: &Signature) -> String {
+    with_tokens(formula, signature, |tokens, signature| {
+        let mut out = String::from("<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>");
+        for token in tokens {
+            match token {
+                Token::Quantifier(q, v) => {
+                    operator(
+                        match q {
+                            Quantifier::Universal => "∀",
+                            Quantifier::Existential => "∃",
+                        },
+                        &mut out,
+                    );
+                    identifier(&signature.variable_name(v), &mut out);
+                    operator(".", &mut out);
+                }
+                Token::Negation => operator("¬", &mut out),
+                Token::Connective(c) => operator(
+                    match c {
+                        Connective::Conjunction => "∧",
+                        Connective::Disjunction => "∨",
+                        Connective::Implication => "⇒",
+                    },
+                    &mut out,
+                ),
+                Token::Open => {
+                    out.push_str("<mrow>");
+                    operator("(", &mut out);
+                }
+                Token::Close => {
+                    operator(")", &mut out);
+                    out.push_str("</mrow>");
+                }
+                Token::Atom(a) => atom(a, signature, &mut out),
+                Token::Begin | Token::End | Token::Break { .. } => {}
+            }
+        }
+        out.push_str("</mrow></math>");
+        out
+    })
+}
+
+fn escape(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn operator(text: &str, out: &mut String) {
+    out.push_str("<mo>");
+    escape(text, out);
+    out.push_str("</mo>");
+}
+
+/// Write a name as an identifier, or as a number if it is numeric.
+fn identifier(name: &str, out: &mut String) {
+    let tag = if !name.is_empty() && name.chars().all(|c| c.is_ascii_digit()) {
+        "mn"
+    } else {
+        "mi"
+    };
+    out.push_str(&format!("<{}>", tag));
+    escape(name, out);
+    out.push_str(&format!("</{}>", tag));
+}
+
+/// Write the arguments of a function or predicate application.
+fn arguments(terms: &[GenericTerm], signature: &Signature, out: &mut String) {
+    if terms.is_empty() {
+        return;
+    }
+    // U+2061 FUNCTION APPLICATION
+    out.push_str("<mo>&#x2061;</mo><mrow>");
+    operator("(", out);
+    let mut first = true;
+    for t in terms {
+        if !first {
+            operator(",", out);
+        }
+        first = false;
+        term(t, signature, out);
+    }
+    operator(")", out);
+    out.push_str("</mrow>");
+}
+
+fn term(t: &GenericTerm, signature: &Signature, out: &mut String) {
+    match t {
+        GenericTerm::Variable(v) => identifier(&signature.variable_name(v), out),
+        GenericTerm::Constant(c) => identifier(&signature.constant_name(c), out),
+        GenericTerm::FunctionCall(f) => {
+            identifier(&signature.function_name(f.function), out);
+            arguments(&f.terms, signature, out);
+        }
+    }
+}
+
+fn atom(a: &GenericAtomicFormula, signature: &Signature, out: &mut String) {
+    match a {
+        GenericAtomicFormula::Equality(e) => {
+            term(&e.left, signature, out);
+            operator("=", out);
+            term(&e.right, signature, out);
+        }
+        GenericAtomicFormula::Predicate(p) => {
+            identifier(&signature.predicate_name(p.predicate), out);
+            arguments(&p.terms, signature, out);
+        }
+        GenericAtomicFormula::True => operator("⊤", out),
+        GenericAtomicFormula::False => operator("⊥", out),
+    }
+}
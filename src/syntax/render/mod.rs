@@ -0,0 +1,399 @@
+use std::borrow::Cow;
+
+use super::{
+    normal_forms::{
+        Clause, ConjunctiveNormalFormula, Literal, PrenexNormalFormula, PrenexNormalFormulaTerm,
+        SkolemNormalFormula,
+    },
+    GenericAtomicFormula, GenericFormula, Signature, Variable,
+};
+
+mod latex;
+mod mathml;
+mod pretty;
+
+pub use latex::latex;
+pub use mathml::mathml;
+pub use pretty::{PrettyPrinter, Symbols};
+
+////////////////////////////////////////////////////////////////////////////////
+// Render
+////////////////////////////////////////////////////////////////////////////////
+
+/// A formula type which can be rendered.
+///
+/// Every formula type is rendered by first viewing it as a [GenericFormula],
+/// so that all of the normal forms share the same notation.
+pub trait Render {
+    /// View the formula as a [GenericFormula].
+    fn as_formula(&self) -> Cow<'_, GenericFormula>;
+}
+
+impl Render for GenericFormula {
+    fn as_formula(&self) -> Cow<'_, GenericFormula> {
+        Cow::Borrowed(self)
+    }
+}
+
+macro_rules! impl_render_by_conversion {
+    ($($t:ty),*) => {
+        $(
+            impl Render for $t {
+                fn as_formula(&self) -> Cow<'_, GenericFormula> {
+                    Cow::Owned(self.clone().into())
+                }
+            }
+        )*
+    };
+}
+
+impl_render_by_conversion!(
+    GenericAtomicFormula,
+    PrenexNormalFormula,
+    PrenexNormalFormulaTerm,
+    SkolemNormalFormula,
+    ConjunctiveNormalFormula,
+    Clause,
+    Literal
+);
+
+////////////////////////////////////////////////////////////////////////////////
+// Precedence
+////////////////////////////////////////////////////////////////////////////////
+
+/// The binary connectives, in order of increasing precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Connective {
+    Implication,
+    Disjunction,
+    Conjunction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Quantifier {
+    Universal,
+    Existential,
+}
+
+/// A flattened, parenthesised formula, ready to be written by a backend.
+///
+/// `Begin`, `End` and `Break` describe where lines may be broken, and can be
+/// ignored by backends which write a single line.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Token<'a> {
+    Quantifier(Quantifier, Variable),
+    Negation,
+    Connective(Connective),
+    Open,
+    Close,
+    Atom(&'a GenericAtomicFormula),
+    /// Start a group which is either written on one line, or has all of its
+    /// breaks taken.
+    Begin,
+    /// End the current group.
+    End,
+    /// A point at which the line can be broken. If `indent` is true, the new
+    /// line is indented relative to the start of the group; otherwise it is
+    /// aligned with it.
+    Break {
+        indent: bool,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Top,
+    Negated,
+    Left(Connective),
+    Right(Connective),
+}
+
+/// Whether a binary connective needs parentheses in the given position.
+///
+/// `∧` and `∨` are associative, and `⇒` associates to the right.
+fn needs_parentheses(connective: Connective, position: Position) -> bool {
+    match position {
+        Position::Top => false,
+        Position::Negated => true,
+        Position::Left(parent) => {
+            connective < parent || (connective == parent && connective == Connective::Implication)
+        }
+        Position::Right(parent) => connective < parent,
+    }
+}
+
+/// Flatten a formula into tokens, using as few parentheses as possible.
+///
+/// Quantifiers extend as far to the right as possible, so a quantified formula
+/// only needs parentheses when something follows it (`open_right` is false).
+pub(crate) fn tokenise<'a>(formula: &'a GenericFormula, out: &mut Vec<Token<'a>>) {
+    tokenise_at(formula, Position::Top, true, out)
+}
+
+fn tokenise_at<'a>(
+    formula: &'a GenericFormula,
+    position: Position,
+    open_right: bool,
+    out: &mut Vec<Token<'a>>,
+) {
+    let (connective, left, right) = match formula {
+        GenericFormula::Atomic(x) => {
+            out.push(Token::Atom(x));
+            return;
+        }
+        GenericFormula::Negation(x) => {
+            out.push(Token::Negation);
+            tokenise_at(&x.right, Position::Negated, open_right, out);
+            return;
+        }
+        GenericFormula::Universal(x) => {
+            return tokenise_quantifier(Quantifier::Universal, x.left, &x.right, open_right, out);
+        }
+        GenericFormula::Existential(x) => {
+            return tokenise_quantifier(Quantifier::Existential, x.left, &x.right, open_right, out);
+        }
+        GenericFormula::Conjunction(x) => (Connective::Conjunction, &x.left, &x.right),
+        GenericFormula::Disjunction(x) => (Connective::Disjunction, &x.left, &x.right),
+        GenericFormula::Implication(x) => (Connective::Implication, &x.left, &x.right),
+    };
+
+    let parenthesise = needs_parentheses(connective, position);
+    // A chain of the same connective, such as `P ∧ Q ∧ R`, shares one group.
+    let group = parenthesise
+        || !matches!(position, Position::Left(c) | Position::Right(c) if c == connective);
+    if parenthesise {
+        out.push(Token::Open);
+    }
+    if group {
+        out.push(Token::Begin);
+    }
+    tokenise_at(left, Position::Left(connective), false, out);
+    out.push(Token::Break { indent: false });
+    out.push(Token::Connective(connective));
+    tokenise_at(
+        right,
+        Position::Right(connective),
+        open_right || parenthesise,
+        out,
+    );
+    if group {
+        out.push(Token::End);
+    }
+    if parenthesise {
+        out.push(Token::Close);
+    }
+}
+
+fn tokenise_quantifier<'a>(
+    quantifier: Quantifier,
+    variable: Variable,
+    body: &'a GenericFormula,
+    open_right: bool,
+    out: &mut Vec<Token<'a>>,
+) {
+    if !open_right {
+        out.push(Token::Open);
+    }
+    out.push(Token::Begin);
+    out.push(Token::Quantifier(quantifier, variable));
+    out.push(Token::Break { indent: true });
+    tokenise_at(body, Position::Top, true, out);
+    out.push(Token::End);
+    if !open_right {
+        out.push(Token::Close);
+    }
+}
+
+/// Tokenise any renderable formula.
+pub(crate) fn with_tokens<R: Render + ?Sized, T>(
+    formula: &R,
+    signature: &Signature,
+    write: impl FnOnce(&[Token<'_>], &Signature) -> T,
+) -> T {
+    let formula = formula.as_formula();
+    let mut tokens = Vec::new();
+    tokenise(&formula, &mut tokens);
+    write(&tokens, signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::{Clause, ConjunctiveNormalFormula, Literal},
+        Conjunction, Disjunction, Equality, Existential, FunctionCall, GenericFormula, Implication,
+        Negation, PredicateCall, Signature, Universal, Variable,
+    };
+
+    use super::{latex, mathml};
+
+    fn p(predicate: u8, var: Variable) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms: vec![var.into()],
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_latex_minimal_parentheses() {
+        let x = Variable::new(b'x'.into());
+        let signature = Signature::default();
+
+        // ∀x.(P(x) ⇒ Q(x))
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Implication {
+                left: p(b'P', x),
+                right: p(b'Q', x),
+            }
+            .into(),
+        }
+        .into();
+        assert_eq!(
+            latex(&formula, &signature),
+            r"\forall x.\, P(x) \rightarrow Q(x)"
+        );
+
+        // (P ∨ Q) ∧ R ∧ ¬(P ∧ Q)
+        let formula: GenericFormula = Conjunction {
+            left: Conjunction {
+                left: Disjunction {
+                    left: p(b'P', x),
+                    right: p(b'Q', x),
+                }
+                .into(),
+                right: p(b'R', x),
+            }
+            .into(),
+            right: Negation {
+                right: Conjunction {
+                    left: p(b'P', x),
+                    right: p(b'Q', x),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        assert_eq!(
+            latex(&formula, &signature),
+            r"(P(x) \lor Q(x)) \land R(x) \land \lnot (P(x) \land Q(x))"
+        );
+
+        // ((P ⇒ Q) ⇒ R) ⇒ (∃x.P) ∧ R
+        let formula: GenericFormula = Implication {
+            left: Implication {
+                left: Implication {
+                    left: p(b'P', x),
+                    right: p(b'Q', x),
+                }
+                .into(),
+                right: p(b'R', x),
+            }
+            .into(),
+            right: Conjunction {
+                left: Existential {
+                    left: x,
+                    right: p(b'P', x),
+                }
+                .into(),
+                right: p(b'R', x),
+            }
+            .into(),
+        }
+        .into();
+        assert_eq!(
+            latex(&formula, &signature),
+            r"((P(x) \rightarrow Q(x)) \rightarrow R(x)) \rightarrow (\exists x.\, P(x)) \land R(x)"
+        );
+    }
+
+    #[test]
+    fn test_latex_signature_names() {
+        let x = Variable::new(1000);
+        let mut signature = Signature::default();
+        signature.name_variable(1000, "elem");
+        signature.name_function(2000, "inv");
+
+        let formula: GenericFormula = Equality {
+            left: FunctionCall {
+                function: 2000,
+                terms: vec![x.into()],
+            }
+            .into(),
+            right: x.into(),
+        }
+        .into();
+        assert_eq!(
+            latex(&formula, &signature),
+            r"\mathrm{inv}(\mathit{elem}) = \mathit{elem}"
+        );
+    }
+
+    #[test]
+    fn test_normal_forms() {
+        let x = Variable::new(b'x'.into());
+        let literal = |predicate: u8| {
+            Literal::Atom(
+                PredicateCall {
+                    predicate: predicate.into(),
+                    terms: vec![x.into()],
+                }
+                .into(),
+            )
+        };
+        let cnf = ConjunctiveNormalFormula {
+            clauses: vec![
+                Clause {
+                    literals: vec![literal(b'P'), literal(b'Q').negate()],
+                },
+                Clause {
+                    literals: vec![literal(b'R')],
+                },
+                Clause { literals: vec![] },
+            ],
+        };
+
+        assert_eq!(
+            latex(&cnf, &Signature::default()),
+            r"(P(x) \lor \lnot Q(x)) \land R(x) \land \bot"
+        );
+        assert_eq!(
+            mathml(&cnf.clauses[1], &Signature::default()),
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>\
+            <mi>R</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow>\
+            </mrow></math>"
+        );
+    }
+
+    #[test]
+    fn test_mathml() {
+        let x = Variable::new(b'x'.into());
+
+        // ∀x.¬(P(x) ∨ Q(x))
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Negation {
+                right: Disjunction {
+                    left: p(b'P', x),
+                    right: p(b'Q', x),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+
+        assert_eq!(
+            mathml(&formula, &Signature::default()),
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>\
+            <mo>∀</mo><mi>x</mi><mo>.</mo><mo>¬</mo>\
+            <mrow><mo>(</mo>\
+            <mi>P</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow>\
+            <mo>∨</mo>\
+            <mi>Q</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow>\
+            <mo>)</mo></mrow>\
+            </mrow></math>"
+        );
+    }
+}
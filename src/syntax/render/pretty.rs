@@ -0,0 +1,469 @@
+use crate::syntax::{GenericAtomicFormula, GenericTerm, Signature};
+
+use super::{with_tokens, Connective, Quantifier, Render, Token};
+
+/// The symbols used by a [PrettyPrinter] for the logical connectives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Symbols {
+    /// `∀ ∃ ¬ ∧ ∨ ⇒ ⊤ ⊥`
+    #[default]
+    Unicode,
+    /// `forall exists ~ & | -> true false`
+    Ascii,
+}
+
+impl Symbols {
+    fn quantifier(self, quantifier: Quantifier) -> &'static str {
+        match (self, quantifier) {
+            (Self::Unicode, Quantifier::Universal) => "∀",
+            (Self::Unicode, Quantifier::Existential) => "∃",
+            (Self::Ascii, Quantifier::Universal) => "forall ",
+            (Self::Ascii, Quantifier::Existential) => "exists ",
+        }
+    }
+
+    fn connective(self, connective: Connective) -> &'static str {
+        match (self, connective) {
+            (Self::Unicode, Connective::Conjunction) => "∧",
+            (Self::Unicode, Connective::Disjunction) => "∨",
+            (Self::Unicode, Connective::Implication) => "⇒",
+            (Self::Ascii, Connective::Conjunction) => "&",
+            (Self::Ascii, Connective::Disjunction) => "|",
+            (Self::Ascii, Connective::Implication) => "->",
+        }
+    }
+
+    fn negation(self) -> &'static str {
+        match self {
+            Self::Unicode => "¬",
+            Self::Ascii => "~",
+        }
+    }
+
+    fn truth(self, value: bool) -> &'static str {
+        match (self, value) {
+            (Self::Unicode, true) => "⊤",
+            (Self::Unicode, false) => "⊥",
+            (Self::Ascii, true) => "true",
+            (Self::Ascii, false) => "false",
+        }
+    }
+}
+
+/// A configurable, line-breaking printer for formulae.
+///
+/// Parentheses are only written where the precedence of the connectives
+/// requires them. When a formula does not fit in `width` columns, it is broken
+/// before its top-level connectives, and quantifier bodies are moved onto their
+/// own line, indented by `indent`. Chains of the same connective (`P ∧ Q ∧ R`)
+/// are broken together.
+///
+/// Every formula type is printed with [`print`](PrettyPrinter::print), and
+/// terms with [`print_term`](PrettyPrinter::print_term).
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   render::{PrettyPrinter, Symbols}, Conjunction, GenericFormula, PredicateCall,
+/// #   Universal, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let p = |predicate: u8| -> GenericFormula {
+///     PredicateCall { predicate: predicate.into(), terms: vec![x.into()] }.into()
+/// };
+/// let formula: GenericFormula = Universal {
+///     left: x,
+///     right: Conjunction { left: p(b'P'), right: p(b'Q') }.into(),
+/// }
+/// .into();
+///
+/// let printer = PrettyPrinter::default();
+/// assert_eq!(printer.print(&formula), "∀x. P(x) ∧ Q(x)");
+///
+/// let printer = PrettyPrinter {
+///     width: 10,
+///     symbols: Symbols::Ascii,
+///     ..PrettyPrinter::default()
+/// };
+/// assert_eq!(printer.print(&formula), "forall x.\n  P(x)\n  & Q(x)");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PrettyPrinter<'a> {
+    /// The preferred maximum line width.
+    pub width: usize,
+    /// The number of columns to indent quantifier bodies by.
+    pub indent: usize,
+    /// The symbols to use for the logical connectives.
+    pub symbols: Symbols,
+    /// The signature to take names from. Labels are printed as by
+    /// [Display](std::fmt::Display) when there is no signature.
+    pub signature: Option<&'a Signature>,
+}
+
+impl Default for PrettyPrinter<'_> {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            indent: 2,
+            symbols: Symbols::default(),
+            signature: None,
+        }
+    }
+}
+
+/// A formula laid out into groups, with the points at which it may break.
+enum Doc {
+    Text(String),
+    Break { indent: bool },
+    Group(Vec<Doc>),
+}
+
+/// The width of some docs, when written on a single line.
+fn flat_width(docs: &[Doc]) -> usize {
+    docs.iter()
+        .map(|d| match d {
+            Doc::Text(t) => t.chars().count(),
+            Doc::Break { .. } => 1,
+            Doc::Group(g) => flat_width(g),
+        })
+        .sum()
+}
+
+impl PrettyPrinter<'_> {
+    /// Print a formula.
+    pub fn print<R: Render + ?Sized>(&self, formula: &R) -> String {
+        let default_signature = Signature::default();
+        let signature = self.signature.unwrap_or(&default_signature);
+
+        let docs = with_tokens(formula, signature, |tokens, signature| {
+            self.layout(tokens, signature)
+        });
+
+        let mut out = String::new();
+        self.write_group(&docs, 0, &mut out, &mut 0);
+        out
+    }
+
+    /// Print a term, such as a [`Variable`](crate::syntax::Variable), a
+    /// [`Constant`](crate::syntax::Constant) or a
+    /// [`FunctionCall`](crate::syntax::FunctionCall). Terms are always printed
+    /// on a single line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use first_order_logic::syntax::{
+    /// #   render::PrettyPrinter, Constant, FunctionCall, GenericTerm, Signature, Variable,
+    /// # };
+    /// let mut signature = Signature::default();
+    /// signature.name_function(1, "plus");
+    /// signature.name_constant(2, "zero");
+    /// let printer = PrettyPrinter { signature: Some(&signature), ..PrettyPrinter::default() };
+    ///
+    /// let x = Variable::new(b'x'.into());
+    /// let sum = FunctionCall {
+    ///     function: 1,
+    ///     terms: vec![x.into(), Constant { label: 2 }.into()],
+    /// };
+    /// assert_eq!(printer.print_term(&sum), "plus(x, zero)");
+    /// assert_eq!(printer.print_term(&x), "x");
+    /// ```
+    pub fn print_term<T: Clone + Into<GenericTerm>>(&self, term: &T) -> String {
+        let default_signature = Signature::default();
+        let signature = self.signature.unwrap_or(&default_signature);
+        self.term(&term.clone().into(), signature)
+    }
+
+    fn layout(&self, tokens: &[Token<'_>], signature: &Signature) -> Vec<Doc> {
+        let mut stack: Vec<Vec<Doc>> = vec![Vec::new()];
+        for token in tokens {
+            let text = match token {
+                Token::Begin => {
+                    stack.push(Vec::new());
+                    continue;
+                }
+                Token::End => {
+                    let group = stack.pop().unwrap();
+                    stack.last_mut().unwrap().push(Doc::Group(group));
+                    continue;
+                }
+                Token::Break { indent } => {
+                    stack
+                        .last_mut()
+                        .unwrap()
+                        .push(Doc::Break { indent: *indent });
+                    continue;
+                }
+                Token::Quantifier(q, v) => format!(
+                    "{}{}.",
+                    self.symbols.quantifier(*q),
+                    signature.variable_name(v)
+                ),
+                Token::Negation => self.symbols.negation().to_owned(),
+                Token::Connective(c) => format!("{} ", self.symbols.connective(*c)),
+                Token::Open => "(".to_owned(),
+                Token::Close => ")".to_owned(),
+                Token::Atom(a) => self.atom(a, signature),
+            };
+
+            let current = stack.last_mut().unwrap();
+            match current.last_mut() {
+                Some(Doc::Text(t)) => t.push_str(&text),
+                _ => current.push(Doc::Text(text)),
+            }
+        }
+        stack.pop().unwrap()
+    }
+
+    /// Write a group, breaking it if it (and any text which follows it before
+    /// the next break) does not fit on the current line.
+    fn write_group(&self, docs: &[Doc], trailing: usize, out: &mut String, column: &mut usize) {
+        let start = *column;
+        let flat = start + flat_width(docs) + trailing <= self.width;
+
+        for (i, doc) in docs.iter().enumerate() {
+            match doc {
+                Doc::Text(t) => {
+                    out.push_str(t);
+                    *column += t.chars().count();
+                }
+                Doc::Break { .. } if flat => {
+                    out.push(' ');
+                    *column += 1;
+                }
+                Doc::Break { indent } => {
+                    let pad = if *indent { start + self.indent } else { start };
+                    out.push('\n');
+                    out.extend(std::iter::repeat_n(' ', pad));
+                    *column = pad;
+                }
+                Doc::Group(g) => {
+                    let rest = &docs[i + 1..];
+                    let following = match rest.iter().position(|d| !matches!(d, Doc::Text(_))) {
+                        Some(end) => flat_width(&rest[..end]),
+                        None => flat_width(rest) + trailing,
+                    };
+                    self.write_group(g, following, out, column);
+                }
+            }
+        }
+    }
+
+    fn atom(&self, a: &GenericAtomicFormula, signature: &Signature) -> String {
+        match a {
+            GenericAtomicFormula::Equality(e) => format!(
+                "{} = {}",
+                self.term(&e.left, signature),
+                self.term(&e.right, signature)
+            ),
+            GenericAtomicFormula::Predicate(p) => format!(
+                "{}{}",
+                signature.predicate_name(p.predicate),
+                self.arguments(&p.terms, signature)
+            ),
+            GenericAtomicFormula::True => self.symbols.truth(true).to_owned(),
+            GenericAtomicFormula::False => self.symbols.truth(false).to_owned(),
+        }
+    }
+
+    fn arguments(&self, terms: &[GenericTerm], signature: &Signature) -> String {
+        if terms.is_empty() {
+            return String::new();
+        }
+        let terms: Vec<String> = terms.iter().map(|t| self.term(t, signature)).collect();
+        format!("({})", terms.join(", "))
+    }
+
+    fn term(&self, t: &GenericTerm, signature: &Signature) -> String {
+        match t {
+            GenericTerm::Variable(v) => signature.variable_name(v),
+            GenericTerm::Constant(c) => signature.constant_name(c),
+            GenericTerm::FunctionCall(f) => format!(
+                "{}{}",
+                signature.function_name(f.function),
+                self.arguments(&f.terms, signature)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::{
+            ConjunctiveNormalFormula, PrenexNormalFormula, PrenexNormalQuantifier,
+            SkolemNormalFormula,
+        },
+        Conjunction, Constant, Disjunction, Equality, Existential, FunctionCall, GenericFormula,
+        GenericTerm, Implication, Negation, PredicateCall, Signature, Universal, Variable,
+    };
+
+    use super::{PrettyPrinter, Symbols};
+
+    fn p(predicate: u8, var: Variable) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms: vec![var.into()],
+        }
+        .into()
+    }
+
+    /// `∀x.(P(x) ∨ Q(x)) ∧ ¬R(x) ∧ (∃y.P(y)) ⇒ Q(x)`
+    fn formula() -> GenericFormula {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        Universal {
+            left: x,
+            right: Implication {
+                left: Conjunction {
+                    left: Conjunction {
+                        left: Disjunction {
+                            left: p(b'P', x),
+                            right: p(b'Q', x),
+                        }
+                        .into(),
+                        right: Negation { right: p(b'R', x) }.into(),
+                    }
+                    .into(),
+                    right: Existential {
+                        left: y,
+                        right: p(b'P', y),
+                    }
+                    .into(),
+                }
+                .into(),
+                right: p(b'Q', x),
+            }
+            .into(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_single_line() {
+        assert_eq!(
+            PrettyPrinter::default().print(&formula()),
+            "∀x. (P(x) ∨ Q(x)) ∧ ¬R(x) ∧ (∃y. P(y)) ⇒ Q(x)"
+        );
+
+        let printer = PrettyPrinter {
+            symbols: Symbols::Ascii,
+            ..PrettyPrinter::default()
+        };
+        assert_eq!(
+            printer.print(&formula()),
+            "forall x. (P(x) | Q(x)) & ~R(x) & (exists y. P(y)) -> Q(x)"
+        );
+    }
+
+    #[test]
+    fn test_line_breaking() {
+        let printer = PrettyPrinter {
+            width: 30,
+            ..PrettyPrinter::default()
+        };
+        assert_eq!(
+            printer.print(&formula()),
+            "∀x.\n  (P(x) ∨ Q(x))\n  ∧ ¬R(x)\n  ∧ (∃y. P(y))\n  ⇒ Q(x)"
+        );
+
+        for line in printer.print(&formula()).lines() {
+            assert!(line.chars().count() <= 30);
+        }
+    }
+
+    #[test]
+    fn test_signature_and_normal_forms() {
+        let pnf = PrenexNormalFormula::from(formula());
+        let cnf = ConjunctiveNormalFormula::from(SkolemNormalFormula::from(pnf.clone()));
+
+        // Converting to prenex form renames y apart from x, so the fresh
+        // variable is named too.
+        let [PrenexNormalQuantifier::Universal(y), PrenexNormalQuantifier::Universal(_)] =
+            pnf.quantifiers[..]
+        else {
+            panic!("{:?}", pnf.quantifiers)
+        };
+        let mut signature = Signature::default();
+        signature.name_predicate(b'P'.into(), "Even");
+        signature.name_variable(b'x'.into(), "n");
+        signature.name_variable(y.label, "m");
+        let printer = PrettyPrinter {
+            signature: Some(&signature),
+            ..PrettyPrinter::default()
+        };
+
+        assert_eq!(
+            printer.print(&pnf),
+            "∀n. ∀m. (Even(n) ∨ Q(n)) ∧ ¬R(n) ∧ Even(m) ⇒ Q(n)"
+        );
+        assert_eq!(
+            printer.print(&cnf),
+            "(¬Even(n) ∨ R(n) ∨ ¬Even(m) ∨ Q(n)) ∧ (¬Q(n) ∨ R(n) ∨ ¬Even(m) ∨ Q(n))"
+        );
+    }
+
+    #[test]
+    fn test_terms() {
+        let x = Variable::new(b'x'.into());
+        let zero: GenericTerm = Constant { label: b'0'.into() }.into();
+        let call = |function: u8, terms| -> GenericTerm {
+            FunctionCall {
+                function: function.into(),
+                terms,
+            }
+            .into()
+        };
+
+        // ∀x. x + 0 = x ∧ P(s(s(0)))
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: Conjunction {
+                left: Equality {
+                    left: call(b'+', vec![x.into(), zero.clone()]),
+                    right: x.into(),
+                }
+                .into(),
+                right: PredicateCall {
+                    predicate: b'P'.into(),
+                    terms: vec![call(b's', vec![call(b's', vec![zero.clone()])])],
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+
+        let mut signature = Signature::default();
+        signature.name_function(b'+'.into(), "plus");
+        signature.name_function(b's'.into(), "succ");
+        signature.name_constant(b'0'.into(), "zero");
+        signature.name_predicate(b'P'.into(), "Even");
+        let printer = PrettyPrinter {
+            signature: Some(&signature),
+            ..PrettyPrinter::default()
+        };
+        assert_eq!(
+            printer.print(&formula),
+            "∀x. plus(x, zero) = x ∧ Even(succ(succ(zero)))"
+        );
+
+        // Terms on their own.
+        let two = FunctionCall {
+            function: b's'.into(),
+            terms: vec![call(b's', vec![zero.clone()])],
+        };
+        assert_eq!(printer.print_term(&two), "succ(succ(zero))");
+        assert_eq!(
+            printer.print_term(&call(b'+', vec![x.into(), two.into()])),
+            "plus(x, succ(succ(zero)))"
+        );
+        assert_eq!(printer.print_term(&x), "x");
+        assert_eq!(printer.print_term(&Constant { label: b'0'.into() }), "zero");
+        assert_eq!(
+            PrettyPrinter::default().print_term(&zero),
+            Constant { label: b'0'.into() }.to_string()
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use crate::syntax::{
+    normal_forms::{Clause, ClauseNormalForm, Literal},
+    rewriting::{replace_at, subterms},
+    unification::unify,
+    Equality, FunctionCall, GenericAtomicFormula, GenericTerm, PredicateCall, Signature, Variable,
+};
+
+/// How a prover treats the equality symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EqualityHandling {
+    /// Treat `=` as an ordinary binary predicate.
+    None,
+    /// Add the axioms of equality for the symbols of the input (see
+    /// [equality_axioms]), and use resolution alone.
+    Axioms,
+    /// Reason with equalities directly, using paramodulation and equality
+    /// resolution.
+    #[default]
+    Paramodulation,
+}
+
+fn equality(left: GenericTerm, right: GenericTerm) -> Literal {
+    Literal::Atom(Equality { left, right }.into())
+}
+
+fn fresh_variables(n: usize) -> Vec<GenericTerm> {
+    (0..n).map(|_| Variable::rand().into()).collect()
+}
+
+/// The axioms of equality for the symbols of a signature, as clauses.
+///
+/// These are reflexivity, symmetry and transitivity, together with a
+/// congruence axiom for every function and predicate which takes arguments:
+/// ```text
+/// x = x
+/// ¬(x = y) ∨ y = x
+/// ¬(x = y) ∨ ¬(y = z) ∨ x = z
+/// ¬(x₁ = y₁) ∨ … ∨ ¬(xₙ = yₙ) ∨ f(x₁, …, xₙ) = f(y₁, …, yₙ)
+/// ¬(x₁ = y₁) ∨ … ∨ ¬(xₙ = yₙ) ∨ ¬P(x₁, …, xₙ) ∨ P(y₁, …, yₙ)
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{resolution::equality_axioms, Signature, Symbol};
+/// let mut signature = Signature::default();
+/// signature.functions.insert(b'f'.into(), Symbol { name: "f".to_owned(), arity: 2 });
+///
+/// let axioms = equality_axioms(&signature);
+/// assert_eq!(axioms.len(), 4);
+/// assert_eq!(axioms[3].len(), 3);
+/// ```
+pub fn equality_axioms(signature: &Signature) -> ClauseNormalForm {
+    let [x, y, z]: [GenericTerm; 3] = fresh_variables(3).try_into().unwrap();
+
+    let mut axioms = vec![
+        vec![equality(x.clone(), x.clone())],
+        vec![
+            equality(x.clone(), y.clone()).negate(),
+            equality(y.clone(), x.clone()),
+        ],
+        vec![
+            equality(x.clone(), y.clone()).negate(),
+            equality(y, z.clone()).negate(),
+            equality(x, z),
+        ],
+    ];
+
+    let congruence =
+        |arity: usize, conclusion: &dyn Fn(Vec<GenericTerm>, Vec<GenericTerm>) -> Vec<Literal>| {
+            let xs = fresh_variables(arity);
+            let ys = fresh_variables(arity);
+            let mut clause: Vec<Literal> = xs
+                .iter()
+                .zip(ys.iter())
+                .map(|(x, y)| equality(x.clone(), y.clone()).negate())
+                .collect();
+            clause.extend(conclusion(xs, ys));
+            clause
+        };
+
+    for (function, symbol) in signature.functions.iter().filter(|(_, s)| s.arity > 0) {
+        axioms.push(congruence(symbol.arity, &|xs, ys| {
+            let call = |terms| -> GenericTerm {
+                FunctionCall {
+                    function: *function,
+                    terms,
+                }
+                .into()
+            };
+            vec![equality(call(xs), call(ys))]
+        }));
+    }
+
+    for (predicate, symbol) in signature.predicates.iter().filter(|(_, s)| s.arity > 0) {
+        axioms.push(congruence(symbol.arity, &|xs, ys| {
+            let call = |terms| {
+                Literal::Atom(
+                    PredicateCall {
+                        predicate: *predicate,
+                        terms,
+                    }
+                    .into(),
+                )
+            };
+            vec![call(xs).negate(), call(ys)]
+        }));
+    }
+
+    axioms
+}
+
+/// Whether any literal of some clauses is an equality.
+pub(crate) fn contains_equality(clauses: &ClauseNormalForm) -> bool {
+    clauses
+        .iter()
+        .flatten()
+        .any(|l| matches!(l.atom(), GenericAtomicFormula::Equality(_)))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Positions
+////////////////////////////////////////////////////////////////////////////////
+
+/// The arguments of an atom: the two sides of an equality, or the terms of a
+/// predicate call.
+fn arguments(atom: &GenericAtomicFormula) -> Vec<&GenericTerm> {
+    match atom {
+        GenericAtomicFormula::Equality(e) => vec![&e.left, &e.right],
+        GenericAtomicFormula::Predicate(p) => p.terms.iter().collect(),
+        GenericAtomicFormula::True | GenericAtomicFormula::False => vec![],
+    }
+}
+
+/// Every non-variable subterm of an atom. The first index of each path is the
+/// index of the argument of the atom.
+fn atom_subterms(atom: &GenericAtomicFormula) -> Vec<(Vec<usize>, &GenericTerm)> {
+    let mut out = Vec::new();
+    for (i, t) in arguments(atom).into_iter().enumerate() {
+        subterms(t, &mut vec![i], &mut out);
+    }
+    out
+}
+
+fn replace_in_atom(
+    atom: &GenericAtomicFormula,
+    path: &[usize],
+    replacement: &GenericTerm,
+) -> GenericAtomicFormula {
+    let (i, rest) = path.split_first().unwrap();
+    match atom {
+        GenericAtomicFormula::Equality(e) => {
+            let mut e = e.clone();
+            let side = if *i == 0 { &mut e.left } else { &mut e.right };
+            *side = replace_at(side, rest, replacement);
+            e.into()
+        }
+        GenericAtomicFormula::Predicate(p) => {
+            let mut p = p.clone();
+            p.terms[*i] = replace_at(&p.terms[*i], rest, replacement);
+            p.into()
+        }
+        GenericAtomicFormula::True | GenericAtomicFormula::False => {
+            unreachable!("truth values have no subterms")
+        }
+    }
+}
+
+fn with_atom(literal: &Literal, atom: GenericAtomicFormula) -> Literal {
+    if literal.is_negated() {
+        Literal::Atom(atom).negate()
+    } else {
+        Literal::Atom(atom)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Inferences
+////////////////////////////////////////////////////////////////////////////////
+
+/// Every paramodulant from a positive equality of `from` into a non-variable
+/// subterm of `into`. The clauses must be variable-disjoint.
+///
+/// From `l = r ∨ C` and `D[u]`, where `σ` unifies `l` and `u`, paramodulation
+/// derives `(C ∨ D[r])σ`. Both orientations of each equality are used.
+pub(crate) fn paramodulants(from: &Clause, into: &Clause) -> Vec<Clause> {
+    let mut paramodulants = Vec::new();
+    for (i, equation) in from.literals.iter().enumerate() {
+        let e = match (equation.atom(), equation.is_negated()) {
+            (GenericAtomicFormula::Equality(e), false) => e,
+            _ => continue,
+        };
+        for (l, r) in [(&e.left, &e.right), (&e.right, &e.left)] {
+            for (j, target) in into.literals.iter().enumerate() {
+                for (path, u) in atom_subterms(target.atom()) {
+                    let unifier = match unify(l, u) {
+                        Some(u) => u,
+                        None => continue,
+                    };
+                    let rewritten = with_atom(target, replace_in_atom(target.atom(), &path, r));
+                    let literals = from
+                        .literals
+                        .iter()
+                        .enumerate()
+                        .filter(|(k, _)| *k != i)
+                        .map(|(_, l)| l)
+                        .chain(std::iter::once(&rewritten))
+                        .chain(
+                            into.literals
+                                .iter()
+                                .enumerate()
+                                .filter(|(k, _)| *k != j)
+                                .map(|(_, l)| l),
+                        )
+                        .map(|l| unifier.apply(l))
+                        .collect();
+                    paramodulants.push(Clause { literals });
+                }
+            }
+        }
+    }
+    paramodulants
+}
+
+/// Every clause derived by equality resolution: from `¬(s = t) ∨ C`, where `σ`
+/// unifies `s` and `t`, derive `Cσ`.
+pub(crate) fn equality_resolvents(clause: &Clause) -> Vec<Clause> {
+    let mut resolvents = Vec::new();
+    for (i, literal) in clause.literals.iter().enumerate() {
+        let e = match (literal.atom(), literal.is_negated()) {
+            (GenericAtomicFormula::Equality(e), true) => e,
+            _ => continue,
+        };
+        if let Some(unifier) = unify(&e.left, &e.right) {
+            let literals = clause
+                .literals
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| *k != i)
+                .map(|(_, l)| unifier.apply(l))
+                .collect();
+            resolvents.push(Clause { literals });
+        }
+    }
+    resolvents
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::{Clause, ClauseNormalForm, Literal},
+        resolution::{RefutationResult, ResolutionProver},
+        Constant, Equality, FunctionCall, GenericTerm, PredicateCall, Signature, Variable,
+    };
+
+    use super::{equality_axioms, paramodulants, EqualityHandling};
+
+    fn c(label: u64) -> GenericTerm {
+        Constant { label }.into()
+    }
+
+    fn f(t: GenericTerm) -> GenericTerm {
+        FunctionCall {
+            function: b'f'.into(),
+            terms: vec![t],
+        }
+        .into()
+    }
+
+    fn eq(left: GenericTerm, right: GenericTerm) -> Literal {
+        Literal::Atom(Equality { left, right }.into())
+    }
+
+    fn p(t: GenericTerm) -> Literal {
+        Literal::Atom(
+            PredicateCall {
+                predicate: b'P'.into(),
+                terms: vec![t],
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn test_paramodulation() {
+        let from = Clause {
+            literals: vec![eq(c(1), c(2))],
+        };
+        let into = Clause {
+            literals: vec![p(f(c(1)))],
+        };
+        assert_eq!(
+            paramodulants(&from, &into),
+            vec![Clause {
+                literals: vec![p(f(c(2)))]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_equational_reasoning() {
+        // a = b, f(a) = c, P(c) ⊢ P(f(b))
+        let clauses: ClauseNormalForm = vec![
+            vec![eq(c(1), c(2))],
+            vec![eq(f(c(1)), c(3))],
+            vec![p(c(3))],
+            vec![p(f(c(2))).negate()],
+        ];
+
+        for equality in [EqualityHandling::Paramodulation, EqualityHandling::Axioms] {
+            let prover = ResolutionProver {
+                equality,
+                ..ResolutionProver::default()
+            };
+            assert!(matches!(
+                prover.refute(&clauses),
+                RefutationResult::Unsatisfiable(_)
+            ));
+        }
+
+        let prover = ResolutionProver {
+            equality: EqualityHandling::None,
+            ..ResolutionProver::default()
+        };
+        assert_eq!(prover.refute(&clauses), RefutationResult::Saturated);
+    }
+
+    #[test]
+    fn test_symmetry_and_reflexivity() {
+        // ∀x.f(x) = x ⊢ a = f(f(a))
+        let x: GenericTerm = Variable::new(b'x'.into()).into();
+        let clauses: ClauseNormalForm = vec![
+            vec![eq(f(x.clone()), x)],
+            vec![eq(c(1), f(f(c(1)))).negate()],
+        ];
+        assert!(matches!(
+            ResolutionProver::default().refute(&clauses),
+            RefutationResult::Unsatisfiable(_)
+        ));
+    }
+
+    #[test]
+    fn test_reflexivity() {
+        // ∀x.∀y.(x = y ⇒ P(x)) ⊢ P(a) needs reflexivity: a = a.
+        let x: GenericTerm = Variable::new(b'x'.into()).into();
+        let y: GenericTerm = Variable::new(b'y'.into()).into();
+        let clauses: ClauseNormalForm = vec![
+            vec![eq(x.clone(), y).negate(), p(x)],
+            vec![p(c(1)).negate()],
+        ];
+        for equality in [EqualityHandling::Paramodulation, EqualityHandling::Axioms] {
+            let prover = ResolutionProver {
+                equality,
+                ..ResolutionProver::default()
+            };
+            assert!(matches!(
+                prover.refute(&clauses),
+                RefutationResult::Unsatisfiable(_)
+            ));
+        }
+
+        // Without equality, `a = a` is an ordinary atom, which may be false.
+        let prover = ResolutionProver {
+            equality: EqualityHandling::None,
+            ..ResolutionProver::default()
+        };
+        assert_eq!(
+            prover.refute(&vec![vec![eq(c(1), c(1)).negate()]]),
+            RefutationResult::Saturated
+        );
+        assert_eq!(prover.refute(&clauses), RefutationResult::Saturated);
+    }
+
+    #[test]
+    fn test_axioms() {
+        let mut signature = Signature::default();
+        signature.extend_from_literals(&[p(f(c(1)))]);
+
+        // Reflexivity, symmetry, transitivity, and congruence for f and P.
+        let axioms = equality_axioms(&signature);
+        assert_eq!(axioms.len(), 5);
+        assert_eq!(axioms[3].len(), 2);
+        assert_eq!(axioms[4].len(), 3);
+    }
+}
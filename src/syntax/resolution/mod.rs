@@ -0,0 +1,713 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    time::{Duration, Instant},
+};
+
+use super::{
+    normal_forms::{
+        Clause, ClauseNormalForm, ConjunctiveNormalFormula, Literal, SkolemNormalFormula,
+    },
+    unification::{match_extending, unify, Substitution},
+    GenericAtomicFormula, GenericFormula, GenericTerm, Negation, Signature, Variable,
+};
+
+use equality::{contains_equality, equality_resolvents, paramodulants};
+
+mod equality;
+mod proof;
+
+pub use equality::{equality_axioms, EqualityHandling};
+pub use proof::{Inference, Proof, ProofStep};
+
+////////////////////////////////////////////////////////////////////////////////
+// Configuration
+////////////////////////////////////////////////////////////////////////////////
+
+/// How the next given clause is chosen from the passive set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClauseSelection {
+    /// Always choose the oldest clause (breadth-first).
+    Age,
+    /// Always choose the clause with the fewest symbols.
+    Weight,
+    /// Alternate: choose `age` clauses by age, then `weight` by weight.
+    AgeWeight {
+        /// The number of clauses chosen by age in each round.
+        age: usize,
+        /// The number of clauses chosen by weight in each round.
+        weight: usize,
+    },
+}
+
+impl Default for ClauseSelection {
+    fn default() -> Self {
+        Self::AgeWeight { age: 1, weight: 4 }
+    }
+}
+
+/// Limits on the resources a prover may use. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// The maximum number of given clauses to process.
+    pub max_iterations: Option<usize>,
+    /// The maximum number of clauses to keep.
+    pub max_clauses: Option<usize>,
+    /// The maximum time to search for.
+    pub timeout: Option<Duration>,
+}
+
+/// The outcome of a refutation attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefutationResult {
+    /// The clauses are unsatisfiable, as shown by the proof.
+    Unsatisfiable(Proof),
+    /// Every inference has been made without deriving the empty clause, so the
+    /// clauses are satisfiable (treating `=` as an ordinary predicate if
+    /// [EqualityHandling::None] was used).
+    Saturated,
+    /// A resource limit was reached before the search finished.
+    ResourceOut,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Prover
+////////////////////////////////////////////////////////////////////////////////
+
+/// A saturation-based prover using binary resolution and factoring.
+///
+/// The prover runs a given-clause loop: a clause is chosen from the passive
+/// set, every inference between it and the active set is made, and it is moved
+/// to the active set. Tautologies and clauses subsumed by an active clause are
+/// discarded.
+///
+/// Equalities are handled as set by [EqualityHandling]: by default the prover
+/// also makes paramodulation and equality resolution inferences.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   resolution::{RefutationResult, ResolutionProver}, Constant, GenericFormula,
+/// #   Implication, PredicateCall, Universal, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let socrates = Constant { label: 1 };
+/// let man = |t| -> GenericFormula { PredicateCall { predicate: b'M'.into(), terms: vec![t] }.into() };
+/// let mortal = |t| -> GenericFormula { PredicateCall { predicate: b'D'.into(), terms: vec![t] }.into() };
+///
+/// let axioms = vec![
+///     Universal { left: x, right: Implication { left: man(x.into()), right: mortal(x.into()) }.into() }.into(),
+///     man(socrates.into()),
+/// ];
+///
+/// let result = ResolutionProver::default().prove(&axioms, &mortal(socrates.into()));
+/// assert!(matches!(result, RefutationResult::Unsatisfiable(_)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResolutionProver {
+    /// How given clauses are chosen.
+    pub selection: ClauseSelection,
+    /// The resources the prover may use.
+    pub limits: ResourceLimits,
+    /// How equalities are reasoned with.
+    pub equality: EqualityHandling,
+}
+
+impl ResolutionProver {
+    /// Attempt to refute a set of clauses.
+    pub fn refute(&self, clauses: &ClauseNormalForm) -> RefutationResult {
+        Saturation::new(self).run(clauses)
+    }
+
+    /// Attempt to prove that a goal follows from some axioms, by refuting the
+    /// axioms together with the negated goal.
+    ///
+    /// The axioms and goal should be sentences: free variables are treated as
+    /// universally quantified in each clause, which for the negated goal is not
+    /// the same as negating its universal closure.
+    pub fn prove(&self, axioms: &[GenericFormula], goal: &GenericFormula) -> RefutationResult {
+        let negated_goal: GenericFormula = Negation {
+            right: goal.clone(),
+        }
+        .into();
+
+        let clauses: ClauseNormalForm = axioms
+            .iter()
+            .chain(std::iter::once(&negated_goal))
+            .flat_map(|f| clausify(f.clone()))
+            .collect();
+        self.refute(&clauses)
+    }
+}
+
+/// Convert a formula into clauses, via its prenex and Skolem normal forms.
+pub fn clausify(formula: GenericFormula) -> ClauseNormalForm {
+    ConjunctiveNormalFormula::from(SkolemNormalFormula::from(formula)).into()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Saturation
+////////////////////////////////////////////////////////////////////////////////
+
+struct StoredClause {
+    clause: Clause,
+    inference: Inference,
+    weight: usize,
+}
+
+/// The state of a single run of the given-clause loop.
+struct Saturation<'a> {
+    prover: &'a ResolutionProver,
+    clauses: Vec<StoredClause>,
+    active: Vec<usize>,
+    passive_by_age: BTreeSet<usize>,
+    passive_by_weight: BTreeSet<(usize, usize)>,
+    picks: usize,
+}
+
+enum Outcome {
+    Refuted(usize),
+    Continue,
+    ResourceOut,
+}
+
+impl<'a> Saturation<'a> {
+    fn new(prover: &'a ResolutionProver) -> Self {
+        Self {
+            prover,
+            clauses: Vec::new(),
+            active: Vec::new(),
+            passive_by_age: BTreeSet::new(),
+            passive_by_weight: BTreeSet::new(),
+            picks: 0,
+        }
+    }
+
+    fn run(mut self, input: &ClauseNormalForm) -> RefutationResult {
+        let start = Instant::now();
+        let limits = self.prover.limits;
+
+        for (i, literals) in input.iter().enumerate() {
+            let clause = Clause {
+                literals: literals.clone(),
+            };
+            if let Outcome::Refuted(id) = self.add(clause, Inference::Input(i)) {
+                return RefutationResult::Unsatisfiable(self.proof(id));
+            }
+        }
+
+        if self.prover.equality == EqualityHandling::Axioms && contains_equality(input) {
+            let mut signature = Signature::default();
+            input.iter().for_each(|c| signature.extend_from_literals(c));
+            for literals in equality_axioms(&signature) {
+                // No input clauses are refuted by the axioms alone.
+                self.add(Clause { literals }, Inference::EqualityAxiom);
+            }
+        }
+
+        let mut iterations = 0;
+        while let Some(given) = self.select() {
+            if limits.max_iterations.is_some_and(|m| iterations >= m)
+                || limits.timeout.is_some_and(|t| start.elapsed() >= t)
+            {
+                return RefutationResult::ResourceOut;
+            }
+            iterations += 1;
+
+            let given_clause = self.clauses[given].clause.clone();
+            if self
+                .active
+                .iter()
+                .any(|a| subsumes(&self.clauses[*a].clause, &given_clause))
+            {
+                continue;
+            }
+            let clauses = &self.clauses;
+            self.active
+                .retain(|a| !subsumes(&given_clause, &clauses[*a].clause));
+            self.active.push(given);
+
+            match self.infer(given) {
+                Outcome::Refuted(id) => return RefutationResult::Unsatisfiable(self.proof(id)),
+                Outcome::ResourceOut => return RefutationResult::ResourceOut,
+                Outcome::Continue => {}
+            }
+        }
+
+        RefutationResult::Saturated
+    }
+
+    /// Make every inference between the given clause and the active set.
+    fn infer(&mut self, given: usize) -> Outcome {
+        let mut new: Vec<(Clause, Inference)> = Vec::new();
+        let given_clause = &self.clauses[given].clause;
+
+        for factor in factors(given_clause) {
+            new.push((factor, Inference::Factoring { parent: given }));
+        }
+
+        let paramodulation = self.prover.equality == EqualityHandling::Paramodulation;
+        if paramodulation {
+            for resolvent in equality_resolvents(given_clause) {
+                new.push((resolvent, Inference::EqualityResolution { parent: given }));
+            }
+        }
+
+        for active in &self.active {
+            // The given clause is in the active set: rename it apart to resolve
+            // it with itself.
+            let renamed;
+            let other = if *active == given {
+                renamed = rename(given_clause);
+                &renamed
+            } else {
+                &self.clauses[*active].clause
+            };
+            for resolvent in resolvents(given_clause, other) {
+                new.push((
+                    resolvent,
+                    Inference::Resolution {
+                        left: given,
+                        right: *active,
+                    },
+                ));
+            }
+            if paramodulation {
+                for paramodulant in paramodulants(given_clause, other) {
+                    new.push((
+                        paramodulant,
+                        Inference::Paramodulation {
+                            from: given,
+                            into: *active,
+                        },
+                    ));
+                }
+                if *active != given {
+                    for paramodulant in paramodulants(other, given_clause) {
+                        new.push((
+                            paramodulant,
+                            Inference::Paramodulation {
+                                from: *active,
+                                into: given,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+
+        for (clause, inference) in new {
+            if let Outcome::Refuted(id) = self.add(clause, inference) {
+                return Outcome::Refuted(id);
+            }
+            if self
+                .prover
+                .limits
+                .max_clauses
+                .is_some_and(|m| self.clauses.len() >= m)
+            {
+                return Outcome::ResourceOut;
+            }
+        }
+        Outcome::Continue
+    }
+
+    /// Simplify a new clause and add it to the passive set, unless it is
+    /// redundant.
+    fn add(&mut self, clause: Clause, inference: Inference) -> Outcome {
+        // The equality axioms are kept as they are: reflexivity is itself a
+        // trivial equality, and would otherwise be deleted.
+        let clause = if inference == Inference::EqualityAxiom {
+            Some(clause)
+        } else {
+            simplify(clause, self.prover.equality != EqualityHandling::None)
+        };
+        let clause = match clause {
+            Some(c) => rename(&c),
+            None => return Outcome::Continue,
+        };
+        if self
+            .active
+            .iter()
+            .any(|a| subsumes(&self.clauses[*a].clause, &clause))
+        {
+            return Outcome::Continue;
+        }
+
+        let id = self.clauses.len();
+        let weight = clause.literals.iter().map(literal_weight).sum();
+        let empty = clause.is_empty();
+        self.clauses.push(StoredClause {
+            clause,
+            inference,
+            weight,
+        });
+
+        if empty {
+            return Outcome::Refuted(id);
+        }
+        self.passive_by_age.insert(id);
+        self.passive_by_weight.insert((weight, id));
+        Outcome::Continue
+    }
+
+    fn select(&mut self) -> Option<usize> {
+        let by_age = match self.prover.selection {
+            ClauseSelection::Age => true,
+            ClauseSelection::Weight => false,
+            ClauseSelection::AgeWeight { age, weight } => self.picks % (age + weight).max(1) < age,
+        };
+        self.picks += 1;
+
+        let id = if by_age {
+            self.passive_by_age.first().copied()
+        } else {
+            self.passive_by_weight.first().map(|(_, id)| *id)
+        }?;
+        self.passive_by_age.remove(&id);
+        self.passive_by_weight
+            .remove(&(self.clauses[id].weight, id));
+        Some(id)
+    }
+
+    /// Extract the derivation of a clause.
+    fn proof(&self, id: usize) -> Proof {
+        let mut ids = HashSet::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            if ids.insert(id) {
+                stack.extend(self.clauses[id].inference.parents());
+            }
+        }
+
+        let mut ids: Vec<usize> = ids.into_iter().collect();
+        ids.sort_unstable();
+        Proof {
+            steps: ids
+                .into_iter()
+                .map(|id| ProofStep {
+                    id,
+                    clause: self.clauses[id].clause.clone(),
+                    inference: self.clauses[id].inference.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Inferences
+////////////////////////////////////////////////////////////////////////////////
+
+/// Rename every variable in a clause to a fresh variable.
+fn rename(clause: &Clause) -> Clause {
+    let renaming: Substitution = clause
+        .variables()
+        .into_iter()
+        .map(|v| (v, Variable::rand().into()))
+        .collect();
+    renaming.apply(clause)
+}
+
+/// Every binary resolvent of two (variable-disjoint) clauses.
+fn resolvents(left: &Clause, right: &Clause) -> Vec<Clause> {
+    let mut resolvents = Vec::new();
+    for (i, l) in left.literals.iter().enumerate() {
+        for (j, r) in right.literals.iter().enumerate() {
+            if l.is_negated() == r.is_negated() {
+                continue;
+            }
+            if let Some(unifier) = unify(l.atom(), r.atom()) {
+                let literals = left
+                    .literals
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != i)
+                    .chain(right.literals.iter().enumerate().filter(|(k, _)| *k != j))
+                    .map(|(_, l)| unifier.apply(l))
+                    .collect();
+                resolvents.push(Clause { literals });
+            }
+        }
+    }
+    resolvents
+}
+
+/// Every factor of a clause made by unifying two of its literals.
+fn factors(clause: &Clause) -> Vec<Clause> {
+    let mut factors = Vec::new();
+    for (i, l) in clause.literals.iter().enumerate() {
+        for (j, r) in clause.literals.iter().enumerate().skip(i + 1) {
+            if let Some(unifier) = unify(l, r) {
+                let literals = clause
+                    .literals
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != j)
+                    .map(|(_, l)| unifier.apply(l))
+                    .collect();
+                factors.push(Clause { literals });
+            }
+        }
+    }
+    factors
+}
+
+/// Remove duplicate and false literals. Returns `None` if the clause is a
+/// tautology.
+///
+/// When reasoning with `equality`, trivial equalities `t = t` are treated as
+/// true. Otherwise `=` is an ordinary predicate, and they are left alone.
+fn simplify(clause: Clause, equality: bool) -> Option<Clause> {
+    let mut literals: Vec<Literal> = Vec::with_capacity(clause.literals.len());
+    for literal in clause.literals {
+        match (literal.atom(), literal.is_negated()) {
+            (GenericAtomicFormula::True, false) | (GenericAtomicFormula::False, true) => {
+                return None
+            }
+            (GenericAtomicFormula::True, true) | (GenericAtomicFormula::False, false) => continue,
+            (GenericAtomicFormula::Equality(e), false) if equality && e.left == e.right => {
+                return None
+            }
+            (GenericAtomicFormula::Equality(e), true) if equality && e.left == e.right => continue,
+            _ => {}
+        }
+        if literals.contains(&literal.negate()) {
+            return None;
+        }
+        if !literals.contains(&literal) {
+            literals.push(literal);
+        }
+    }
+    Some(Clause { literals })
+}
+
+/// Whether `c` subsumes `d`: some substitution maps every literal of `c` to a
+/// literal of `d`.
+fn subsumes(c: &Clause, d: &Clause) -> bool {
+    fn search(c: &[Literal], d: &[Literal], substitution: &Substitution) -> bool {
+        match c.split_first() {
+            None => true,
+            Some((literal, rest)) => d.iter().any(|target| {
+                match_extending(literal, target, substitution).is_some_and(|s| search(rest, d, &s))
+            }),
+        }
+    }
+    c.literals.len() <= d.literals.len() && search(&c.literals, &d.literals, &Substitution::new())
+}
+
+fn term_weight(term: &GenericTerm) -> usize {
+    match term {
+        GenericTerm::Variable(_) | GenericTerm::Constant(_) => 1,
+        GenericTerm::FunctionCall(f) => 1 + f.terms.iter().map(term_weight).sum::<usize>(),
+    }
+}
+
+fn literal_weight(literal: &Literal) -> usize {
+    match literal.atom() {
+        GenericAtomicFormula::Equality(e) => 1 + term_weight(&e.left) + term_weight(&e.right),
+        GenericAtomicFormula::Predicate(p) => 1 + p.terms.iter().map(term_weight).sum::<usize>(),
+        GenericAtomicFormula::True | GenericAtomicFormula::False => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::{Clause, ClauseNormalForm, Literal},
+        Constant, Existential, FunctionCall, GenericFormula, GenericTerm, Implication,
+        PredicateCall, Universal, Variable,
+    };
+
+    use super::{subsumes, ClauseSelection, RefutationResult, ResolutionProver, ResourceLimits};
+
+    fn atom(predicate: u8, terms: Vec<GenericTerm>) -> Literal {
+        Literal::Atom(
+            PredicateCall {
+                predicate: predicate.into(),
+                terms,
+            }
+            .into(),
+        )
+    }
+
+    fn var(c: u8) -> GenericTerm {
+        Variable::new(c.into()).into()
+    }
+
+    fn a() -> GenericTerm {
+        Constant { label: 1 }.into()
+    }
+
+    #[test]
+    fn test_refutation_with_proof() {
+        // {¬P(x) ∨ Q(x)}, {P(a)}, {¬Q(a)}
+        let clauses: ClauseNormalForm = vec![
+            vec![
+                atom(b'P', vec![var(b'x')]).negate(),
+                atom(b'Q', vec![var(b'x')]),
+            ],
+            vec![atom(b'P', vec![a()])],
+            vec![atom(b'Q', vec![a()]).negate()],
+        ];
+
+        for selection in [
+            ClauseSelection::Age,
+            ClauseSelection::Weight,
+            ClauseSelection::default(),
+        ] {
+            let prover = ResolutionProver {
+                selection,
+                ..ResolutionProver::default()
+            };
+            match prover.refute(&clauses) {
+                RefutationResult::Unsatisfiable(proof) => {
+                    let last = proof.steps.last().unwrap();
+                    assert!(last.clause.is_empty());
+                    for step in &proof.steps {
+                        for parent in step.inference.parents() {
+                            assert!(parent < step.id);
+                            assert!(proof.step(parent).is_some());
+                        }
+                    }
+                }
+                r => panic!("{:?}", r),
+            }
+        }
+    }
+
+    #[test]
+    fn test_factoring_required() {
+        // {P(x) ∨ P(y)}, {¬P(x) ∨ ¬P(y)} is only refutable with factoring.
+        let clauses: ClauseNormalForm = vec![
+            vec![atom(b'P', vec![var(b'x')]), atom(b'P', vec![var(b'y')])],
+            vec![
+                atom(b'P', vec![var(b'x')]).negate(),
+                atom(b'P', vec![var(b'y')]).negate(),
+            ],
+        ];
+        assert!(matches!(
+            ResolutionProver::default().refute(&clauses),
+            RefutationResult::Unsatisfiable(_)
+        ));
+    }
+
+    #[test]
+    fn test_saturated_and_resource_out() {
+        let clauses: ClauseNormalForm = vec![
+            vec![atom(b'P', vec![a()])],
+            vec![
+                atom(b'P', vec![var(b'x')]).negate(),
+                atom(b'Q', vec![var(b'x')]),
+            ],
+        ];
+        assert_eq!(
+            ResolutionProver::default().refute(&clauses),
+            RefutationResult::Saturated
+        );
+
+        // P(a), P(x) ⇒ P(f(x)), ¬Q(a) saturates forever.
+        let f = |t: GenericTerm| -> GenericTerm {
+            FunctionCall {
+                function: b'f'.into(),
+                terms: vec![t],
+            }
+            .into()
+        };
+        let clauses: ClauseNormalForm = vec![
+            vec![atom(b'P', vec![a()])],
+            vec![
+                atom(b'P', vec![var(b'x')]).negate(),
+                atom(b'P', vec![f(var(b'x'))]),
+            ],
+            vec![atom(b'Q', vec![a()]).negate()],
+        ];
+        let prover = ResolutionProver {
+            limits: ResourceLimits {
+                max_iterations: Some(50),
+                ..ResourceLimits::default()
+            },
+            ..ResolutionProver::default()
+        };
+        assert_eq!(prover.refute(&clauses), RefutationResult::ResourceOut);
+    }
+
+    #[test]
+    fn test_prove_with_skolemisation() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let r = |l: Variable, r: Variable| -> GenericFormula {
+            PredicateCall {
+                predicate: b'R'.into(),
+                terms: vec![l.into(), r.into()],
+            }
+            .into()
+        };
+        let s = |t: Variable| -> GenericFormula {
+            PredicateCall {
+                predicate: b'S'.into(),
+                terms: vec![t.into()],
+            }
+            .into()
+        };
+
+        // ∀x.∃y.R(x, y), ∀x.∀y.(R(x, y) ⇒ S(y)) ⊢ ∃y.S(y)
+        let axioms: Vec<GenericFormula> = vec![
+            Universal {
+                left: x,
+                right: Existential {
+                    left: y,
+                    right: r(x, y),
+                }
+                .into(),
+            }
+            .into(),
+            Universal {
+                left: x,
+                right: Universal {
+                    left: y,
+                    right: Implication {
+                        left: r(x, y),
+                        right: s(y),
+                    }
+                    .into(),
+                }
+                .into(),
+            }
+            .into(),
+        ];
+        let goal: GenericFormula = Existential {
+            left: y,
+            right: s(y),
+        }
+        .into();
+
+        assert!(matches!(
+            ResolutionProver::default().prove(&axioms, &goal),
+            RefutationResult::Unsatisfiable(_)
+        ));
+
+        // But ∀x.S(x) does not follow.
+        let goal: GenericFormula = Universal {
+            left: x,
+            right: s(x),
+        }
+        .into();
+        assert_eq!(
+            ResolutionProver::default().prove(&axioms, &goal),
+            RefutationResult::Saturated
+        );
+    }
+
+    #[test]
+    fn test_subsumption() {
+        let general = Clause {
+            literals: vec![atom(b'P', vec![var(b'x')])],
+        };
+        let specific = Clause {
+            literals: vec![atom(b'P', vec![a()]), atom(b'Q', vec![a()])],
+        };
+        assert!(subsumes(&general, &specific));
+        assert!(!subsumes(&specific, &general));
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Display;
+
+use crate::syntax::normal_forms::Clause;
+
+/// How a clause in a [Proof] was derived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inference {
+    /// An input clause, given by its index in the input.
+    Input(usize),
+    /// Binary resolution between two earlier steps.
+    Resolution {
+        /// The step containing the resolved-upon positive or negative literal.
+        left: usize,
+        /// The step containing the complementary literal.
+        right: usize,
+    },
+    /// Factoring of an earlier step.
+    Factoring {
+        /// The factored step.
+        parent: usize,
+    },
+    /// An axiom of equality, added by the prover (see
+    /// [equality_axioms](super::equality_axioms)).
+    EqualityAxiom,
+    /// Paramodulation of an equality from one step into another.
+    Paramodulation {
+        /// The step containing the equality used to rewrite.
+        from: usize,
+        /// The step which is rewritten.
+        into: usize,
+    },
+    /// Equality resolution: removal of a negated equality whose sides unify.
+    EqualityResolution {
+        /// The step containing the negated equality.
+        parent: usize,
+    },
+}
+
+impl Inference {
+    /// The ids of the steps this inference was made from.
+    pub fn parents(&self) -> Vec<usize> {
+        match self {
+            Self::Input(_) | Self::EqualityAxiom => vec![],
+            Self::Resolution { left, right } => vec![*left, *right],
+            Self::Factoring { parent } | Self::EqualityResolution { parent } => vec![*parent],
+            Self::Paramodulation { from, into } => vec![*from, *into],
+        }
+    }
+}
+
+impl Display for Inference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Input(i) => f.write_fmt(format_args!("input {}", i)),
+            Self::Resolution { left, right } => {
+                f.write_fmt(format_args!("resolution {}, {}", left, right))
+            }
+            Self::Factoring { parent } => f.write_fmt(format_args!("factoring {}", parent)),
+            Self::EqualityAxiom => f.write_str("equality axiom"),
+            Self::Paramodulation { from, into } => {
+                f.write_fmt(format_args!("paramodulation {}, {}", from, into))
+            }
+            Self::EqualityResolution { parent } => {
+                f.write_fmt(format_args!("equality resolution {}", parent))
+            }
+        }
+    }
+}
+
+/// A single derived clause in a [Proof].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofStep {
+    /// The id of the step, referenced by later steps.
+    pub id: usize,
+    /// The derived clause.
+    pub clause: Clause,
+    /// How the clause was derived.
+    pub inference: Inference,
+}
+
+/// A refutation: a derivation of the empty clause.
+///
+/// The steps are in order of derivation, so every step's parents come before
+/// it, and the final step is the empty clause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    /// The steps of the proof.
+    pub steps: Vec<ProofStep>,
+}
+
+impl Proof {
+    /// Get a step by its id.
+    pub fn step(&self, id: usize) -> Option<&ProofStep> {
+        self.steps.iter().find(|s| s.id == id)
+    }
+}
+
+impl Display for Proof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            f.write_fmt(format_args!("{}. ", step.id))?;
+            if step.clause.is_empty() {
+                f.write_str("⊥")?;
+            } else {
+                step.clause.fmt(f)?;
+            }
+            f.write_fmt(format_args!(" [{}]\n", step.inference))?;
+        }
+        Ok(())
+    }
+}
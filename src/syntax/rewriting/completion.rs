@@ -0,0 +1,212 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use crate::syntax::{ordering::TermOrdering, Equality, GenericTerm};
+
+use super::{critical_pairs, RewriteRule, RewriteSystem, Strategy};
+
+/// The reasons Knuth–Bendix completion can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompletionError {
+    /// An equation could not be oriented by the ordering, such as a
+    /// commutativity law.
+    Unorientable(Equality<GenericTerm, GenericTerm>),
+    /// The limit on the number of equations to process was reached.
+    ResourceOut,
+}
+
+impl Display for CompletionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unorientable(x) => f.write_fmt(format_args!("cannot orient {}", x)),
+            Self::ResourceOut => f.write_str("resource limit reached"),
+        }
+    }
+}
+
+impl std::error::Error for CompletionError {}
+
+/// Complete a set of equations into a convergent rewrite system.
+///
+/// Equations are normalised by the rules found so far, and oriented by the
+/// ordering into new rules. Each new rule is used to simplify the existing
+/// rules, and its critical pairs with them become new equations. If every
+/// equation is either joinable or oriented, the resulting system is
+/// terminating and confluent, and decides the equational theory.
+///
+/// At most `max_steps` equations are processed.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   ordering::{Head, LexicographicPathOrdering, Precedence},
+/// #   rewriting::{complete, Strategy}, Constant, Equality, FunctionCall, GenericTerm,
+/// #   Variable,
+/// # };
+/// let x: GenericTerm = Variable::new(b'x'.into()).into();
+/// let a: GenericTerm = Constant { label: b'a'.into() }.into();
+/// let f = |t| -> GenericTerm { FunctionCall { function: b'f'.into(), terms: vec![t] }.into() };
+///
+/// // f(f(x)) = x, f(a) = a
+/// let equations = vec![
+///     Equality { left: f(f(x.clone())), right: x },
+///     Equality { left: f(a.clone()), right: a.clone() },
+/// ];
+/// let system = complete(&equations, &LexicographicPathOrdering::default(), 100).unwrap();
+/// assert_eq!(system.normalise(&f(f(f(a.clone()))), Strategy::Innermost), a);
+/// ```
+pub fn complete<O: TermOrdering + ?Sized>(
+    equations: &[Equality<GenericTerm, GenericTerm>],
+    ordering: &O,
+    max_steps: usize,
+) -> Result<RewriteSystem, CompletionError> {
+    let mut pending: VecDeque<(GenericTerm, GenericTerm)> = equations
+        .iter()
+        .map(|e| (e.left.clone(), e.right.clone()))
+        .collect();
+    let mut system = RewriteSystem::default();
+
+    let mut steps = 0;
+    while let Some((s, t)) = pending.pop_front() {
+        if steps >= max_steps {
+            return Err(CompletionError::ResourceOut);
+        }
+        steps += 1;
+
+        let s = system.normalise(&s, Strategy::Innermost);
+        let t = system.normalise(&t, Strategy::Innermost);
+        if s == t {
+            continue;
+        }
+
+        let rule = if ordering.greater(&s, &t) {
+            RewriteRule { left: s, right: t }
+        } else if ordering.greater(&t, &s) {
+            RewriteRule { left: t, right: s }
+        } else {
+            return Err(CompletionError::Unorientable(Equality {
+                left: s,
+                right: t,
+            }));
+        };
+
+        // Rules whose left-hand side can be rewritten by the new rule become
+        // equations again; the others have their right-hand sides simplified.
+        let new = RewriteSystem {
+            rules: vec![rule.clone()],
+        };
+        let mut kept = Vec::with_capacity(system.rules.len() + 1);
+        for old in system.rules {
+            if new.is_normal(&old.left) {
+                kept.push(old);
+            } else {
+                pending.push_back((old.left, old.right));
+            }
+        }
+        kept.push(rule.clone());
+        system.rules = kept;
+        for i in 0..system.rules.len() {
+            let right = system.normalise(&system.rules[i].right, Strategy::Innermost);
+            system.rules[i].right = right;
+        }
+
+        for other in &system.rules {
+            pending.extend(critical_pairs(&rule, other));
+            if *other != rule {
+                pending.extend(critical_pairs(other, &rule));
+            }
+        }
+    }
+
+    Ok(system)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        ordering::{Head, LexicographicPathOrdering, Precedence},
+        Constant, Equality, FunctionCall, GenericTerm, Variable,
+    };
+
+    use super::{complete, CompletionError, Strategy};
+
+    fn var(c: u8) -> GenericTerm {
+        Variable::new(c.into()).into()
+    }
+
+    fn e() -> GenericTerm {
+        Constant { label: b'e'.into() }.into()
+    }
+
+    fn mul(l: GenericTerm, r: GenericTerm) -> GenericTerm {
+        FunctionCall {
+            function: b'*'.into(),
+            terms: vec![l, r],
+        }
+        .into()
+    }
+
+    fn inv(t: GenericTerm) -> GenericTerm {
+        FunctionCall {
+            function: b'i'.into(),
+            terms: vec![t],
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_group_completion() {
+        let (x, y, z) = (var(b'x'), var(b'y'), var(b'z'));
+        let equations = vec![
+            Equality {
+                left: mul(e(), x.clone()),
+                right: x.clone(),
+            },
+            Equality {
+                left: mul(inv(x.clone()), x.clone()),
+                right: e(),
+            },
+            Equality {
+                left: mul(mul(x.clone(), y.clone()), z.clone()),
+                right: mul(x.clone(), mul(y.clone(), z)),
+            },
+        ];
+        // e < * < i
+        let lpo = LexicographicPathOrdering::new(Precedence::new(vec![
+            Head::Constant(b'e'.into()),
+            Head::Function(b'*'.into()),
+            Head::Function(b'i'.into()),
+        ]));
+
+        let system = complete(&equations, &lpo, 10_000).unwrap();
+        assert_eq!(system.rules.len(), 10);
+        assert!(system.is_locally_confluent());
+
+        // i(x * y) = i(y) * i(x), and x * i(x) = e.
+        assert_eq!(
+            system.normalise(&inv(mul(x.clone(), y.clone())), Strategy::Innermost),
+            mul(inv(y.clone()), inv(x.clone()))
+        );
+        assert_eq!(
+            system.normalise(&mul(y.clone(), inv(y)), Strategy::Innermost),
+            e()
+        );
+        assert_eq!(
+            system.normalise(&inv(inv(x.clone())), Strategy::Innermost),
+            x
+        );
+    }
+
+    #[test]
+    fn test_unorientable() {
+        let (x, y) = (var(b'x'), var(b'y'));
+        let commutativity = Equality {
+            left: mul(x.clone(), y.clone()),
+            right: mul(y, x),
+        };
+        assert!(matches!(
+            complete(&[commutativity], &LexicographicPathOrdering::default(), 100),
+            Err(CompletionError::Unorientable(_))
+        ));
+    }
+}
@@ -0,0 +1,409 @@
+use std::{convert::TryFrom, fmt::Display};
+
+use super::{
+    unification::{match_term, unify, Substitution},
+    Equality, FunctionCall, GenericTerm, Variable,
+};
+
+mod completion;
+
+pub use completion::{complete, CompletionError};
+
+////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////
+
+/// The reasons an equation cannot be used as a rewrite rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    /// The left-hand side is a variable, so the rule would rewrite every term.
+    VariableLeft(Variable),
+    /// A variable of the right-hand side does not occur in the left-hand side.
+    UnboundVariable(Variable),
+}
+
+impl Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VariableLeft(x) => {
+                f.write_fmt(format_args!("left-hand side is the variable {}", x))
+            }
+            Self::UnboundVariable(x) => f.write_fmt(format_args!(
+                "variable {} does not occur in the left-hand side",
+                x
+            )),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Positions
+////////////////////////////////////////////////////////////////////////////////
+
+/// Every non-variable subterm of a term, with the path of argument indices
+/// leading to it from `path`. Parents come before their arguments.
+pub(crate) fn subterms<'a>(
+    term: &'a GenericTerm,
+    path: &mut Vec<usize>,
+    out: &mut Vec<(Vec<usize>, &'a GenericTerm)>,
+) {
+    if let GenericTerm::Variable(_) = term {
+        return;
+    }
+    out.push((path.clone(), term));
+    if let GenericTerm::FunctionCall(f) = term {
+        for (i, t) in f.terms.iter().enumerate() {
+            path.push(i);
+            subterms(t, path, out);
+            path.pop();
+        }
+    }
+}
+
+/// Replace the subterm of a term at a path of argument indices.
+pub(crate) fn replace_at(
+    term: &GenericTerm,
+    path: &[usize],
+    replacement: &GenericTerm,
+) -> GenericTerm {
+    match (path.split_first(), term) {
+        (None, _) => replacement.clone(),
+        (Some((i, rest)), GenericTerm::FunctionCall(f)) => {
+            let mut terms = f.terms.clone();
+            terms[*i] = replace_at(&f.terms[*i], rest, replacement);
+            FunctionCall {
+                function: f.function,
+                terms,
+            }
+            .into()
+        }
+        _ => unreachable!("paths only lead through function calls"),
+    }
+}
+
+/// Rename every variable in some terms to a fresh variable.
+fn rename(terms: &[&GenericTerm]) -> Vec<GenericTerm> {
+    let mut variables = Vec::new();
+    terms
+        .iter()
+        .for_each(|t| t.collect_variables(&mut variables));
+    let renaming: Substitution = variables
+        .into_iter()
+        .map(|v| (v, Variable::rand().into()))
+        .collect();
+    terms.iter().map(|t| renaming.apply(*t)).collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Rules
+////////////////////////////////////////////////////////////////////////////////
+
+/// A rewrite rule `l → r`, which replaces instances of `l` by the
+/// corresponding instances of `r`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RewriteRule {
+    /// The pattern to rewrite.
+    pub left: GenericTerm,
+    /// The term to replace it with.
+    pub right: GenericTerm,
+}
+
+impl RewriteRule {
+    /// Create a rule. The left-hand side must not be a variable, and must
+    /// contain every variable of the right-hand side.
+    pub fn new(left: GenericTerm, right: GenericTerm) -> Result<Self, RuleError> {
+        if let GenericTerm::Variable(v) = left {
+            return Err(RuleError::VariableLeft(v));
+        }
+        if let Some(v) = right.variables().into_iter().find(|v| !left.contains(v)) {
+            return Err(RuleError::UnboundVariable(v));
+        }
+        Ok(Self { left, right })
+    }
+
+    /// Rewrite a term at its root, if it is an instance of the left-hand side.
+    pub fn apply(&self, term: &GenericTerm) -> Option<GenericTerm> {
+        match_term(&self.left, term).map(|matcher| matcher.apply(&self.right))
+    }
+}
+
+impl TryFrom<Equality<GenericTerm, GenericTerm>> for RewriteRule {
+    type Error = RuleError;
+
+    fn try_from(e: Equality<GenericTerm, GenericTerm>) -> Result<Self, Self::Error> {
+        Self::new(e.left, e.right)
+    }
+}
+
+impl From<RewriteRule> for Equality<GenericTerm, GenericTerm> {
+    fn from(r: RewriteRule) -> Self {
+        Equality {
+            left: r.left,
+            right: r.right,
+        }
+    }
+}
+
+impl Display for RewriteRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} → {}", self.left, self.right))
+    }
+}
+
+/// Every critical pair between two rules: the two ways of rewriting a term
+/// in which the left-hand sides overlap.
+///
+/// If the left-hand side of `inner` unifies with a non-variable subterm of
+/// the left-hand side of `outer`, by `σ`, then `(outer.rightσ,
+/// outer.left[inner.right]σ)` is a critical pair. The rules are renamed apart
+/// first, so a rule can be overlapped with itself.
+pub fn critical_pairs(outer: &RewriteRule, inner: &RewriteRule) -> Vec<(GenericTerm, GenericTerm)> {
+    let [inner_left, inner_right]: [GenericTerm; 2] =
+        rename(&[&inner.left, &inner.right]).try_into().unwrap();
+    let same = outer == inner;
+
+    let mut positions = Vec::new();
+    subterms(&outer.left, &mut Vec::new(), &mut positions);
+
+    let mut pairs = Vec::new();
+    for (path, subterm) in positions {
+        // A rule overlaps itself trivially at the root.
+        if same && path.is_empty() {
+            continue;
+        }
+        if let Some(unifier) = unify(subterm, &inner_left) {
+            pairs.push((
+                unifier.apply(&outer.right),
+                unifier.apply(&replace_at(&outer.left, &path, &inner_right)),
+            ));
+        }
+    }
+    pairs
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Rewrite Systems
+////////////////////////////////////////////////////////////////////////////////
+
+/// The order in which redexes are rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Rewrite the leftmost of the innermost redexes, so arguments are
+    /// normalised before the terms containing them.
+    #[default]
+    Innermost,
+    /// Rewrite the leftmost of the outermost redexes.
+    Outermost,
+}
+
+/// A set of rewrite rules.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   rewriting::{RewriteRule, RewriteSystem, Strategy}, Constant, FunctionCall,
+/// #   GenericTerm, Variable,
+/// # };
+/// let x: GenericTerm = Variable::new(b'x'.into()).into();
+/// let zero: GenericTerm = Constant { label: b'0'.into() }.into();
+/// let s = |t| -> GenericTerm { FunctionCall { function: b's'.into(), terms: vec![t] }.into() };
+/// let add = |l, r| -> GenericTerm {
+///     FunctionCall { function: b'+'.into(), terms: vec![l, r] }.into()
+/// };
+///
+/// // 0 + x → x, s(x) + y → s(x + y)
+/// let y: GenericTerm = Variable::new(b'y'.into()).into();
+/// let system = RewriteSystem {
+///     rules: vec![
+///         RewriteRule::new(add(zero.clone(), x.clone()), x.clone()).unwrap(),
+///         RewriteRule::new(add(s(x.clone()), y.clone()), s(add(x, y))).unwrap(),
+///     ],
+/// };
+///
+/// let two = s(s(zero.clone()));
+/// let four = s(s(two.clone()));
+/// assert_eq!(system.normalise(&add(two.clone(), two), Strategy::Innermost), four);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RewriteSystem {
+    /// The rules of the system.
+    pub rules: Vec<RewriteRule>,
+}
+
+impl RewriteSystem {
+    /// Rewrite a term at its root with the first applicable rule.
+    fn rewrite_root(&self, term: &GenericTerm) -> Option<GenericTerm> {
+        self.rules.iter().find_map(|r| r.apply(term))
+    }
+
+    /// Rewrite the arguments of a function call, stopping at the first
+    /// argument that can be rewritten.
+    fn rewrite_arguments(&self, term: &GenericTerm, strategy: Strategy) -> Option<GenericTerm> {
+        let f = match term {
+            GenericTerm::FunctionCall(f) => f,
+            GenericTerm::Variable(_) | GenericTerm::Constant(_) => return None,
+        };
+        f.terms.iter().enumerate().find_map(|(i, t)| {
+            self.rewrite_step(t, strategy).map(|rewritten| {
+                let mut terms = f.terms.clone();
+                terms[i] = rewritten;
+                FunctionCall {
+                    function: f.function,
+                    terms,
+                }
+                .into()
+            })
+        })
+    }
+
+    /// Make a single rewrite step, or return `None` if the term is in normal
+    /// form.
+    pub fn rewrite_step(&self, term: &GenericTerm, strategy: Strategy) -> Option<GenericTerm> {
+        match strategy {
+            Strategy::Innermost => self
+                .rewrite_arguments(term, strategy)
+                .or_else(|| self.rewrite_root(term)),
+            Strategy::Outermost => self
+                .rewrite_root(term)
+                .or_else(|| self.rewrite_arguments(term, strategy)),
+        }
+    }
+
+    /// Rewrite a term until no rule applies.
+    ///
+    /// This only terminates if the system is terminating; see
+    /// [normalise_bounded](RewriteSystem::normalise_bounded) otherwise.
+    pub fn normalise(&self, term: &GenericTerm, strategy: Strategy) -> GenericTerm {
+        let mut term = term.clone();
+        while let Some(next) = self.rewrite_step(&term, strategy) {
+            term = next;
+        }
+        term
+    }
+
+    /// Rewrite a term until no rule applies, making at most `max_steps`
+    /// steps. Returns `None` if the limit is reached.
+    pub fn normalise_bounded(
+        &self,
+        term: &GenericTerm,
+        strategy: Strategy,
+        max_steps: usize,
+    ) -> Option<GenericTerm> {
+        let mut term = term.clone();
+        for _ in 0..=max_steps {
+            match self.rewrite_step(&term, strategy) {
+                Some(next) => term = next,
+                None => return Some(term),
+            }
+        }
+        None
+    }
+
+    /// Whether a term is in normal form.
+    pub fn is_normal(&self, term: &GenericTerm) -> bool {
+        self.rewrite_step(term, Strategy::Outermost).is_none()
+    }
+
+    /// Every critical pair between the rules of the system.
+    pub fn critical_pairs(&self) -> Vec<(GenericTerm, GenericTerm)> {
+        self.rules
+            .iter()
+            .flat_map(|outer| {
+                self.rules
+                    .iter()
+                    .flat_map(move |inner| critical_pairs(outer, inner))
+            })
+            .collect()
+    }
+
+    /// Whether every critical pair of the system is joinable, which for a
+    /// terminating system means it is confluent.
+    pub fn is_locally_confluent(&self) -> bool {
+        self.critical_pairs().iter().all(|(s, t)| {
+            self.normalise(s, Strategy::Innermost) == self.normalise(t, Strategy::Innermost)
+        })
+    }
+}
+
+impl Display for RewriteSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for rule in &self.rules {
+            f.write_fmt(format_args!("{}\n", rule))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{Constant, FunctionCall, GenericTerm, Variable};
+
+    use super::{critical_pairs, RewriteRule, RewriteSystem, RuleError, Strategy};
+
+    fn x() -> GenericTerm {
+        Variable::new(b'x'.into()).into()
+    }
+
+    fn a() -> GenericTerm {
+        Constant { label: b'a'.into() }.into()
+    }
+
+    fn call(function: u8, terms: Vec<GenericTerm>) -> GenericTerm {
+        FunctionCall {
+            function: function.into(),
+            terms,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert_eq!(
+            RewriteRule::new(x(), a()),
+            Err(RuleError::VariableLeft(Variable::new(b'x'.into())))
+        );
+        assert_eq!(
+            RewriteRule::new(a(), x()),
+            Err(RuleError::UnboundVariable(Variable::new(b'x'.into())))
+        );
+    }
+
+    #[test]
+    fn test_strategies() {
+        // f(x) → a, g(x) → g(x): innermost rewriting of f(g(a)) loops, but
+        // outermost rewriting discards the argument.
+        let system = RewriteSystem {
+            rules: vec![
+                RewriteRule::new(call(b'f', vec![x()]), a()).unwrap(),
+                RewriteRule::new(call(b'g', vec![x()]), call(b'g', vec![x()])).unwrap(),
+            ],
+        };
+        let term = call(b'f', vec![call(b'g', vec![a()])]);
+
+        assert_eq!(
+            system.normalise_bounded(&term, Strategy::Outermost, 10),
+            Some(a())
+        );
+        assert_eq!(
+            system.normalise_bounded(&term, Strategy::Innermost, 10),
+            None
+        );
+    }
+
+    #[test]
+    fn test_critical_pairs() {
+        // f(f(x)) → a overlaps itself at f(x): f(f(f(y))) rewrites to both
+        // f(a) and a.
+        let rule = RewriteRule::new(call(b'f', vec![call(b'f', vec![x()])]), a()).unwrap();
+        let pairs = critical_pairs(&rule, &rule);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].0, a());
+        assert_eq!(pairs[0].1, call(b'f', vec![a()]));
+
+        let system = RewriteSystem { rules: vec![rule] };
+        assert!(!system.is_locally_confluent());
+    }
+}
@@ -0,0 +1,648 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    fmt::Display,
+};
+
+use super::{
+    normal_forms::{ClauseNormalForm, Literal},
+    GenericAtomicFormula,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+// Results
+////////////////////////////////////////////////////////////////////////////////
+
+/// The errors which can occur when solving a clause set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SatError {
+    /// A clause contains a literal with a variable in it, so is not
+    /// propositional.
+    NotGround(Literal),
+}
+
+impl Display for SatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotGround(x) => f.write_fmt(format_args!("literal {} is not ground", x)),
+        }
+    }
+}
+
+impl std::error::Error for SatError {}
+
+/// The outcome of solving a ground clause set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SatResult {
+    /// The clauses are satisfied by the model, which gives a value to every
+    /// atom in them other than `⊤` and `⊥`.
+    Satisfiable(HashMap<GenericAtomicFormula, bool>),
+    /// The clauses are unsatisfiable. The core is the indices of a subset of
+    /// the input clauses which is itself unsatisfiable, in increasing order.
+    /// It is not necessarily minimal.
+    Unsatisfiable(Vec<usize>),
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Solver
+////////////////////////////////////////////////////////////////////////////////
+
+/// A conflict-driven clause learning SAT solver for ground clauses.
+///
+/// Each distinct ground atom is a propositional variable. The solver uses two
+/// watched literals per clause for unit propagation, learns the first-UIP
+/// clause of each conflict, chooses decision variables by activity (VSIDS)
+/// with phase saving, and restarts on the Luby sequence.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   normal_forms::Literal, sat::{SatResult, SatSolver}, Constant, PredicateCall,
+/// # };
+/// let p = |c: u64| Literal::Atom(
+///     PredicateCall { predicate: b'P'.into(), terms: vec![Constant { label: c }.into()] }.into()
+/// );
+///
+/// // P(1), P(1) ⇒ P(2), ¬P(2) ∨ ¬P(3), P(3) ∨ P(4).
+/// let mut clauses = vec![
+///     vec![p(1)],
+///     vec![p(1).negate(), p(2)],
+///     vec![p(2).negate(), p(3).negate()],
+///     vec![p(3), p(4)],
+/// ];
+/// match SatSolver::default().solve(&clauses).unwrap() {
+///     SatResult::Satisfiable(model) => assert!(model[p(4).atom()]),
+///     result => panic!("{:?}", result),
+/// }
+///
+/// // Adding ¬P(4) makes the clauses unsatisfiable, and every clause is needed.
+/// clauses.push(vec![p(4).negate()]);
+/// assert_eq!(
+///     SatSolver::default().solve(&clauses).unwrap(),
+///     SatResult::Unsatisfiable(vec![0, 1, 2, 3, 4]),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SatSolver {
+    /// The number of conflicts in the first run between restarts, which is
+    /// scaled by the Luby sequence for later runs.
+    pub restart_base: u64,
+    /// The factor variable activities decay by after each conflict.
+    pub variable_decay: f64,
+}
+
+impl Default for SatSolver {
+    fn default() -> Self {
+        Self {
+            restart_base: 100,
+            variable_decay: 0.95,
+        }
+    }
+}
+
+impl SatSolver {
+    /// Decide whether a set of ground clauses is satisfiable.
+    pub fn solve(&self, clauses: &ClauseNormalForm) -> Result<SatResult, SatError> {
+        let mut atoms: Vec<GenericAtomicFormula> = Vec::new();
+        let mut variables: HashMap<GenericAtomicFormula, usize> = HashMap::new();
+        let mut encoded: Vec<(usize, Vec<Lit>)> = Vec::new();
+
+        'clauses: for (index, clause) in clauses.iter().enumerate() {
+            let mut lits: Vec<Lit> = Vec::new();
+            for literal in clause {
+                let atom = literal.atom();
+                if !atom.is_ground() {
+                    return Err(SatError::NotGround(literal.clone()));
+                }
+                match (atom, literal.is_negated()) {
+                    (GenericAtomicFormula::True, false) | (GenericAtomicFormula::False, true) => {
+                        continue 'clauses
+                    }
+                    (GenericAtomicFormula::True, true) | (GenericAtomicFormula::False, false) => {
+                        continue
+                    }
+                    _ => {}
+                }
+                let variable = *variables.entry(atom.clone()).or_insert_with(|| {
+                    atoms.push(atom.clone());
+                    atoms.len() - 1
+                });
+                let lit = Lit::new(variable, literal.is_negated());
+                if lits.contains(&lit.negate()) {
+                    // A tautology.
+                    continue 'clauses;
+                }
+                if !lits.contains(&lit) {
+                    lits.push(lit);
+                }
+            }
+            encoded.push((index, lits));
+        }
+
+        let mut search = Search::new(self, atoms.len());
+        let outcome = search.run(encoded);
+        Ok(match outcome {
+            Ok(()) => SatResult::Satisfiable(
+                atoms
+                    .into_iter()
+                    .enumerate()
+                    .map(|(v, atom)| (atom, search.assignment[v] == Some(true)))
+                    .collect(),
+            ),
+            Err(core) => SatResult::Unsatisfiable(core),
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Search
+////////////////////////////////////////////////////////////////////////////////
+
+/// A propositional literal: a variable index and a sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Lit(usize);
+
+impl Lit {
+    fn new(variable: usize, negated: bool) -> Self {
+        Self(2 * variable + negated as usize)
+    }
+
+    fn variable(self) -> usize {
+        self.0 / 2
+    }
+
+    fn is_negated(self) -> bool {
+        self.0 % 2 == 1
+    }
+
+    fn negate(self) -> Self {
+        Self(self.0 ^ 1)
+    }
+}
+
+/// The value of a literal under an assignment.
+fn value(assignment: &[Option<bool>], lit: Lit) -> Option<bool> {
+    assignment[lit.variable()].map(|v| v != lit.is_negated())
+}
+
+/// The union of two sorted lists of clause indices.
+fn merge(into: &mut Vec<usize>, from: &[usize]) {
+    if from.is_empty() {
+        return;
+    }
+    into.extend_from_slice(from);
+    into.sort_unstable();
+    into.dedup();
+}
+
+/// The `i`th element of the Luby sequence `1, 1, 2, 1, 1, 2, 4, …`, from
+/// zero.
+fn luby(mut i: u64) -> u64 {
+    let (mut size, mut sequence) = (1, 0);
+    while size < i + 1 {
+        sequence += 1;
+        size = 2 * size + 1;
+    }
+    while size - 1 != i {
+        size = (size - 1) / 2;
+        sequence -= 1;
+        i %= size;
+    }
+    1 << sequence
+}
+
+struct StoredClause {
+    lits: Vec<Lit>,
+    /// The input clauses this clause was derived from.
+    origins: Vec<usize>,
+}
+
+/// A variable activity in the decision heap.
+#[derive(PartialEq)]
+struct Activity(f64, usize);
+
+impl Eq for Activity {}
+
+impl PartialOrd for Activity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Activity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(other.1.cmp(&self.1))
+    }
+}
+
+struct Search<'a> {
+    solver: &'a SatSolver,
+    clauses: Vec<StoredClause>,
+    /// The clauses watching each literal, indexed by the literal.
+    watches: Vec<Vec<usize>>,
+    assignment: Vec<Option<bool>>,
+    level: Vec<usize>,
+    reason: Vec<Option<usize>>,
+    /// For variables assigned at level zero, the input clauses their value
+    /// follows from.
+    fixed_origins: Vec<Vec<usize>>,
+    trail: Vec<Lit>,
+    /// The start of each decision level on the trail.
+    trail_limits: Vec<usize>,
+    propagated: usize,
+    activity: Vec<f64>,
+    increment: f64,
+    /// A heap of variables by activity. Entries may be stale, and are
+    /// checked when popped.
+    heap: BinaryHeap<Activity>,
+    phase: Vec<bool>,
+}
+
+impl<'a> Search<'a> {
+    fn new(solver: &'a SatSolver, variables: usize) -> Self {
+        Self {
+            solver,
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * variables],
+            assignment: vec![None; variables],
+            level: vec![0; variables],
+            reason: vec![None; variables],
+            fixed_origins: vec![Vec::new(); variables],
+            trail: Vec::new(),
+            trail_limits: Vec::new(),
+            propagated: 0,
+            activity: vec![0.0; variables],
+            increment: 1.0,
+            heap: (0..variables).map(|v| Activity(0.0, v)).collect(),
+            phase: vec![true; variables],
+        }
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_limits.len()
+    }
+
+    /// Add a clause, returning its index. Clauses with at least two literals
+    /// are watched on their first two.
+    fn add_clause(&mut self, lits: Vec<Lit>, origins: Vec<usize>) -> usize {
+        let index = self.clauses.len();
+        if lits.len() >= 2 {
+            self.watches[lits[0].0].push(index);
+            self.watches[lits[1].0].push(index);
+        }
+        self.clauses.push(StoredClause { lits, origins });
+        index
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<usize>) {
+        let v = lit.variable();
+        self.assignment[v] = Some(!lit.is_negated());
+        self.level[v] = self.decision_level();
+        self.reason[v] = reason;
+        if let (0, Some(reason)) = (self.decision_level(), reason) {
+            let mut origins = self.clauses[reason].origins.clone();
+            for other in &self.clauses[reason].lits {
+                if other.variable() != v {
+                    merge(&mut origins, &self.fixed_origins[other.variable()]);
+                }
+            }
+            self.fixed_origins[v] = origins;
+        }
+        self.trail.push(lit);
+    }
+
+    /// Propagate the assignments on the trail, returning a conflicting clause
+    /// if one is found.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagated < self.trail.len() {
+            let falsified = self.trail[self.propagated].negate();
+            self.propagated += 1;
+
+            let mut watching = std::mem::take(&mut self.watches[falsified.0]);
+            let mut i = 0;
+            while i < watching.len() {
+                let index = watching[i];
+                let lits = &mut self.clauses[index].lits;
+                if lits[0] == falsified {
+                    lits.swap(0, 1);
+                }
+                if value(&self.assignment, lits[0]) == Some(true) {
+                    i += 1;
+                    continue;
+                }
+
+                // Look for a new literal to watch.
+                if let Some(k) =
+                    (2..lits.len()).find(|k| value(&self.assignment, lits[*k]) != Some(false))
+                {
+                    lits.swap(1, k);
+                    self.watches[lits[1].0].push(index);
+                    watching.swap_remove(i);
+                    continue;
+                }
+
+                let unit = lits[0];
+                if value(&self.assignment, unit) == Some(false) {
+                    self.watches[falsified.0] = watching;
+                    return Some(index);
+                }
+                self.enqueue(unit, Some(index));
+                i += 1;
+            }
+            self.watches[falsified.0] = watching;
+        }
+        None
+    }
+
+    fn bump(&mut self, v: usize) {
+        self.activity[v] += self.increment;
+        if self.activity[v] > 1e100 {
+            for a in &mut self.activity {
+                *a *= 1e-100;
+            }
+            self.increment *= 1e-100;
+            self.heap = (0..self.activity.len())
+                .map(|v| Activity(self.activity[v], v))
+                .collect();
+        } else {
+            self.heap.push(Activity(self.activity[v], v));
+        }
+    }
+
+    /// Learn the first-UIP clause of a conflict, returning it with the
+    /// asserting literal first, the level to backtrack to and its origins.
+    fn analyse(&mut self, conflict: usize) -> (Vec<Lit>, usize, Vec<usize>) {
+        let mut seen = vec![false; self.assignment.len()];
+        let mut learnt = vec![Lit(0)];
+        let mut origins = Vec::new();
+        let mut pending = 0;
+        let mut index = self.trail.len();
+        let mut clause = conflict;
+        let mut resolved: Option<Lit> = None;
+
+        loop {
+            merge(&mut origins, &self.clauses[clause].origins);
+            for i in 0..self.clauses[clause].lits.len() {
+                let lit = self.clauses[clause].lits[i];
+                let v = lit.variable();
+                if Some(lit) == resolved || seen[v] {
+                    continue;
+                }
+                seen[v] = true;
+                if self.level[v] == 0 {
+                    merge(&mut origins, &self.fixed_origins[v]);
+                    continue;
+                }
+                self.bump(v);
+                if self.level[v] == self.decision_level() {
+                    pending += 1;
+                } else {
+                    learnt.push(lit);
+                }
+            }
+
+            // The next literal of the current level on the trail.
+            loop {
+                index -= 1;
+                if seen[self.trail[index].variable()] {
+                    break;
+                }
+            }
+            let lit = self.trail[index];
+            pending -= 1;
+            if pending == 0 {
+                learnt[0] = lit.negate();
+                break;
+            }
+            resolved = Some(lit);
+            clause = self.reason[lit.variable()].expect("only decisions have no reason");
+        }
+
+        let mut backtrack = 0;
+        if learnt.len() > 1 {
+            let (i, level) = (1..learnt.len())
+                .map(|i| (i, self.level[learnt[i].variable()]))
+                .max_by_key(|(_, level)| *level)
+                .unwrap();
+            learnt.swap(1, i);
+            backtrack = level;
+        }
+        (learnt, backtrack, origins)
+    }
+
+    /// The input clauses a conflict at level zero follows from.
+    fn final_core(&self, conflict: usize) -> Vec<usize> {
+        let mut core = self.clauses[conflict].origins.clone();
+        for lit in &self.clauses[conflict].lits {
+            merge(&mut core, &self.fixed_origins[lit.variable()]);
+        }
+        core
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+        let start = self.trail_limits[level];
+        for lit in self.trail.drain(start..) {
+            let v = lit.variable();
+            self.phase[v] = !lit.is_negated();
+            self.assignment[v] = None;
+            self.reason[v] = None;
+            self.heap.push(Activity(self.activity[v], v));
+        }
+        self.trail_limits.truncate(level);
+        self.propagated = self.trail.len();
+    }
+
+    fn decide(&mut self) -> Option<Lit> {
+        while let Some(Activity(activity, v)) = self.heap.pop() {
+            if self.assignment[v].is_none() && activity == self.activity[v] {
+                return Some(Lit::new(v, !self.phase[v]));
+            }
+        }
+        // Stale entries may have hidden an unassigned variable.
+        (0..self.assignment.len())
+            .find(|v| self.assignment[*v].is_none())
+            .map(|v| Lit::new(v, !self.phase[v]))
+    }
+
+    /// Search for a satisfying assignment, or return an unsatisfiable core.
+    fn run(&mut self, clauses: Vec<(usize, Vec<Lit>)>) -> Result<(), Vec<usize>> {
+        for (index, lits) in clauses {
+            match lits.len() {
+                0 => return Err(vec![index]),
+                1 => {
+                    let lit = lits[0];
+                    let clause = self.add_clause(lits, vec![index]);
+                    match value(&self.assignment, lit) {
+                        None => self.enqueue(lit, Some(clause)),
+                        Some(true) => {}
+                        Some(false) => return Err(self.final_core(clause)),
+                    }
+                }
+                _ => {
+                    self.add_clause(lits, vec![index]);
+                }
+            }
+        }
+
+        let mut restarts = 0;
+        let mut conflicts = 0;
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.decision_level() == 0 {
+                    return Err(self.final_core(conflict));
+                }
+                conflicts += 1;
+                let (learnt, level, origins) = self.analyse(conflict);
+                self.backtrack(level);
+                let asserting = learnt[0];
+                let clause = self.add_clause(learnt, origins);
+                self.enqueue(asserting, Some(clause));
+                self.increment /= self.solver.variable_decay;
+            } else if conflicts >= self.solver.restart_base * luby(restarts) {
+                conflicts = 0;
+                restarts += 1;
+                self.backtrack(0);
+            } else {
+                match self.decide() {
+                    None => return Ok(()),
+                    Some(lit) => {
+                        self.trail_limits.push(self.trail.len());
+                        self.enqueue(lit, None);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::{ClauseNormalForm, Literal},
+        Constant, PredicateCall,
+    };
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{luby, SatResult, SatSolver};
+
+    fn p(i: u64) -> Literal {
+        Literal::Atom(
+            PredicateCall {
+                predicate: b'P'.into(),
+                terms: vec![Constant { label: i }.into()],
+            }
+            .into(),
+        )
+    }
+
+    fn satisfies(
+        model: &std::collections::HashMap<crate::syntax::GenericAtomicFormula, bool>,
+        clauses: &ClauseNormalForm,
+    ) -> bool {
+        clauses.iter().all(|c| {
+            c.iter()
+                .any(|l| model.get(l.atom()) == Some(&!l.is_negated()))
+        })
+    }
+
+    fn brute_force(variables: u64, clauses: &ClauseNormalForm) -> bool {
+        (0..1u64 << variables).any(|bits| {
+            let model = (0..variables)
+                .map(|i| (p(i).atom().clone(), bits >> i & 1 == 1))
+                .collect();
+            satisfies(&model, clauses)
+        })
+    }
+
+    #[test]
+    fn test_luby() {
+        let sequence: Vec<u64> = (0..15).map(luby).collect();
+        assert_eq!(sequence, [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn test_pigeonhole() {
+        // Four pigeons in three holes: P(3i + j) means pigeon i is in hole j.
+        let at = |pigeon: u64, hole: u64| p(3 * pigeon + hole);
+        let mut clauses: ClauseNormalForm =
+            (0..4).map(|i| (0..3).map(|j| at(i, j)).collect()).collect();
+        for j in 0..3 {
+            for i in 0..4 {
+                for k in i + 1..4 {
+                    clauses.push(vec![at(i, j).negate(), at(k, j).negate()]);
+                }
+            }
+        }
+        match SatSolver::default().solve(&clauses).unwrap() {
+            SatResult::Unsatisfiable(core) => {
+                // Every clause is needed.
+                assert_eq!(core, (0..clauses.len()).collect::<Vec<_>>());
+            }
+            result => panic!("{:?}", result),
+        }
+
+        // Irrelevant clauses are left out of the core.
+        let mut padded = vec![vec![p(100), p(101)]];
+        padded.extend(clauses.iter().cloned());
+        match SatSolver::default().solve(&padded).unwrap() {
+            SatResult::Unsatisfiable(core) => assert!(!core.contains(&0)),
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_random_3_sat() {
+        let solver = SatSolver {
+            restart_base: 4,
+            ..SatSolver::default()
+        };
+        let mut rng = StdRng::seed_from_u64(41);
+        for _ in 0..200 {
+            let variables = 8;
+            let clauses: ClauseNormalForm = (0..34)
+                .map(|_| {
+                    (0..3)
+                        .map(|_| {
+                            let literal = p(rng.gen_range(0..variables));
+                            if rng.gen() {
+                                literal.negate()
+                            } else {
+                                literal
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+
+            match solver.solve(&clauses).unwrap() {
+                SatResult::Satisfiable(model) => assert!(satisfies(&model, &clauses)),
+                SatResult::Unsatisfiable(core) => {
+                    assert!(!brute_force(variables, &clauses));
+                    let core: ClauseNormalForm = core.iter().map(|i| clauses[*i].clone()).collect();
+                    assert!(!brute_force(variables, &core));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_not_ground() {
+        let x = crate::syntax::Variable::new(b'x'.into());
+        let literal = Literal::Atom(
+            PredicateCall {
+                predicate: b'P'.into(),
+                terms: vec![x.into()],
+            }
+            .into(),
+        );
+        assert_eq!(
+            SatSolver::default().solve(&vec![vec![literal.clone()]]),
+            Err(super::SatError::NotGround(literal))
+        );
+    }
+}
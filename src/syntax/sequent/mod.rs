@@ -0,0 +1,671 @@
+use std::fmt::Display;
+
+use super::{
+    natural_deduction::alpha_equivalent, unification::Substitution, GenericAtomicFormula,
+    GenericFormula, GenericTerm, Variable,
+};
+
+mod print;
+mod search;
+
+pub use search::SequentProver;
+
+////////////////////////////////////////////////////////////////////////////////
+// Sequents
+////////////////////////////////////////////////////////////////////////////////
+
+/// A sequent `Γ ⊢ Δ`: the conjunction of the antecedent implies the
+/// disjunction of the succedent.
+///
+/// The formulas are kept in order, and the rules refer to them by position.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Sequent {
+    /// The formulas on the left of the turnstile.
+    pub antecedent: Vec<GenericFormula>,
+    /// The formulas on the right of the turnstile.
+    pub succedent: Vec<GenericFormula>,
+}
+
+impl Sequent {
+    /// Create a sequent.
+    pub fn new(antecedent: Vec<GenericFormula>, succedent: Vec<GenericFormula>) -> Self {
+        Self {
+            antecedent,
+            succedent,
+        }
+    }
+
+    /// Whether the sequents have the same formulas in the same positions, up
+    /// to the renaming of bound variables.
+    fn equivalent(&self, other: &Sequent) -> bool {
+        fn same(left: &[GenericFormula], right: &[GenericFormula]) -> bool {
+            left.len() == right.len() && left.iter().zip(right).all(|(l, r)| alpha_equivalent(l, r))
+        }
+        same(&self.antecedent, &other.antecedent) && same(&self.succedent, &other.succedent)
+    }
+
+    fn free_variables(&self) -> Vec<Variable> {
+        let mut variables = Vec::new();
+        for formula in self.antecedent.iter().chain(&self.succedent) {
+            for v in formula.free_variables() {
+                if !variables.contains(&v) {
+                    variables.push(v);
+                }
+            }
+        }
+        variables
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Rules
+////////////////////////////////////////////////////////////////////////////////
+
+/// A rule of the sequent calculus LK.
+///
+/// The principal formula of a rule is given by its `index` in the
+/// antecedent (for `…Left` rules) or succedent (for `…Right` rules) of the
+/// conclusion. The premises have the same context, with the principal formula
+/// replaced in place by its active formulas. An active formula which changes
+/// sides is placed next to the turnstile: at the start of the antecedent, or
+/// the end of the succedent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// `Γ, A, Γ' ⊢ Δ, A, Δ'`. Contexts are allowed, which builds in
+    /// weakening.
+    Axiom {
+        /// The position of `A` in the antecedent.
+        left: usize,
+        /// The position of `A` in the succedent.
+        right: usize,
+    },
+    /// `Γ ⊢ Δ, ⊤, Δ'`.
+    TrueRight(usize),
+    /// `Γ, ⊥, Γ' ⊢ Δ`.
+    FalseLeft(usize),
+
+    /// `Γ, A, Γ' ⊢ Δ`, from `Γ, Γ' ⊢ Δ`.
+    WeakeningLeft(usize),
+    /// `Γ ⊢ Δ, A, Δ'`, from `Γ ⊢ Δ, Δ'`.
+    WeakeningRight(usize),
+    /// `Γ, A, Γ' ⊢ Δ`, from `Γ, A, A, Γ' ⊢ Δ`.
+    ContractionLeft(usize),
+    /// `Γ ⊢ Δ, A, Δ'`, from `Γ ⊢ Δ, A, A, Δ'`.
+    ContractionRight(usize),
+    /// `Γ, A, B, Γ' ⊢ Δ`, from `Γ, B, A, Γ' ⊢ Δ`, where `A` is at the index.
+    ExchangeLeft(usize),
+    /// `Γ ⊢ Δ, A, B, Δ'`, from `Γ ⊢ Δ, B, A, Δ'`, where `A` is at the index.
+    ExchangeRight(usize),
+    /// `Γ ⊢ Δ`, from `Γ ⊢ Δ, A` and `A, Γ ⊢ Δ`.
+    Cut(GenericFormula),
+
+    /// `A ∧ B` on the left, from `A, B` in its place.
+    ConjunctionLeft(usize),
+    /// `A ∧ B` on the right, from `A` and from `B` in its place.
+    ConjunctionRight(usize),
+    /// `A ∨ B` on the left, from `A` and from `B` in its place.
+    DisjunctionLeft(usize),
+    /// `A ∨ B` on the right, from `A, B` in its place.
+    DisjunctionRight(usize),
+    /// `A ⇒ B` on the left, from `Γ ⊢ Δ, A` without it, and from `B` in its
+    /// place.
+    ImplicationLeft(usize),
+    /// `A ⇒ B` on the right, from `A, Γ ⊢ Δ` with `B` in its place.
+    ImplicationRight(usize),
+    /// `¬A` on the left, from `Γ ⊢ Δ, A` without it.
+    NegationLeft(usize),
+    /// `¬A` on the right, from `A, Γ ⊢ Δ` without it.
+    NegationRight(usize),
+    /// `∀x.A` on the left, from `A[x := t]` in its place.
+    UniversalLeft {
+        /// The position of the principal formula.
+        index: usize,
+        /// The term `t`.
+        term: GenericTerm,
+    },
+    /// `∀x.A` on the right, from `A[x := a]` in its place, where `a` is not
+    /// free in the conclusion.
+    UniversalRight {
+        /// The position of the principal formula.
+        index: usize,
+        /// The eigenvariable `a`.
+        eigenvariable: Variable,
+    },
+    /// `∃x.A` on the left, from `A[x := a]` in its place, where `a` is not
+    /// free in the conclusion.
+    ExistentialLeft {
+        /// The position of the principal formula.
+        index: usize,
+        /// The eigenvariable `a`.
+        eigenvariable: Variable,
+    },
+    /// `∃x.A` on the right, from `A[x := t]` in its place.
+    ExistentialRight {
+        /// The position of the principal formula.
+        index: usize,
+        /// The term `t`.
+        term: GenericTerm,
+    },
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////
+
+/// The ways a step of a derivation can be invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DerivationErrorKind {
+    /// The rule was given the wrong number of premises.
+    WrongPremiseCount {
+        /// The number of premises the rule takes.
+        expected: usize,
+        /// The number of premises given.
+        found: usize,
+    },
+    /// The rule refers to a position past the end of the sequent.
+    NoSuchFormula(usize),
+    /// The principal formula does not have the form the rule requires.
+    UnexpectedForm(GenericFormula),
+    /// The formulas of an axiom differ.
+    NotAnAxiom(GenericFormula, GenericFormula),
+    /// A premise is not the sequent the rule requires.
+    Mismatch {
+        /// The sequent required by the rule.
+        expected: Sequent,
+        /// The sequent given.
+        found: Sequent,
+    },
+    /// The eigenvariable of a quantifier rule is free in the conclusion.
+    Eigenvariable(Variable),
+}
+
+/// The first invalid step of a derivation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationError {
+    /// The path to the invalid step from the conclusion: the index of the
+    /// premise taken at each step.
+    pub path: Vec<usize>,
+    /// What is wrong with the step.
+    pub kind: DerivationErrorKind,
+}
+
+impl Display for DerivationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("step ")?;
+        if self.path.is_empty() {
+            f.write_str("(conclusion)")?;
+        } else {
+            let path: Vec<String> = self.path.iter().map(|i| i.to_string()).collect();
+            f.write_str(&path.join("."))?;
+        }
+        f.write_str(": ")?;
+        match &self.kind {
+            DerivationErrorKind::WrongPremiseCount { expected, found } => f.write_fmt(
+                format_args!("expected {} premises, found {}", expected, found),
+            ),
+            DerivationErrorKind::NoSuchFormula(i) => {
+                f.write_fmt(format_args!("there is no formula at position {}", i))
+            }
+            DerivationErrorKind::UnexpectedForm(x) => {
+                f.write_fmt(format_args!("{} does not have the required form", x))
+            }
+            DerivationErrorKind::NotAnAxiom(l, r) => {
+                f.write_fmt(format_args!("{} and {} differ", l, r))
+            }
+            DerivationErrorKind::Mismatch { expected, found } => {
+                f.write_fmt(format_args!("expected {}, found {}", expected, found))
+            }
+            DerivationErrorKind::Eigenvariable(x) => {
+                f.write_fmt(format_args!("eigenvariable {} is not fresh", x))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DerivationError {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Derivations
+////////////////////////////////////////////////////////////////////////////////
+
+/// A derivation in LK, as a tree of rule applications.
+///
+/// Derivations are printed as indented text by [Display], and as a
+/// `bussproofs` tree by [`latex`](Derivation::latex).
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   sequent::{Derivation, Rule, Sequent}, Disjunction, GenericFormula, Negation,
+/// #   PredicateCall,
+/// # };
+/// let a: GenericFormula = PredicateCall { predicate: b'A'.into(), terms: vec![] }.into();
+/// let not_a: GenericFormula = Negation { right: a.clone() }.into();
+/// let lem: GenericFormula = Disjunction { left: a.clone(), right: not_a.clone() }.into();
+///
+/// let derivation = Derivation::new(
+///     Sequent::new(vec![], vec![lem]),
+///     Rule::DisjunctionRight(0),
+///     vec![Derivation::new(
+///         Sequent::new(vec![], vec![a.clone(), not_a]),
+///         Rule::NegationRight(1),
+///         vec![Derivation::new(
+///             Sequent::new(vec![a.clone()], vec![a]),
+///             Rule::Axiom { left: 0, right: 0 },
+///             vec![],
+///         )],
+///     )],
+/// );
+///
+/// assert!(derivation.check().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Derivation {
+    /// The sequent derived.
+    pub conclusion: Sequent,
+    /// The rule used in the last step.
+    pub rule: Rule,
+    /// The derivations of the premises of the rule.
+    pub premises: Vec<Derivation>,
+}
+
+fn substitute(variable: Variable, term: GenericTerm, formula: &GenericFormula) -> GenericFormula {
+    Substitution::from_iter([(variable, term)]).apply(formula)
+}
+
+fn replaced(
+    formulas: &[GenericFormula],
+    index: usize,
+    with: impl IntoIterator<Item = GenericFormula>,
+) -> Vec<GenericFormula> {
+    let mut out = formulas[..index].to_vec();
+    out.extend(with);
+    out.extend_from_slice(&formulas[index + 1..]);
+    out
+}
+
+/// The premises of a rule applied backwards to a sequent.
+pub(crate) fn premises_of(
+    rule: &Rule,
+    conclusion: &Sequent,
+) -> Result<Vec<Sequent>, DerivationErrorKind> {
+    use DerivationErrorKind::*;
+
+    let Sequent {
+        antecedent,
+        succedent,
+    } = conclusion;
+    let left = |i: usize| antecedent.get(i).ok_or(NoSuchFormula(i));
+    let right = |i: usize| succedent.get(i).ok_or(NoSuchFormula(i));
+    let on_left = |i: usize, with: Vec<GenericFormula>| Sequent {
+        antecedent: replaced(antecedent, i, with),
+        succedent: succedent.clone(),
+    };
+    let on_right = |i: usize, with: Vec<GenericFormula>| Sequent {
+        antecedent: antecedent.clone(),
+        succedent: replaced(succedent, i, with),
+    };
+    let fresh = |a: &Variable| {
+        if conclusion.free_variables().contains(a) {
+            Err(Eigenvariable(*a))
+        } else {
+            Ok(())
+        }
+    };
+
+    Ok(match rule {
+        Rule::Axiom { left: l, right: r } => {
+            let (l, r) = (left(*l)?, right(*r)?);
+            if !alpha_equivalent(l, r) {
+                return Err(NotAnAxiom(l.clone(), r.clone()));
+            }
+            vec![]
+        }
+        Rule::TrueRight(i) => match right(*i)? {
+            GenericFormula::Atomic(GenericAtomicFormula::True) => vec![],
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::FalseLeft(i) => match left(*i)? {
+            GenericFormula::Atomic(GenericAtomicFormula::False) => vec![],
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+
+        Rule::WeakeningLeft(i) => {
+            left(*i)?;
+            vec![on_left(*i, vec![])]
+        }
+        Rule::WeakeningRight(i) => {
+            right(*i)?;
+            vec![on_right(*i, vec![])]
+        }
+        Rule::ContractionLeft(i) => {
+            let x = left(*i)?;
+            vec![on_left(*i, vec![x.clone(), x.clone()])]
+        }
+        Rule::ContractionRight(i) => {
+            let x = right(*i)?;
+            vec![on_right(*i, vec![x.clone(), x.clone()])]
+        }
+        Rule::ExchangeLeft(i) => {
+            left(*i + 1)?;
+            let mut premise = conclusion.clone();
+            premise.antecedent.swap(*i, *i + 1);
+            vec![premise]
+        }
+        Rule::ExchangeRight(i) => {
+            right(*i + 1)?;
+            let mut premise = conclusion.clone();
+            premise.succedent.swap(*i, *i + 1);
+            vec![premise]
+        }
+        Rule::Cut(x) => {
+            let mut first = conclusion.clone();
+            first.succedent.push(x.clone());
+            let mut second = conclusion.clone();
+            second.antecedent.insert(0, x.clone());
+            vec![first, second]
+        }
+
+        Rule::ConjunctionLeft(i) => match left(*i)? {
+            GenericFormula::Conjunction(x) => {
+                vec![on_left(*i, vec![x.left.clone(), x.right.clone()])]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::ConjunctionRight(i) => match right(*i)? {
+            GenericFormula::Conjunction(x) => vec![
+                on_right(*i, vec![x.left.clone()]),
+                on_right(*i, vec![x.right.clone()]),
+            ],
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::DisjunctionLeft(i) => match left(*i)? {
+            GenericFormula::Disjunction(x) => vec![
+                on_left(*i, vec![x.left.clone()]),
+                on_left(*i, vec![x.right.clone()]),
+            ],
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::DisjunctionRight(i) => match right(*i)? {
+            GenericFormula::Disjunction(x) => {
+                vec![on_right(*i, vec![x.left.clone(), x.right.clone()])]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::ImplicationLeft(i) => match left(*i)? {
+            GenericFormula::Implication(x) => {
+                let mut first = on_left(*i, vec![]);
+                first.succedent.push(x.left.clone());
+                vec![first, on_left(*i, vec![x.right.clone()])]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::ImplicationRight(i) => match right(*i)? {
+            GenericFormula::Implication(x) => {
+                let mut premise = on_right(*i, vec![x.right.clone()]);
+                premise.antecedent.insert(0, x.left.clone());
+                vec![premise]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::NegationLeft(i) => match left(*i)? {
+            GenericFormula::Negation(x) => {
+                let mut premise = on_left(*i, vec![]);
+                premise.succedent.push(x.right.clone());
+                vec![premise]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::NegationRight(i) => match right(*i)? {
+            GenericFormula::Negation(x) => {
+                let mut premise = on_right(*i, vec![]);
+                premise.antecedent.insert(0, x.right.clone());
+                vec![premise]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::UniversalLeft { index, term } => match left(*index)? {
+            GenericFormula::Universal(x) => {
+                vec![on_left(
+                    *index,
+                    vec![substitute(x.left, term.clone(), &x.right)],
+                )]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::UniversalRight {
+            index,
+            eigenvariable,
+        } => match right(*index)? {
+            GenericFormula::Universal(x) => {
+                fresh(eigenvariable)?;
+                vec![on_right(
+                    *index,
+                    vec![substitute(x.left, (*eigenvariable).into(), &x.right)],
+                )]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::ExistentialLeft {
+            index,
+            eigenvariable,
+        } => match left(*index)? {
+            GenericFormula::Existential(x) => {
+                fresh(eigenvariable)?;
+                vec![on_left(
+                    *index,
+                    vec![substitute(x.left, (*eigenvariable).into(), &x.right)],
+                )]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+        Rule::ExistentialRight { index, term } => match right(*index)? {
+            GenericFormula::Existential(x) => {
+                vec![on_right(
+                    *index,
+                    vec![substitute(x.left, term.clone(), &x.right)],
+                )]
+            }
+            x => return Err(UnexpectedForm(x.clone())),
+        },
+    })
+}
+
+impl Derivation {
+    /// Create a derivation from a conclusion, a rule and the derivations of
+    /// its premises.
+    pub fn new(conclusion: Sequent, rule: Rule, premises: Vec<Derivation>) -> Self {
+        Self {
+            conclusion,
+            rule,
+            premises,
+        }
+    }
+
+    /// Whether the derivation uses the cut rule.
+    pub fn has_cut(&self) -> bool {
+        matches!(self.rule, Rule::Cut(_)) || self.premises.iter().any(Derivation::has_cut)
+    }
+
+    /// Check that every step of the derivation is a valid application of its
+    /// rule.
+    ///
+    /// Formulas are compared up to the renaming of bound variables. Steps are
+    /// checked premises first, so the error is for the first invalid step in
+    /// reading order.
+    pub fn check(&self) -> Result<(), DerivationError> {
+        self.check_step(&mut Vec::new())
+    }
+
+    fn check_step(&self, path: &mut Vec<usize>) -> Result<(), DerivationError> {
+        for (i, premise) in self.premises.iter().enumerate() {
+            path.push(i);
+            premise.check_step(path)?;
+            path.pop();
+        }
+
+        let error = |kind| DerivationError {
+            path: path.clone(),
+            kind,
+        };
+        let expected = premises_of(&self.rule, &self.conclusion).map_err(error)?;
+        if expected.len() != self.premises.len() {
+            return Err(error(DerivationErrorKind::WrongPremiseCount {
+                expected: expected.len(),
+                found: self.premises.len(),
+            }));
+        }
+        for (expected, premise) in expected.into_iter().zip(&self.premises) {
+            if !expected.equivalent(&premise.conclusion) {
+                return Err(error(DerivationErrorKind::Mismatch {
+                    expected,
+                    found: premise.conclusion.clone(),
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        Conjunction, GenericFormula, GenericTerm, Implication, Negation, PredicateCall, Universal,
+        Variable,
+    };
+
+    use super::{Derivation, DerivationErrorKind, Rule, Sequent};
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_structural_rules_and_cut() {
+        let a = p(b'A', vec![]);
+        let b = p(b'B', vec![]);
+
+        // A ⊢ A, weakened to B, A ⊢ A and exchanged to A, B ⊢ A.
+        let axiom = Derivation::new(
+            Sequent::new(vec![a.clone()], vec![a.clone()]),
+            Rule::Axiom { left: 0, right: 0 },
+            vec![],
+        );
+        let weakened = Derivation::new(
+            Sequent::new(vec![b.clone(), a.clone()], vec![a.clone()]),
+            Rule::WeakeningLeft(0),
+            vec![axiom.clone()],
+        );
+        let exchanged = Derivation::new(
+            Sequent::new(vec![a.clone(), b.clone()], vec![a.clone()]),
+            Rule::ExchangeLeft(0),
+            vec![weakened],
+        );
+        assert_eq!(exchanged.check(), Ok(()));
+
+        // Cut A from A ⊢ A and A ⊢ A.
+        let cut = Derivation::new(
+            Sequent::new(vec![a.clone()], vec![a.clone()]),
+            Rule::Cut(a.clone()),
+            vec![
+                Derivation::new(
+                    Sequent::new(vec![a.clone()], vec![a.clone(), a.clone()]),
+                    Rule::WeakeningRight(1),
+                    vec![axiom.clone()],
+                ),
+                Derivation::new(
+                    Sequent::new(vec![a.clone(), a.clone()], vec![a.clone()]),
+                    Rule::WeakeningLeft(0),
+                    vec![axiom],
+                ),
+            ],
+        );
+        assert_eq!(cut.check(), Ok(()));
+        assert!(cut.has_cut());
+    }
+
+    #[test]
+    fn test_invalid_steps() {
+        let a = p(b'A', vec![]);
+        let b = p(b'B', vec![]);
+        let a_and_b: GenericFormula = Conjunction {
+            left: a.clone(),
+            right: b.clone(),
+        }
+        .into();
+
+        // The premise of ∧L should be A, B ⊢ A.
+        let derivation = Derivation::new(
+            Sequent::new(vec![a_and_b], vec![a.clone()]),
+            Rule::ConjunctionLeft(0),
+            vec![Derivation::new(
+                Sequent::new(vec![b.clone()], vec![a.clone()]),
+                Rule::Axiom { left: 0, right: 0 },
+                vec![],
+            )],
+        );
+        let error = derivation.check().unwrap_err();
+        assert_eq!(error.path, vec![0]);
+        assert_eq!(error.kind, DerivationErrorKind::NotAnAxiom(b, a.clone()));
+
+        // ∀R with an eigenvariable free in the conclusion.
+        let x = Variable::new(b'x'.into());
+        let px = p(b'P', vec![x.into()]);
+        let all: GenericFormula = Universal {
+            left: x,
+            right: px.clone(),
+        }
+        .into();
+        let derivation = Derivation::new(
+            Sequent::new(vec![px.clone()], vec![all]),
+            Rule::UniversalRight {
+                index: 0,
+                eigenvariable: x,
+            },
+            vec![Derivation::new(
+                Sequent::new(vec![px.clone()], vec![px]),
+                Rule::Axiom { left: 0, right: 0 },
+                vec![],
+            )],
+        );
+        assert_eq!(
+            derivation.check().unwrap_err().kind,
+            DerivationErrorKind::Eigenvariable(x)
+        );
+
+        // A rule pointing past the end of the sequent.
+        let not_a: GenericFormula = Negation { right: a.clone() }.into();
+        let derivation = Derivation::new(
+            Sequent::new(vec![], vec![not_a]),
+            Rule::NegationLeft(0),
+            vec![],
+        );
+        assert_eq!(
+            derivation.check().unwrap_err().kind,
+            DerivationErrorKind::NoSuchFormula(0)
+        );
+
+        let implication: GenericFormula = Implication {
+            left: a.clone(),
+            right: a,
+        }
+        .into();
+        let derivation = Derivation::new(
+            Sequent::new(vec![], vec![implication]),
+            Rule::ImplicationRight(0),
+            vec![],
+        );
+        assert_eq!(
+            derivation.check().unwrap_err().kind,
+            DerivationErrorKind::WrongPremiseCount {
+                expected: 1,
+                found: 0
+            }
+        );
+    }
+}
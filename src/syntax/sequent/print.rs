@@ -0,0 +1,141 @@
+use std::fmt::Display;
+
+use crate::syntax::{render::latex, GenericFormula, Signature};
+
+use super::{Derivation, Rule, Sequent};
+
+/// The name of a rule, as text and as LaTeX.
+fn names(rule: &Rule) -> (&'static str, &'static str) {
+    match rule {
+        Rule::Axiom { .. } => ("Ax", r"\mathrm{Ax}"),
+        Rule::TrueRight(_) => ("⊤R", r"\top R"),
+        Rule::FalseLeft(_) => ("⊥L", r"\bot L"),
+        Rule::WeakeningLeft(_) => ("WL", r"\mathrm{W}L"),
+        Rule::WeakeningRight(_) => ("WR", r"\mathrm{W}R"),
+        Rule::ContractionLeft(_) => ("CL", r"\mathrm{C}L"),
+        Rule::ContractionRight(_) => ("CR", r"\mathrm{C}R"),
+        Rule::ExchangeLeft(_) => ("XL", r"\mathrm{X}L"),
+        Rule::ExchangeRight(_) => ("XR", r"\mathrm{X}R"),
+        Rule::Cut(_) => ("Cut", r"\mathrm{Cut}"),
+        Rule::ConjunctionLeft(_) => ("∧L", r"\land L"),
+        Rule::ConjunctionRight(_) => ("∧R", r"\land R"),
+        Rule::DisjunctionLeft(_) => ("∨L", r"\lor L"),
+        Rule::DisjunctionRight(_) => ("∨R", r"\lor R"),
+        Rule::ImplicationLeft(_) => ("⇒L", r"\rightarrow L"),
+        Rule::ImplicationRight(_) => ("⇒R", r"\rightarrow R"),
+        Rule::NegationLeft(_) => ("¬L", r"\lnot L"),
+        Rule::NegationRight(_) => ("¬R", r"\lnot R"),
+        Rule::UniversalLeft { .. } => ("∀L", r"\forall L"),
+        Rule::UniversalRight { .. } => ("∀R", r"\forall R"),
+        Rule::ExistentialLeft { .. } => ("∃L", r"\exists L"),
+        Rule::ExistentialRight { .. } => ("∃R", r"\exists R"),
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(names(self).0)
+    }
+}
+
+fn write_formulas(
+    f: &mut std::fmt::Formatter<'_>,
+    formulas: &[GenericFormula],
+) -> std::fmt::Result {
+    for (i, formula) in formulas.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        formula.fmt(f)?;
+    }
+    Ok(())
+}
+
+impl Display for Sequent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_formulas(f, &self.antecedent)?;
+        if !self.antecedent.is_empty() {
+            f.write_str(" ")?;
+        }
+        f.write_str("⊢")?;
+        if !self.succedent.is_empty() {
+            f.write_str(" ")?;
+        }
+        write_formulas(f, &self.succedent)
+    }
+}
+
+impl Sequent {
+    /// Render the sequent as LaTeX math-mode source, taking names from the
+    /// signature.
+    pub fn latex(&self, signature: &Signature) -> String {
+        let side = |formulas: &[GenericFormula]| {
+            formulas
+                .iter()
+                .map(|x| latex(x, signature))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!(
+            r"{} \vdash {}",
+            side(&self.antecedent),
+            side(&self.succedent)
+        )
+        .trim()
+        .to_string()
+    }
+}
+
+impl Derivation {
+    fn write_text(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{:indent$}{}  ({})",
+            "",
+            self.conclusion,
+            self.rule,
+            indent = 2 * depth
+        ))?;
+        for premise in &self.premises {
+            f.write_str("\n")?;
+            premise.write_text(f, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Render the derivation as a `prooftree` environment of the LaTeX
+    /// `bussproofs` package, taking names from the signature.
+    pub fn latex(&self, signature: &Signature) -> String {
+        let mut out = String::from("\\begin{prooftree}\n");
+        self.write_latex(signature, &mut out);
+        out.push_str("\\end{prooftree}");
+        out
+    }
+
+    fn write_latex(&self, signature: &Signature, out: &mut String) {
+        if self.premises.is_empty() {
+            out.push_str("\\AxiomC{}\n");
+        }
+        for premise in &self.premises {
+            premise.write_latex(signature, out);
+        }
+        let inference = match self.premises.len() {
+            0 | 1 => "UnaryInfC",
+            2 => "BinaryInfC",
+            _ => "TrinaryInfC",
+        };
+        out.push_str(&format!(
+            "\\RightLabel{{${}$}}\n\\{}{{${}$}}\n",
+            names(&self.rule).1,
+            inference,
+            self.conclusion.latex(signature)
+        ));
+    }
+}
+
+/// Derivations are shown with one sequent per line, conclusion first, and
+/// each premise indented below the sequent it proves.
+impl Display for Derivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_text(f, 0)
+    }
+}
@@ -0,0 +1,337 @@
+use crate::syntax::{
+    natural_deduction::alpha_equivalent, unification::Substitution, GenericAtomicFormula,
+    GenericFormula, GenericTerm, Variable,
+};
+
+use super::{premises_of, Derivation, Rule, Sequent};
+
+/// A bounded proof search for cut-free LK.
+///
+/// Every rule except `∀L` and `∃R` is invertible, so is applied eagerly
+/// without backtracking. `∀L` and `∃R` contract their principal formula
+/// before instantiating it, with a term occurring in the sequent, and are
+/// the only choice points. The number of these instantiations on a branch is
+/// bounded by iterative deepening up to `max_gamma`.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   sequent::{Sequent, SequentProver}, Existential, GenericFormula, GenericTerm,
+/// #   PredicateCall, Universal, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let p = |t: GenericTerm| -> GenericFormula {
+///     PredicateCall { predicate: b'P'.into(), terms: vec![t] }.into()
+/// };
+/// let everything: GenericFormula = Universal { left: x, right: p(x.into()) }.into();
+/// let something: GenericFormula = Existential { left: x, right: p(x.into()) }.into();
+///
+/// let derivation = SequentProver::default()
+///     .prove(&Sequent::new(vec![everything], vec![something]))
+///     .unwrap();
+/// assert!(derivation.check().is_ok());
+/// assert!(!derivation.has_cut());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequentProver {
+    /// The maximum number of `∀L` and `∃R` applications on a branch.
+    pub max_gamma: usize,
+}
+
+impl Default for SequentProver {
+    fn default() -> Self {
+        Self { max_gamma: 4 }
+    }
+}
+
+impl SequentProver {
+    /// Search for a cut-free derivation of a sequent.
+    pub fn prove(&self, sequent: &Sequent) -> Option<Derivation> {
+        // Quantifiers need a term to instantiate with even if the sequent
+        // has none.
+        let default: GenericTerm = Variable::rand().into();
+        (0..=self.max_gamma).find_map(|budget| search(sequent.clone(), budget, &default))
+    }
+}
+
+/// The rule for an axiom of the sequent, if it is one.
+fn axiom(sequent: &Sequent) -> Option<Rule> {
+    for (i, formula) in sequent.antecedent.iter().enumerate() {
+        if *formula == GenericAtomicFormula::False.into() {
+            return Some(Rule::FalseLeft(i));
+        }
+        if let Some(j) = sequent
+            .succedent
+            .iter()
+            .position(|x| alpha_equivalent(formula, x))
+        {
+            return Some(Rule::Axiom { left: i, right: j });
+        }
+    }
+    sequent
+        .succedent
+        .iter()
+        .position(|x| *x == GenericAtomicFormula::True.into())
+        .map(Rule::TrueRight)
+}
+
+/// An invertible rule which applies to the sequent.
+fn invertible(sequent: &Sequent) -> Option<Rule> {
+    let left = sequent
+        .antecedent
+        .iter()
+        .enumerate()
+        .find_map(|(i, formula)| match formula {
+            GenericFormula::Conjunction(_) => Some(Rule::ConjunctionLeft(i)),
+            GenericFormula::Disjunction(_) => Some(Rule::DisjunctionLeft(i)),
+            GenericFormula::Implication(_) => Some(Rule::ImplicationLeft(i)),
+            GenericFormula::Negation(_) => Some(Rule::NegationLeft(i)),
+            GenericFormula::Existential(_) => Some(Rule::ExistentialLeft {
+                index: i,
+                eigenvariable: Variable::rand(),
+            }),
+            GenericFormula::Atomic(_) | GenericFormula::Universal(_) => None,
+        });
+    left.or_else(|| {
+        sequent
+            .succedent
+            .iter()
+            .enumerate()
+            .find_map(|(i, formula)| match formula {
+                GenericFormula::Conjunction(_) => Some(Rule::ConjunctionRight(i)),
+                GenericFormula::Disjunction(_) => Some(Rule::DisjunctionRight(i)),
+                GenericFormula::Implication(_) => Some(Rule::ImplicationRight(i)),
+                GenericFormula::Negation(_) => Some(Rule::NegationRight(i)),
+                GenericFormula::Universal(_) => Some(Rule::UniversalRight {
+                    index: i,
+                    eigenvariable: Variable::rand(),
+                }),
+                GenericFormula::Atomic(_) | GenericFormula::Existential(_) => None,
+            })
+    })
+}
+
+/// The terms occurring in the sequent which contain no bound variables.
+fn terms(sequent: &Sequent, default: &GenericTerm) -> Vec<GenericTerm> {
+    fn from_term(term: &GenericTerm, bound: &[Variable], out: &mut Vec<GenericTerm>) {
+        if term.variables().iter().all(|v| !bound.contains(v)) && !out.contains(term) {
+            out.push(term.clone());
+        }
+        if let GenericTerm::FunctionCall(f) = term {
+            for t in &f.terms {
+                from_term(t, bound, out);
+            }
+        }
+    }
+
+    fn from_formula(
+        formula: &GenericFormula,
+        bound: &mut Vec<Variable>,
+        out: &mut Vec<GenericTerm>,
+    ) {
+        match formula {
+            GenericFormula::Atomic(GenericAtomicFormula::Equality(e)) => {
+                from_term(&e.left, bound, out);
+                from_term(&e.right, bound, out);
+            }
+            GenericFormula::Atomic(GenericAtomicFormula::Predicate(p)) => {
+                for t in &p.terms {
+                    from_term(t, bound, out);
+                }
+            }
+            GenericFormula::Atomic(_) => {}
+            GenericFormula::Universal(x) => {
+                bound.push(x.left);
+                from_formula(&x.right, bound, out);
+                bound.pop();
+            }
+            GenericFormula::Existential(x) => {
+                bound.push(x.left);
+                from_formula(&x.right, bound, out);
+                bound.pop();
+            }
+            GenericFormula::Conjunction(x) => {
+                from_formula(&x.left, bound, out);
+                from_formula(&x.right, bound, out);
+            }
+            GenericFormula::Disjunction(x) => {
+                from_formula(&x.left, bound, out);
+                from_formula(&x.right, bound, out);
+            }
+            GenericFormula::Implication(x) => {
+                from_formula(&x.left, bound, out);
+                from_formula(&x.right, bound, out);
+            }
+            GenericFormula::Negation(x) => from_formula(&x.right, bound, out),
+        }
+    }
+
+    let mut out = Vec::new();
+    for formula in sequent.antecedent.iter().chain(&sequent.succedent) {
+        from_formula(formula, &mut Vec::new(), &mut out);
+    }
+    if out.is_empty() {
+        out.push(default.clone());
+    }
+    out
+}
+
+/// Apply a rule backwards and search for derivations of all its premises.
+fn apply(sequent: Sequent, rule: Rule, budget: usize, default: &GenericTerm) -> Option<Derivation> {
+    let premises = premises_of(&rule, &sequent)
+        .expect("the search only applies rules which match")
+        .into_iter()
+        .map(|premise| search(premise, budget, default))
+        .collect::<Option<Vec<_>>>()?;
+    Some(Derivation::new(sequent, rule, premises))
+}
+
+fn search(sequent: Sequent, budget: usize, default: &GenericTerm) -> Option<Derivation> {
+    if let Some(rule) = axiom(&sequent) {
+        return Some(Derivation::new(sequent, rule, Vec::new()));
+    }
+    if let Some(rule) = invertible(&sequent) {
+        return apply(sequent, rule, budget, default);
+    }
+    if budget == 0 {
+        return None;
+    }
+
+    // Instantiate a universal on the left or an existential on the right,
+    // keeping a copy of it for later instantiations.
+    let terms = terms(&sequent, default);
+    for (i, formula) in sequent.antecedent.iter().enumerate() {
+        let GenericFormula::Universal(x) = formula else {
+            continue;
+        };
+        for term in &terms {
+            let instance = Substitution::from_iter([(x.left, term.clone())]).apply(&x.right);
+            if sequent
+                .antecedent
+                .iter()
+                .any(|f| alpha_equivalent(f, &instance))
+            {
+                continue;
+            }
+            let mut contracted = sequent.clone();
+            contracted.antecedent.insert(i + 1, formula.clone());
+            let rule = Rule::UniversalLeft {
+                index: i + 1,
+                term: term.clone(),
+            };
+            if let Some(derivation) = apply(contracted, rule, budget - 1, default) {
+                return Some(Derivation::new(
+                    sequent,
+                    Rule::ContractionLeft(i),
+                    vec![derivation],
+                ));
+            }
+        }
+    }
+    for (i, formula) in sequent.succedent.iter().enumerate() {
+        let GenericFormula::Existential(x) = formula else {
+            continue;
+        };
+        for term in &terms {
+            let instance = Substitution::from_iter([(x.left, term.clone())]).apply(&x.right);
+            if sequent
+                .succedent
+                .iter()
+                .any(|f| alpha_equivalent(f, &instance))
+            {
+                continue;
+            }
+            let mut contracted = sequent.clone();
+            contracted.succedent.insert(i + 1, formula.clone());
+            let rule = Rule::ExistentialRight {
+                index: i + 1,
+                term: term.clone(),
+            };
+            if let Some(derivation) = apply(contracted, rule, budget - 1, default) {
+                return Some(Derivation::new(
+                    sequent,
+                    Rule::ContractionRight(i),
+                    vec![derivation],
+                ));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        Existential, GenericFormula, GenericTerm, Implication, Negation, PredicateCall, Signature,
+        Universal, Variable,
+    };
+
+    use super::{Sequent, SequentProver};
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_drinker_paradox() {
+        // ∃x.(D(x) ⇒ ∀y.D(y)) needs two instantiations of the existential.
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let drinker: GenericFormula = Existential {
+            left: x,
+            right: Implication {
+                left: p(b'D', vec![x.into()]),
+                right: Universal {
+                    left: y,
+                    right: p(b'D', vec![y.into()]),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+
+        let sequent = Sequent::new(vec![], vec![drinker]);
+        let derivation = SequentProver::default().prove(&sequent).unwrap();
+        assert_eq!(derivation.conclusion, sequent);
+        assert_eq!(derivation.check(), Ok(()));
+        assert!(!derivation.has_cut());
+
+        // Not provable with only one instantiation.
+        assert_eq!(SequentProver { max_gamma: 1 }.prove(&sequent), None);
+    }
+
+    #[test]
+    fn test_unprovable_and_printing() {
+        let a = p(b'A', vec![]);
+        let b = p(b'B', vec![]);
+
+        let sequent = Sequent::new(vec![a.clone()], vec![b.clone()]);
+        assert_eq!(SequentProver::default().prove(&sequent), None);
+
+        let not_not_a: GenericFormula = Negation {
+            right: Negation { right: a.clone() }.into(),
+        }
+        .into();
+        let sequent = Sequent::new(vec![not_not_a], vec![a]);
+        let derivation = SequentProver::default().prove(&sequent).unwrap();
+        assert_eq!(
+            derivation.to_string(),
+            "¬(¬(A())) ⊢ A()  (¬L)\n  ⊢ A(), ¬(A())  (¬R)\n    A() ⊢ A()  (Ax)"
+        );
+        assert_eq!(
+            derivation.latex(&Signature::default()),
+            "\\begin{prooftree}\n\
+             \\AxiomC{}\n\
+             \\RightLabel{$\\mathrm{Ax}$}\n\\UnaryInfC{$A \\vdash A$}\n\
+             \\RightLabel{$\\lnot R$}\n\\UnaryInfC{$\\vdash A, \\lnot A$}\n\
+             \\RightLabel{$\\lnot L$}\n\\UnaryInfC{$\\lnot \\lnot A \\vdash A$}\n\
+             \\end{prooftree}"
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use super::{
+    normal_forms::{Clause, ConjunctiveNormalFormula, Literal},
+    Constant, GenericAtomicFormula, GenericFormula, GenericTerm, Variable,
+};
+
+/// A function or predicate symbol in a [Signature].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// The human-readable name of the symbol.
+    pub name: String,
+    /// The number of arguments the symbol takes.
+    pub arity: usize,
+}
+
+/// The non-logical symbols of a language, and their human-readable names.
+///
+/// Syntax nodes only store `u64` labels. A signature records which labels are
+/// in use as constants, functions and predicates (together with their
+/// arities), and gives names to labels which should not be printed using the
+/// default label formatting.
+///
+/// A signature can be collected from a formula, and then have names attached:
+/// ```
+/// # use first_order_logic::syntax::{Constant, GenericFormula, PredicateCall, Signature};
+/// let formula: GenericFormula = PredicateCall {
+///     predicate: 1000,
+///     terms: vec![Constant { label: 3 }.into()],
+/// }
+/// .into();
+///
+/// let mut signature = Signature::from(&formula);
+/// signature.name_predicate(1000, "Prime");
+/// signature.name_constant(3, "three");
+///
+/// assert_eq!(signature.predicates[&1000].arity, 1);
+/// assert_eq!(signature.predicate_name(1000), "Prime");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Signature {
+    /// Names for variables. Variables are not part of the signature proper,
+    /// so are never collected from formulae.
+    pub variables: BTreeMap<u64, String>,
+    /// The constant symbols, and their names.
+    pub constants: BTreeMap<u64, String>,
+    /// The function symbols.
+    pub functions: BTreeMap<u64, Symbol>,
+    /// The predicate symbols.
+    pub predicates: BTreeMap<u64, Symbol>,
+}
+
+/// The name used for a label when no name has been given.
+///
+/// Matches the [Display](std::fmt::Display) implementations of the grammar:
+/// labels which fit in a byte are shown as a character.
+pub(crate) fn default_name(label: u64) -> String {
+    match u8::try_from(label) {
+        Ok(x) => char::from(x).to_string(),
+        Err(_) => label.to_string(),
+    }
+}
+
+impl Signature {
+    /// Name a variable.
+    pub fn name_variable(&mut self, label: u64, name: impl Into<String>) {
+        self.variables.insert(label, name.into());
+    }
+
+    /// Add a constant to the signature with the given name.
+    pub fn name_constant(&mut self, label: u64, name: impl Into<String>) {
+        self.constants.insert(label, name.into());
+    }
+
+    /// Name a function. If the function is not already in the signature, it
+    /// is added with an arity of zero.
+    pub fn name_function(&mut self, label: u64, name: impl Into<String>) {
+        self.functions
+            .entry(label)
+            .or_insert(Symbol {
+                name: String::new(),
+                arity: 0,
+            })
+            .name = name.into();
+    }
+
+    /// Name a predicate. If the predicate is not already in the signature, it
+    /// is added with an arity of zero.
+    pub fn name_predicate(&mut self, label: u64, name: impl Into<String>) {
+        self.predicates
+            .entry(label)
+            .or_insert(Symbol {
+                name: String::new(),
+                arity: 0,
+            })
+            .name = name.into();
+    }
+
+    /// The name of a variable.
+    pub fn variable_name(&self, variable: &Variable) -> String {
+        self.variables
+            .get(&variable.label)
+            .cloned()
+            .unwrap_or_else(|| variable.to_string())
+    }
+
+    /// The name of a constant.
+    pub fn constant_name(&self, constant: &Constant) -> String {
+        self.constants
+            .get(&constant.label)
+            .cloned()
+            .unwrap_or_else(|| constant.to_string())
+    }
+
+    /// The name of a function symbol.
+    pub fn function_name(&self, label: u64) -> String {
+        self.functions
+            .get(&label)
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| default_name(label))
+    }
+
+    /// The name of a predicate symbol.
+    pub fn predicate_name(&self, label: u64) -> String {
+        self.predicates
+            .get(&label)
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| default_name(label))
+    }
+
+    /// Add every constant, function and predicate in a term to the signature.
+    pub fn extend_from_term(&mut self, term: &GenericTerm) {
+        match term {
+            GenericTerm::Variable(_) => {}
+            GenericTerm::Constant(c) => {
+                self.constants
+                    .entry(c.label)
+                    .or_insert_with(|| c.to_string());
+            }
+            GenericTerm::FunctionCall(f) => {
+                self.functions.entry(f.function).or_insert(Symbol {
+                    name: default_name(f.function),
+                    arity: f.terms.len(),
+                });
+                f.terms.iter().for_each(|t| self.extend_from_term(t));
+            }
+        }
+    }
+
+    /// Add every constant, function and predicate in an atom to the signature.
+    pub fn extend_from_atom(&mut self, atom: &GenericAtomicFormula) {
+        match atom {
+            GenericAtomicFormula::Equality(e) => {
+                self.extend_from_term(&e.left);
+                self.extend_from_term(&e.right);
+            }
+            GenericAtomicFormula::Predicate(p) => {
+                self.predicates.entry(p.predicate).or_insert(Symbol {
+                    name: default_name(p.predicate),
+                    arity: p.terms.len(),
+                });
+                p.terms.iter().for_each(|t| self.extend_from_term(t));
+            }
+            GenericAtomicFormula::True | GenericAtomicFormula::False => {}
+        }
+    }
+
+    /// Add every constant, function and predicate in a formula to the
+    /// signature.
+    pub fn extend_from_formula(&mut self, formula: &GenericFormula) {
+        match formula {
+            GenericFormula::Atomic(x) => self.extend_from_atom(x),
+            GenericFormula::Universal(x) => self.extend_from_formula(&x.right),
+            GenericFormula::Existential(x) => self.extend_from_formula(&x.right),
+            GenericFormula::Conjunction(x) => {
+                self.extend_from_formula(&x.left);
+                self.extend_from_formula(&x.right);
+            }
+            GenericFormula::Disjunction(x) => {
+                self.extend_from_formula(&x.left);
+                self.extend_from_formula(&x.right);
+            }
+            GenericFormula::Implication(x) => {
+                self.extend_from_formula(&x.left);
+                self.extend_from_formula(&x.right);
+            }
+            GenericFormula::Negation(x) => self.extend_from_formula(&x.right),
+        }
+    }
+
+    /// Add every constant, function and predicate in a set of literals to the
+    /// signature.
+    pub fn extend_from_literals<'a>(&mut self, literals: impl IntoIterator<Item = &'a Literal>) {
+        literals
+            .into_iter()
+            .for_each(|l| self.extend_from_atom(l.atom()));
+    }
+}
+
+impl From<&GenericFormula> for Signature {
+    fn from(f: &GenericFormula) -> Self {
+        let mut signature = Self::default();
+        signature.extend_from_formula(f);
+        signature
+    }
+}
+
+impl From<&ConjunctiveNormalFormula> for Signature {
+    fn from(f: &ConjunctiveNormalFormula) -> Self {
+        let mut signature = Self::default();
+        f.clauses
+            .iter()
+            .for_each(|c: &Clause| signature.extend_from_literals(&c.literals));
+        signature
+    }
+}
@@ -0,0 +1,411 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+};
+
+use super::{GenericAtomicFormula, GenericFormula, GenericTerm, Variable};
+
+/// An assignment of domain elements to variables.
+pub type Assignment<E> = HashMap<Variable, E>;
+
+/// The errors which can occur when evaluating in a [Structure].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StructureError {
+    /// A free variable has no value in the assignment.
+    UnassignedVariable(Variable),
+    /// A constant has no interpretation.
+    UninterpretedConstant(u64),
+    /// A function has no value for some arguments.
+    UninterpretedFunction(u64),
+}
+
+impl Display for StructureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnassignedVariable(x) => {
+                f.write_fmt(format_args!("variable {} is unassigned", x))
+            }
+            Self::UninterpretedConstant(x) => {
+                f.write_fmt(format_args!("constant {} is uninterpreted", x))
+            }
+            Self::UninterpretedFunction(x) => f.write_fmt(format_args!(
+                "function {} is uninterpreted for some arguments",
+                x
+            )),
+        }
+    }
+}
+
+impl std::error::Error for StructureError {}
+
+/// A finite first-order structure: a domain, and interpretations of
+/// constants, functions and predicates by label.
+///
+/// Functions are interpreted by tables from argument tuples to elements.
+/// Predicates are interpreted by their extension: the set of argument tuples
+/// they hold for, so a predicate holds for no arguments unless they are
+/// listed.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   structure::{Assignment, Structure}, Equality, Existential, FunctionCall, GenericFormula,
+/// #   Universal, Variable,
+/// # };
+/// // The integers modulo 3 under addition.
+/// let mut z3 = Structure::new(vec![0, 1, 2]);
+/// for i in 0..3 {
+///     for j in 0..3 {
+///         z3.set_function(b'+'.into(), vec![i, j], (i + j) % 3);
+///     }
+/// }
+/// z3.set_constant(b'0'.into(), 0);
+///
+/// // Every element has an inverse: ∀x.∃y.x + y = 0.
+/// let x = Variable::new(b'x'.into());
+/// let y = Variable::new(b'y'.into());
+/// let inverses: GenericFormula = Universal {
+///     left: x,
+///     right: Existential {
+///         left: y,
+///         right: Equality {
+///             left: FunctionCall { function: b'+'.into(), terms: vec![x.into(), y.into()] }.into(),
+///             right: first_order_logic::syntax::Constant { label: b'0'.into() }.into(),
+///         }
+///         .into(),
+///     }
+///     .into(),
+/// }
+/// .into();
+///
+/// assert!(z3.satisfies(&inverses, &Assignment::new()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Structure<E: Eq + Hash> {
+    /// The elements of the structure.
+    pub domain: Vec<E>,
+    /// The element denoted by each constant.
+    pub constants: HashMap<u64, E>,
+    /// The table of each function.
+    pub functions: HashMap<u64, HashMap<Vec<E>, E>>,
+    /// The argument tuples each predicate holds for.
+    pub predicates: HashMap<u64, HashSet<Vec<E>>>,
+}
+
+impl<E: Clone + Eq + Hash> Structure<E> {
+    /// Create a structure over a domain, with nothing interpreted.
+    pub fn new(domain: Vec<E>) -> Self {
+        Self {
+            domain,
+            constants: HashMap::new(),
+            functions: HashMap::new(),
+            predicates: HashMap::new(),
+        }
+    }
+
+    /// Interpret a constant as an element.
+    pub fn set_constant(&mut self, label: u64, element: E) {
+        self.constants.insert(label, element);
+    }
+
+    /// Set the value of a function for some arguments.
+    pub fn set_function(&mut self, label: u64, arguments: Vec<E>, value: E) {
+        self.functions
+            .entry(label)
+            .or_default()
+            .insert(arguments, value);
+    }
+
+    /// Set whether a predicate holds for some arguments.
+    pub fn set_predicate(&mut self, label: u64, arguments: Vec<E>, holds: bool) {
+        let extension = self.predicates.entry(label).or_default();
+        if holds {
+            extension.insert(arguments);
+        } else {
+            extension.remove(&arguments);
+        }
+    }
+
+    /// The element a term denotes under an assignment.
+    pub fn evaluate(
+        &self,
+        term: &GenericTerm,
+        assignment: &Assignment<E>,
+    ) -> Result<E, StructureError> {
+        match term {
+            GenericTerm::Variable(v) => assignment
+                .get(v)
+                .cloned()
+                .ok_or(StructureError::UnassignedVariable(*v)),
+            GenericTerm::Constant(c) => self
+                .constants
+                .get(&c.label)
+                .cloned()
+                .ok_or(StructureError::UninterpretedConstant(c.label)),
+            GenericTerm::FunctionCall(f) => {
+                let arguments = f
+                    .terms
+                    .iter()
+                    .map(|t| self.evaluate(t, assignment))
+                    .collect::<Result<Vec<E>, _>>()?;
+                self.functions
+                    .get(&f.function)
+                    .and_then(|table| table.get(&arguments))
+                    .cloned()
+                    .ok_or(StructureError::UninterpretedFunction(f.function))
+            }
+        }
+    }
+
+    /// Whether an atomic formula holds under an assignment.
+    pub fn try_satisfies_atom(
+        &self,
+        atom: &GenericAtomicFormula,
+        assignment: &Assignment<E>,
+    ) -> Result<bool, StructureError> {
+        Ok(match atom {
+            GenericAtomicFormula::Equality(e) => {
+                self.evaluate(&e.left, assignment)? == self.evaluate(&e.right, assignment)?
+            }
+            GenericAtomicFormula::Predicate(p) => {
+                let arguments = p
+                    .terms
+                    .iter()
+                    .map(|t| self.evaluate(t, assignment))
+                    .collect::<Result<Vec<E>, _>>()?;
+                self.predicates
+                    .get(&p.predicate)
+                    .is_some_and(|extension| extension.contains(&arguments))
+            }
+            GenericAtomicFormula::True => true,
+            GenericAtomicFormula::False => false,
+        })
+    }
+
+    /// Whether a formula holds under an assignment, or an error if a term in
+    /// it cannot be evaluated.
+    pub fn try_satisfies(
+        &self,
+        formula: &GenericFormula,
+        assignment: &Assignment<E>,
+    ) -> Result<bool, StructureError> {
+        self.holds(formula, &mut assignment.clone())
+    }
+
+    /// Whether a formula holds under an assignment.
+    ///
+    /// # Panics
+    ///
+    /// If a term in the formula cannot be evaluated: a free variable is
+    /// unassigned, or a constant or function is not interpreted. See
+    /// [try_satisfies](Self::try_satisfies).
+    pub fn satisfies(&self, formula: &GenericFormula, assignment: &Assignment<E>) -> bool {
+        match self.try_satisfies(formula, assignment) {
+            Ok(holds) => holds,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Whether a formula holds under every assignment of its free
+    /// variables, so that the structure is a model of it.
+    ///
+    /// # Panics
+    ///
+    /// If a constant or function in the formula is not interpreted.
+    pub fn models(&self, formula: &GenericFormula) -> bool {
+        let free = formula.free_variables();
+        let mut assignment = Assignment::new();
+        self.for_all(
+            &free,
+            &mut assignment,
+            &mut |assignment| match self.holds(formula, assignment) {
+                Ok(holds) => holds,
+                Err(e) => panic!("{}", e),
+            },
+        )
+    }
+
+    /// Whether a condition holds for every assignment of the variables.
+    fn for_all(
+        &self,
+        variables: &[Variable],
+        assignment: &mut Assignment<E>,
+        condition: &mut dyn FnMut(&mut Assignment<E>) -> bool,
+    ) -> bool {
+        match variables.split_first() {
+            None => condition(assignment),
+            Some((v, rest)) => self.domain.iter().all(|e| {
+                assignment.insert(*v, e.clone());
+                self.for_all(rest, assignment, condition)
+            }),
+        }
+    }
+
+    /// Evaluate a quantifier body for each element, restoring the previous
+    /// value of the variable afterwards.
+    fn quantify(
+        &self,
+        variable: Variable,
+        body: &GenericFormula,
+        assignment: &mut Assignment<E>,
+        universal: bool,
+    ) -> Result<bool, StructureError> {
+        let previous = assignment.get(&variable).cloned();
+        let mut result = Ok(universal);
+        for e in &self.domain {
+            assignment.insert(variable, e.clone());
+            match self.holds(body, assignment) {
+                Ok(holds) if holds != universal => {
+                    result = Ok(holds);
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        match previous {
+            Some(e) => assignment.insert(variable, e),
+            None => assignment.remove(&variable),
+        };
+        result
+    }
+
+    fn holds(
+        &self,
+        formula: &GenericFormula,
+        assignment: &mut Assignment<E>,
+    ) -> Result<bool, StructureError> {
+        Ok(match formula {
+            GenericFormula::Atomic(x) => self.try_satisfies_atom(x, assignment)?,
+            GenericFormula::Universal(x) => self.quantify(x.left, &x.right, assignment, true)?,
+            GenericFormula::Existential(x) => self.quantify(x.left, &x.right, assignment, false)?,
+            GenericFormula::Conjunction(x) => {
+                self.holds(&x.left, assignment)? && self.holds(&x.right, assignment)?
+            }
+            GenericFormula::Disjunction(x) => {
+                self.holds(&x.left, assignment)? || self.holds(&x.right, assignment)?
+            }
+            GenericFormula::Implication(x) => {
+                !self.holds(&x.left, assignment)? || self.holds(&x.right, assignment)?
+            }
+            GenericFormula::Negation(x) => !self.holds(&x.right, assignment)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::PrenexNormalFormula, tableaux::negation_normal_form, Conjunction, Constant,
+        Disjunction, Existential, GenericFormula, GenericTerm, Implication, Negation,
+        PredicateCall, Universal, Variable,
+    };
+
+    use super::{Assignment, Structure, StructureError};
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    /// All structures over a domain of two elements interpreting unary `P`
+    /// and binary `R`.
+    fn structures() -> Vec<Structure<u8>> {
+        let pairs: Vec<Vec<u8>> = vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]];
+        let mut out = Vec::new();
+        for bits in 0..1u32 << 6 {
+            let mut structure = Structure::new(vec![0, 1]);
+            for e in 0..2u8 {
+                structure.set_predicate(b'P'.into(), vec![e], bits >> e & 1 == 1);
+            }
+            for (i, pair) in pairs.iter().enumerate() {
+                structure.set_predicate(b'R'.into(), pair.clone(), bits >> (i + 2) & 1 == 1);
+            }
+            out.push(structure);
+        }
+        out
+    }
+
+    #[test]
+    fn test_normal_forms_preserve_truth() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+        let z = Variable::new(b'z'.into());
+        // ¬(∀x.P(x) ⇒ ∃y.R(z, y)) ∨ (∃x.∀y.R(y, x) ∧ ¬P(z)), with z free.
+        let formula: GenericFormula = Disjunction {
+            left: Negation {
+                right: Implication {
+                    left: Universal {
+                        left: x,
+                        right: p(b'P', vec![x.into()]),
+                    }
+                    .into(),
+                    right: Existential {
+                        left: y,
+                        right: p(b'R', vec![z.into(), y.into()]),
+                    }
+                    .into(),
+                }
+                .into(),
+            }
+            .into(),
+            right: Conjunction {
+                left: Existential {
+                    left: x,
+                    right: Universal {
+                        left: y,
+                        right: p(b'R', vec![y.into(), x.into()]),
+                    }
+                    .into(),
+                }
+                .into(),
+                right: Negation {
+                    right: p(b'P', vec![z.into()]),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        let prenex: GenericFormula = PrenexNormalFormula::from(formula.clone()).into();
+        let nnf = negation_normal_form(formula.clone());
+
+        for structure in structures() {
+            for e in 0..2 {
+                let assignment = Assignment::from([(z, e)]);
+                let expected = structure.satisfies(&formula, &assignment);
+                assert_eq!(structure.satisfies(&nnf, &assignment), expected);
+                assert_eq!(structure.satisfies(&prenex, &assignment), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_models_and_errors() {
+        let x = Variable::new(b'x'.into());
+        let mut structure = Structure::new(vec![0, 1]);
+        structure.set_predicate(b'P'.into(), vec![0], true);
+        structure.set_predicate(b'P'.into(), vec![1], true);
+        assert!(structure.models(&p(b'P', vec![x.into()])));
+        structure.set_predicate(b'P'.into(), vec![1], false);
+        assert!(!structure.models(&p(b'P', vec![x.into()])));
+
+        let c: GenericTerm = Constant { label: b'c'.into() }.into();
+        assert_eq!(
+            structure.try_satisfies(&p(b'P', vec![c]), &Assignment::new()),
+            Err(StructureError::UninterpretedConstant(b'c'.into()))
+        );
+        assert_eq!(
+            structure.try_satisfies(&p(b'P', vec![x.into()]), &Assignment::new()),
+            Err(StructureError::UnassignedVariable(x))
+        );
+    }
+}
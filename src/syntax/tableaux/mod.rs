@@ -0,0 +1,629 @@
+use std::collections::VecDeque;
+
+use super::{
+    normal_forms::Literal,
+    unification::{unify, Substitution},
+    Conjunction, Constant, Disjunction, Existential, FunctionCall, GenericAtomicFormula,
+    GenericFormula, GenericTerm, Negation, Signature, Universal, Variable,
+};
+
+mod model;
+
+pub use model::Countermodel;
+
+////////////////////////////////////////////////////////////////////////////////
+// Negation-Normal Form
+////////////////////////////////////////////////////////////////////////////////
+
+/// Convert a formula to negation-normal form: implications are eliminated,
+/// and negations are pushed inwards until they only apply to atoms.
+pub fn negation_normal_form(formula: GenericFormula) -> GenericFormula {
+    nnf(formula, false)
+}
+
+fn nnf(formula: GenericFormula, negated: bool) -> GenericFormula {
+    match formula {
+        GenericFormula::Atomic(GenericAtomicFormula::True) if negated => {
+            GenericAtomicFormula::False.into()
+        }
+        GenericFormula::Atomic(GenericAtomicFormula::False) if negated => {
+            GenericAtomicFormula::True.into()
+        }
+        GenericFormula::Atomic(x) if negated => Negation {
+            right: GenericFormula::Atomic(x),
+        }
+        .into(),
+        GenericFormula::Atomic(x) => x.into(),
+        GenericFormula::Negation(x) => nnf(x.right, !negated),
+        GenericFormula::Universal(x) if negated => Existential {
+            left: x.left,
+            right: nnf(x.right, true),
+        }
+        .into(),
+        GenericFormula::Universal(x) => Universal {
+            left: x.left,
+            right: nnf(x.right, false),
+        }
+        .into(),
+        GenericFormula::Existential(x) if negated => Universal {
+            left: x.left,
+            right: nnf(x.right, true),
+        }
+        .into(),
+        GenericFormula::Existential(x) => Existential {
+            left: x.left,
+            right: nnf(x.right, false),
+        }
+        .into(),
+        GenericFormula::Conjunction(x) if negated => Disjunction {
+            left: nnf(x.left, true),
+            right: nnf(x.right, true),
+        }
+        .into(),
+        GenericFormula::Conjunction(x) => Conjunction {
+            left: nnf(x.left, false),
+            right: nnf(x.right, false),
+        }
+        .into(),
+        GenericFormula::Disjunction(x) if negated => Conjunction {
+            left: nnf(x.left, true),
+            right: nnf(x.right, true),
+        }
+        .into(),
+        GenericFormula::Disjunction(x) => Disjunction {
+            left: nnf(x.left, false),
+            right: nnf(x.right, false),
+        }
+        .into(),
+        // P ⇒ Q ≡ ¬P ∨ Q, and ¬(P ⇒ Q) ≡ P ∧ ¬Q
+        GenericFormula::Implication(x) if negated => Conjunction {
+            left: nnf(x.left, false),
+            right: nnf(x.right, true),
+        }
+        .into(),
+        GenericFormula::Implication(x) => Disjunction {
+            left: nnf(x.left, true),
+            right: nnf(x.right, false),
+        }
+        .into(),
+    }
+}
+
+/// The universal closure of a formula.
+pub(crate) fn universal_closure(formula: GenericFormula) -> GenericFormula {
+    formula
+        .free_variables()
+        .into_iter()
+        .rev()
+        .fold(formula, |right, left| Universal { left, right }.into())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Prover
+////////////////////////////////////////////////////////////////////////////////
+
+/// A step expanding a closed tableau.
+///
+/// Formulas are given as they appear on the branch, before the closing
+/// substitution of the [`TableauProof`] is applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableauStep {
+    /// A conjunction on the branch, both of whose conjuncts are added to it.
+    Alpha(GenericFormula),
+    /// A disjunction on the branch, which splits it in two: one branch with
+    /// the left disjunct, and one with the right.
+    Beta(GenericFormula),
+    /// A universal formula on the branch, an instance of which is added to
+    /// it.
+    Gamma {
+        /// The universal formula.
+        formula: GenericFormula,
+        /// Its body, with the bound variable replaced by a fresh free
+        /// variable.
+        instance: GenericFormula,
+    },
+    /// An existential formula on the branch, an instance of which is added to
+    /// it.
+    Delta {
+        /// The existential formula.
+        formula: GenericFormula,
+        /// Its body, with the bound variable replaced by the witness.
+        instance: GenericFormula,
+        /// The Skolem term witnessing the formula.
+        witness: GenericTerm,
+    },
+    /// The branch closes, on two complementary literals or on a single `⊥`.
+    Close(Vec<GenericFormula>),
+}
+
+/// A closed tableau, showing that some formulas are unsatisfiable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableauProof {
+    /// The formulas refuted, universally closed and in negation-normal form.
+    pub formulas: Vec<GenericFormula>,
+    /// The steps expanding the tableau, depth first. Each branch ends with a
+    /// [`Close`](TableauStep::Close) step, or a [`Beta`](TableauStep::Beta)
+    /// step followed by the steps of its left branch and then of its right
+    /// branch.
+    pub steps: Vec<TableauStep>,
+    /// The substitution for the free variables of the tableau under which
+    /// every branch closes.
+    pub substitution: Substitution,
+}
+
+/// The outcome of a tableau search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableauResult {
+    /// Every branch of the tableau closed, so the formulas are unsatisfiable.
+    Closed(TableauProof),
+    /// The free-variable tableau did not close, and a separate search of
+    /// ground tableaux found a saturated open branch, which describes a model
+    /// of the formulas.
+    Open(Countermodel),
+    /// The free-variable tableau did not close within the limits, and no
+    /// model was found: either the ground search found none with at most
+    /// `max_domain` elements, or it was not attempted.
+    ResourceOut,
+}
+
+/// A free-variable semantic tableaux prover.
+///
+/// Formulas are converted to negation-normal form and expanded into a tree of
+/// branches. Universal formulas (γ-formulas) are instantiated with fresh free
+/// variables, existential formulas (δ-formulas) with Skolem terms, and a
+/// branch closes when two of its literals are complementary under a
+/// substitution shared by the whole tableau. The search is repeated with an
+/// increasing bound on the number of γ-rule applications on each branch.
+///
+/// Models are not read off the free-variable tableau, whose open branches
+/// are cut short by the bound. Instead, if the tableau does not close, the
+/// prover runs a separate, exhaustive search of ground tableaux, which
+/// instantiate universal formulas with every element of a finite domain of
+/// constants and existential formulas with an existing or a new element. A
+/// saturated open branch of one with at most `max_domain` elements gives a
+/// model of the formulas. This search is only attempted when the formulas
+/// contain no function symbols and no equalities, as `=` would otherwise be
+/// treated as an ordinary predicate; in every other case the result is
+/// [`ResourceOut`](TableauResult::ResourceOut).
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   tableaux::{TableauProver, TableauResult}, Existential, GenericFormula,
+/// #   PredicateCall, Universal, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let p = |t| -> GenericFormula {
+///     PredicateCall { predicate: b'P'.into(), terms: vec![t] }.into()
+/// };
+///
+/// let everything: GenericFormula = Universal { left: x, right: p(x.into()) }.into();
+/// let something: GenericFormula = Existential { left: x, right: p(x.into()) }.into();
+///
+/// let prover = TableauProver::default();
+/// assert!(matches!(
+///     prover.prove(&[everything.clone()], &something),
+///     TableauResult::Closed(_)
+/// ));
+///
+/// // The countermodel has an element for which P does not hold.
+/// match prover.prove(&[something], &everything) {
+///     TableauResult::Open(model) => assert_eq!(model.domain.len(), 2),
+///     result => panic!("{:?}", result),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableauProver {
+    /// The maximum number of γ-rule applications on a branch.
+    pub max_gamma: usize,
+    /// The largest domain the ground search for a countermodel considers.
+    pub max_domain: usize,
+}
+
+impl Default for TableauProver {
+    fn default() -> Self {
+        Self {
+            max_gamma: 6,
+            max_domain: 4,
+        }
+    }
+}
+
+impl TableauProver {
+    /// Attempt to show that a set of formulas is unsatisfiable. Free
+    /// variables are treated as universally quantified.
+    pub fn refute(&self, formulas: &[GenericFormula]) -> TableauResult {
+        let formulas: Vec<GenericFormula> = formulas
+            .iter()
+            .map(|f| negation_normal_form(universal_closure(f.clone())))
+            .collect();
+
+        for limit in 1..=self.max_gamma {
+            let todo: VecDeque<GenericFormula> = formulas.iter().cloned().collect();
+            let mut proof = None;
+            close(
+                todo,
+                Vec::new(),
+                0,
+                limit,
+                &Substitution::new(),
+                &mut Vec::new(),
+                &mut |substitution, steps| {
+                    proof = Some((substitution.clone(), steps.clone()));
+                    true
+                },
+            );
+            if let Some((substitution, steps)) = proof {
+                return TableauResult::Closed(TableauProof {
+                    formulas,
+                    steps,
+                    substitution,
+                });
+            }
+        }
+
+        let mut signature = Signature::default();
+        formulas
+            .iter()
+            .for_each(|f| signature.extend_from_formula(f));
+        if signature.functions.is_empty() && !formulas.iter().any(contains_equality) {
+            if let Some(model) = model::search(&formulas, &signature, self.max_domain) {
+                return TableauResult::Open(model);
+            }
+        }
+        TableauResult::ResourceOut
+    }
+
+    /// Attempt to prove that a goal follows from some axioms, by refuting the
+    /// axioms together with the negated goal. An open result is a
+    /// countermodel: a model of the axioms in which the goal is false.
+    pub fn prove(&self, axioms: &[GenericFormula], goal: &GenericFormula) -> TableauResult {
+        let mut formulas = axioms.to_vec();
+        formulas.push(
+            Negation {
+                right: universal_closure(goal.clone()),
+            }
+            .into(),
+        );
+        self.refute(&formulas)
+    }
+}
+
+fn contains_equality(formula: &GenericFormula) -> bool {
+    match formula {
+        GenericFormula::Atomic(x) => matches!(x, GenericAtomicFormula::Equality(_)),
+        GenericFormula::Universal(x) => contains_equality(&x.right),
+        GenericFormula::Existential(x) => contains_equality(&x.right),
+        GenericFormula::Conjunction(x) => contains_equality(&x.left) || contains_equality(&x.right),
+        GenericFormula::Disjunction(x) => contains_equality(&x.left) || contains_equality(&x.right),
+        GenericFormula::Implication(x) => contains_equality(&x.left) || contains_equality(&x.right),
+        GenericFormula::Negation(x) => contains_equality(&x.right),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Free-Variable Tableaux
+////////////////////////////////////////////////////////////////////////////////
+
+/// The literal a negation-normal formula is, if it is one.
+fn as_literal(formula: &GenericFormula) -> Option<Literal> {
+    match formula {
+        GenericFormula::Atomic(x) => Some(Literal::Atom(x.clone())),
+        GenericFormula::Negation(x) => match &x.right {
+            GenericFormula::Atomic(a) => Some(Literal::Atom(a.clone()).negate()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Try to close the branch with unexpanded formulas `todo` and literals
+/// `literals`, extending `substitution`. `then` is called with each
+/// substitution which closes the branch, and the search stops as soon as it
+/// returns true.
+///
+/// The steps expanding the branch are recorded in `steps`, which `then` is
+/// also given, and removed again if the branch does not close.
+fn close(
+    todo: VecDeque<GenericFormula>,
+    literals: Vec<Literal>,
+    gammas: usize,
+    limit: usize,
+    substitution: &Substitution,
+    steps: &mut Vec<TableauStep>,
+    then: &mut dyn FnMut(&Substitution, &mut Vec<TableauStep>) -> bool,
+) -> bool {
+    let recorded = steps.len();
+    let closed = expand(todo, literals, gammas, limit, substitution, steps, then);
+    if !closed {
+        steps.truncate(recorded);
+    }
+    closed
+}
+
+fn expand(
+    mut todo: VecDeque<GenericFormula>,
+    literals: Vec<Literal>,
+    gammas: usize,
+    limit: usize,
+    substitution: &Substitution,
+    steps: &mut Vec<TableauStep>,
+    then: &mut dyn FnMut(&Substitution, &mut Vec<TableauStep>) -> bool,
+) -> bool {
+    let formula = match todo.pop_front() {
+        Some(f) => f,
+        None => return false,
+    };
+
+    if let Some(literal) = as_literal(&formula) {
+        match (literal.atom(), literal.is_negated()) {
+            (GenericAtomicFormula::False, false) | (GenericAtomicFormula::True, true) => {
+                steps.push(TableauStep::Close(vec![formula]));
+                return then(substitution, steps);
+            }
+            (GenericAtomicFormula::True, false) | (GenericAtomicFormula::False, true) => {
+                return close(todo, literals, gammas, limit, substitution, steps, then)
+            }
+            _ => {}
+        }
+
+        let atom = substitution.apply(literal.atom());
+        for other in literals
+            .iter()
+            .filter(|l| l.is_negated() != literal.is_negated())
+        {
+            if let Some(unifier) = unify(&atom, &substitution.apply(other.atom())) {
+                steps.push(TableauStep::Close(vec![
+                    other.clone().into(),
+                    formula.clone(),
+                ]));
+                if then(&substitution.compose(&unifier), steps) {
+                    return true;
+                }
+                steps.pop();
+            }
+        }
+
+        let mut literals = literals;
+        literals.push(literal);
+        return close(todo, literals, gammas, limit, substitution, steps, then);
+    }
+
+    match formula {
+        GenericFormula::Conjunction(x) => {
+            steps.push(TableauStep::Alpha(GenericFormula::Conjunction(x.clone())));
+            todo.push_front(x.right);
+            todo.push_front(x.left);
+            close(todo, literals, gammas, limit, substitution, steps, then)
+        }
+        GenericFormula::Disjunction(x) => {
+            steps.push(TableauStep::Beta(GenericFormula::Disjunction(x.clone())));
+            let mut left = todo.clone();
+            left.push_front(x.left);
+            let mut right = todo;
+            right.push_front(x.right);
+            close(
+                left,
+                literals.clone(),
+                gammas,
+                limit,
+                substitution,
+                steps,
+                &mut |s, steps| {
+                    close(
+                        right.clone(),
+                        literals.clone(),
+                        gammas,
+                        limit,
+                        s,
+                        steps,
+                        then,
+                    )
+                },
+            )
+        }
+        GenericFormula::Universal(x) => {
+            if gammas >= limit {
+                return close(todo, literals, gammas, limit, substitution, steps, then);
+            }
+            let instance = Substitution::from_iter([(x.left, Variable::rand().into())]);
+            let instance = instance.apply(&x.right);
+            steps.push(TableauStep::Gamma {
+                formula: GenericFormula::Universal(x.clone()),
+                instance: instance.clone(),
+            });
+            todo.push_front(instance);
+            todo.push_back(GenericFormula::Universal(x));
+            close(todo, literals, gammas + 1, limit, substitution, steps, then)
+        }
+        GenericFormula::Existential(x) => {
+            // The Skolem term only depends on the free variables of the
+            // formula (the δ⁺-rule).
+            let arguments: Vec<GenericTerm> = GenericFormula::Existential(x.clone())
+                .free_variables()
+                .into_iter()
+                .map(GenericTerm::from)
+                .collect();
+            let witness: GenericTerm = if arguments.is_empty() {
+                Constant {
+                    label: rand::random(),
+                }
+                .into()
+            } else {
+                FunctionCall {
+                    function: rand::random(),
+                    terms: arguments,
+                }
+                .into()
+            };
+            let instance = Substitution::from_iter([(x.left, witness.clone())]).apply(&x.right);
+            steps.push(TableauStep::Delta {
+                formula: GenericFormula::Existential(x.clone()),
+                instance: instance.clone(),
+                witness,
+            });
+            todo.push_front(instance);
+            close(todo, literals, gammas, limit, substitution, steps, then)
+        }
+        GenericFormula::Atomic(_)
+        | GenericFormula::Negation(_)
+        | GenericFormula::Implication(_) => {
+            unreachable!("formulas are in negation-normal form")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        Conjunction, Constant, Disjunction, Existential, FunctionCall, GenericFormula, GenericTerm,
+        Implication, Negation, PredicateCall, Universal, Variable,
+    };
+
+    use super::{negation_normal_form, TableauProver, TableauResult};
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_negation_normal_form() {
+        let x = Variable::new(b'x'.into());
+        // ¬∀x.(P(x) ⇒ Q(x)) becomes ∃x.(P(x) ∧ ¬Q(x))
+        let formula: GenericFormula = Negation {
+            right: Universal {
+                left: x,
+                right: Implication {
+                    left: p(b'P', vec![x.into()]),
+                    right: p(b'Q', vec![x.into()]),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        let expected: GenericFormula = Existential {
+            left: x,
+            right: Conjunction {
+                left: p(b'P', vec![x.into()]),
+                right: Negation {
+                    right: p(b'Q', vec![x.into()]),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        assert_eq!(negation_normal_form(formula), expected);
+    }
+
+    #[test]
+    fn test_closes_with_several_instances() {
+        let x = Variable::new(b'x'.into());
+        let a: GenericTerm = Constant { label: b'a'.into() }.into();
+        let b: GenericTerm = Constant { label: b'b'.into() }.into();
+
+        // ∀x.(P(x) ∨ Q(x)), ¬P(a), ¬Q(b), ∀x.(Q(x) ⇒ P(x)) is unsatisfiable,
+        // and needs two instances of the first formula.
+        let formulas: Vec<GenericFormula> = vec![
+            Universal {
+                left: x,
+                right: Disjunction {
+                    left: p(b'P', vec![x.into()]),
+                    right: p(b'Q', vec![x.into()]),
+                }
+                .into(),
+            }
+            .into(),
+            Negation {
+                right: p(b'P', vec![a]),
+            }
+            .into(),
+            Negation {
+                right: p(b'Q', vec![b]),
+            }
+            .into(),
+            Universal {
+                left: x,
+                right: Implication {
+                    left: p(b'Q', vec![x.into()]),
+                    right: p(b'P', vec![x.into()]),
+                }
+                .into(),
+            }
+            .into(),
+        ];
+        assert!(matches!(
+            TableauProver::default().refute(&formulas),
+            TableauResult::Closed(_)
+        ));
+    }
+
+    #[test]
+    fn test_countermodel() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+
+        // ∀x.∃y.R(x, y) does not entail ∃x.R(x, x): the smallest countermodel
+        // has two elements, related to each other but not to themselves.
+        let axiom: GenericFormula = Universal {
+            left: x,
+            right: Existential {
+                left: y,
+                right: p(b'R', vec![x.into(), y.into()]),
+            }
+            .into(),
+        }
+        .into();
+        let goal: GenericFormula = Existential {
+            left: x,
+            right: p(b'R', vec![x.into(), x.into()]),
+        }
+        .into();
+
+        match TableauProver::default().prove(&[axiom], &goal) {
+            TableauResult::Open(model) => {
+                assert_eq!(model.domain.len(), 2);
+                for c in &model.domain {
+                    assert!(!model.holds(
+                        &PredicateCall {
+                            predicate: b'R'.into(),
+                            terms: vec![(*c).into(), (*c).into()],
+                        }
+                        .into()
+                    ));
+                }
+            }
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_no_countermodel_with_function_symbols() {
+        let x = Variable::new(b'x'.into());
+
+        // ∀x.P(f(x)) is satisfiable, but the ground search for a model is not
+        // attempted with function symbols.
+        let formula: GenericFormula = Universal {
+            left: x,
+            right: p(
+                b'P',
+                vec![FunctionCall {
+                    function: b'f'.into(),
+                    terms: vec![x.into()],
+                }
+                .into()],
+            ),
+        }
+        .into();
+        assert_eq!(
+            TableauProver::default().refute(&[formula]),
+            TableauResult::ResourceOut
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::syntax::{
+    normal_forms::Literal, unification::Substitution, Constant, GenericAtomicFormula,
+    GenericFormula, Signature, Universal, Variable,
+};
+
+use super::as_literal;
+
+/// A finite Herbrand model, read off a saturated open branch of a ground
+/// tableau.
+///
+/// The domain is a set of constants, each of which denotes itself. An atom
+/// holds exactly when it occurs positively in `literals`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Countermodel {
+    /// The elements of the model.
+    pub domain: Vec<Constant>,
+    /// The ground literals on the branch.
+    pub literals: Vec<Literal>,
+}
+
+impl Countermodel {
+    /// Whether a ground atom holds in the model.
+    pub fn holds(&self, atom: &GenericAtomicFormula) -> bool {
+        match atom {
+            GenericAtomicFormula::True => true,
+            GenericAtomicFormula::False => false,
+            _ => self.literals.contains(&Literal::Atom(atom.clone())),
+        }
+    }
+}
+
+/// A branch of a ground tableau.
+#[derive(Clone)]
+struct Branch {
+    todo: Vec<GenericFormula>,
+    /// The universal formulas on the branch, and the elements they have been
+    /// instantiated with.
+    gammas: Vec<(Universal<GenericFormula>, Vec<Constant>)>,
+    literals: Vec<Literal>,
+    domain: Vec<Constant>,
+}
+
+fn instantiate(variable: Variable, body: &GenericFormula, c: Constant) -> GenericFormula {
+    Substitution::from_iter([(variable, c.into())]).apply(body)
+}
+
+/// Search for a saturated open branch of a ground tableau with at most
+/// `max_domain` elements.
+///
+/// Universal formulas are instantiated with every element of the domain.
+/// Existential formulas are instantiated with each existing element in turn,
+/// and then with a new one. A branch which has every formula expanded and
+/// every universal formula instantiated with every element is a Hintikka set,
+/// so describes a model.
+pub(crate) fn search(
+    formulas: &[GenericFormula],
+    signature: &Signature,
+    max_domain: usize,
+) -> Option<Countermodel> {
+    let mut domain: Vec<Constant> = signature
+        .constants
+        .keys()
+        .map(|label| Constant { label: *label })
+        .collect();
+    if domain.is_empty() {
+        domain.push(Constant {
+            label: rand::random(),
+        });
+    }
+    expand(
+        Branch {
+            todo: formulas.iter().rev().cloned().collect(),
+            gammas: Vec::new(),
+            literals: Vec::new(),
+            domain,
+        },
+        max_domain,
+    )
+}
+
+fn expand(mut branch: Branch, max_domain: usize) -> Option<Countermodel> {
+    loop {
+        let formula = match branch.todo.pop() {
+            Some(f) => f,
+            None => {
+                // Instantiate a universal formula with an element it has not
+                // been instantiated with yet.
+                let next = branch.gammas.iter_mut().find_map(|(gamma, done)| {
+                    let c = *branch.domain.iter().find(|c| !done.contains(c))?;
+                    done.push(c);
+                    Some(instantiate(gamma.left, &gamma.right, c))
+                });
+                match next {
+                    Some(f) => f,
+                    None => {
+                        return Some(Countermodel {
+                            domain: branch.domain,
+                            literals: branch.literals,
+                        })
+                    }
+                }
+            }
+        };
+
+        if let Some(literal) = as_literal(&formula) {
+            match (literal.atom(), literal.is_negated()) {
+                (GenericAtomicFormula::False, false) | (GenericAtomicFormula::True, true) => {
+                    return None
+                }
+                (GenericAtomicFormula::True, false) | (GenericAtomicFormula::False, true) => {}
+                _ => {
+                    if branch.literals.contains(&literal.negate()) {
+                        return None;
+                    }
+                    if !branch.literals.contains(&literal) {
+                        branch.literals.push(literal);
+                    }
+                }
+            }
+            continue;
+        }
+
+        match formula {
+            GenericFormula::Conjunction(x) => {
+                branch.todo.push(x.right);
+                branch.todo.push(x.left);
+            }
+            GenericFormula::Disjunction(x) => {
+                let mut left = branch.clone();
+                left.todo.push(x.left);
+                if let Some(model) = expand(left, max_domain) {
+                    return Some(model);
+                }
+                branch.todo.push(x.right);
+            }
+            GenericFormula::Universal(x) => branch.gammas.push((*x, Vec::new())),
+            GenericFormula::Existential(x) => {
+                for c in &branch.domain {
+                    let mut reuse = branch.clone();
+                    reuse.todo.push(instantiate(x.left, &x.right, *c));
+                    if let Some(model) = expand(reuse, max_domain) {
+                        return Some(model);
+                    }
+                }
+                if branch.domain.len() >= max_domain {
+                    return None;
+                }
+                let fresh = Constant {
+                    label: rand::random(),
+                };
+                branch.domain.push(fresh);
+                branch.todo.push(instantiate(x.left, &x.right, fresh));
+            }
+            GenericFormula::Atomic(_)
+            | GenericFormula::Negation(_)
+            | GenericFormula::Implication(_) => {
+                unreachable!("formulas are in negation-normal form")
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use crate::syntax::{
+    normal_forms::{ClauseNormalForm, ConjunctiveNormalFormula, SkolemNormalFormula},
+    resolution::{Inference, Proof},
+    GenericFormula, Negation, Signature,
+};
+
+use super::{Names, Role};
+
+/// An input formula, and how it was Skolemised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Input {
+    formula: GenericFormula,
+    role: Role,
+    /// The index of the conjecture this formula is the negation of.
+    negates: Option<usize>,
+    /// The Skolem normal form and the Skolem functions it introduced, if any
+    /// were.
+    skolemised: Option<(GenericFormula, Vec<u64>)>,
+}
+
+/// A problem converted to clauses, remembering the formula each clause came
+/// from, so that refutations of the clauses can be exported as TSTP
+/// derivations.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   resolution::{RefutationResult, ResolutionProver}, tstp::ClausifiedProblem,
+/// #   Existential, GenericFormula, GenericTerm, PredicateCall, Signature, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let p = |t: GenericTerm| -> GenericFormula { PredicateCall { predicate: 1, terms: vec![t] }.into() };
+/// let something: GenericFormula = Existential { left: x, right: p(x.into()) }.into();
+///
+/// let problem = ClausifiedProblem::new(&[something.clone()], Some(&something));
+/// let RefutationResult::Unsatisfiable(proof) = ResolutionProver::default().refute(&problem.clauses)
+/// else {
+///     panic!()
+/// };
+///
+/// let tstp = problem.to_tstp(&proof, &Signature::default());
+/// assert!(tstp.contains("inference(skolemize, [status(esa), new_symbols(skolem, [sk"));
+/// assert!(tstp.contains("fof(f1, conjecture, (? [X120] : p1(X120)))."));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClausifiedProblem {
+    /// The clauses of the problem, to be given to a prover.
+    pub clauses: ClauseNormalForm,
+    inputs: Vec<Input>,
+    /// The index of the input each clause came from.
+    origins: Vec<usize>,
+}
+
+impl ClausifiedProblem {
+    /// Convert some axioms, and the negation of a goal if there is one, into
+    /// clauses.
+    ///
+    /// As in [`ResolutionProver::prove`](crate::syntax::resolution::ResolutionProver::prove),
+    /// free variables are treated as universally quantified in each clause.
+    pub fn new(axioms: &[GenericFormula], goal: Option<&GenericFormula>) -> Self {
+        let mut problem = Self {
+            clauses: Vec::new(),
+            inputs: Vec::new(),
+            origins: Vec::new(),
+        };
+        for axiom in axioms {
+            problem.add(axiom.clone(), Role::Axiom, None);
+        }
+        if let Some(goal) = goal {
+            let conjecture = problem.inputs.len();
+            problem.inputs.push(Input {
+                formula: goal.clone(),
+                role: Role::Conjecture,
+                negates: None,
+                skolemised: None,
+            });
+            let negated = Negation {
+                right: goal.clone(),
+            }
+            .into();
+            problem.add(negated, Role::NegatedConjecture, Some(conjecture));
+        }
+        problem
+    }
+
+    fn add(&mut self, formula: GenericFormula, role: Role, negates: Option<usize>) {
+        let (snf, state) = SkolemNormalFormula::with_state(formula.clone());
+        let symbols = state.symbols();
+
+        let index = self.inputs.len();
+        let clauses: ClauseNormalForm = ConjunctiveNormalFormula::from(snf.clone()).into();
+        self.origins
+            .extend(std::iter::repeat_n(index, clauses.len()));
+        self.clauses.extend(clauses);
+
+        self.inputs.push(Input {
+            formula,
+            role,
+            negates,
+            skolemised: (!symbols.is_empty()).then(|| (snf.into(), symbols)),
+        });
+    }
+
+    /// Export a refutation of the problem's clauses as a TSTP derivation.
+    ///
+    /// The derivation starts with the input formulas the refutation uses, as
+    /// `fof` formulas named `f0`, `f1`, …, in order. A negated conjecture is
+    /// derived from its conjecture, and a formula with existential
+    /// quantifiers is followed by its Skolemisation, named with a `_sk`
+    /// suffix, which lists the Skolem functions it introduces. The steps of
+    /// the proof follow as `cnf` clauses named `c` followed by their ids,
+    /// each with an inference record referencing its parents.
+    ///
+    /// Names are taken from the signature, as by [`formula`](super::formula).
+    ///
+    /// # Panics
+    ///
+    /// If the proof has an input clause which is not one of the problem's
+    /// clauses.
+    pub fn to_tstp(&self, proof: &Proof, signature: &Signature) -> String {
+        let names = Names::new(
+            signature,
+            self.inputs
+                .iter()
+                .filter_map(|input| input.skolemised.as_ref())
+                .flat_map(|(_, symbols)| symbols.iter().copied()),
+        );
+
+        let origin = |clause: usize| {
+            *self
+                .origins
+                .get(clause)
+                .expect("the proof refutes the problem's clauses")
+        };
+        let mut used = vec![false; self.inputs.len()];
+        for step in &proof.steps {
+            if let Inference::Input(clause) = step.inference {
+                let input = origin(clause);
+                used[input] = true;
+                if let Some(conjecture) = self.inputs[input].negates {
+                    used[conjecture] = true;
+                }
+            }
+        }
+
+        let mut out = String::new();
+        for (i, input) in self.inputs.iter().enumerate() {
+            if !used[i] {
+                continue;
+            }
+            let source = match input.negates {
+                Some(conjecture) => format!(
+                    ", inference(negate_conjecture, [status(cth)], [f{}])",
+                    conjecture
+                ),
+                None => String::new(),
+            };
+            out.push_str(&format!(
+                "fof(f{}, {}, {}{}).\n",
+                i,
+                input.role,
+                names.formula(&input.formula),
+                source
+            ));
+            if let Some((snf, symbols)) = &input.skolemised {
+                let symbols: Vec<String> = symbols.iter().map(|s| names.skolem(*s)).collect();
+                out.push_str(&format!(
+                    "fof(f{}_sk, {}, {}, inference(skolemize, [status(esa), new_symbols(skolem, [{}])], [f{}])).\n",
+                    i,
+                    Role::Plain,
+                    names.formula(snf),
+                    symbols.join(","),
+                    i
+                ));
+            }
+        }
+
+        for step in &proof.steps {
+            let clause = names.clause(&step.clause);
+            let source = match &step.inference {
+                Inference::Input(clause) => {
+                    let input = origin(*clause);
+                    let parent = if self.inputs[input].skolemised.is_some() {
+                        format!("f{}_sk", input)
+                    } else {
+                        format!("f{}", input)
+                    };
+                    format!("inference(clausify, [status(thm)], [{}])", parent)
+                }
+                Inference::EqualityAxiom => "theory(equality)".to_string(),
+                inference => {
+                    let rule = match inference {
+                        Inference::Resolution { .. } => "resolution",
+                        Inference::Factoring { .. } => "factoring",
+                        Inference::Paramodulation { .. } => "paramodulation",
+                        Inference::EqualityResolution { .. } => "equality_resolution",
+                        Inference::Input(_) | Inference::EqualityAxiom => unreachable!(),
+                    };
+                    let parents: Vec<String> = inference
+                        .parents()
+                        .iter()
+                        .map(|p| format!("c{}", p))
+                        .collect();
+                    format!(
+                        "inference({}, [status(thm)], [{}])",
+                        rule,
+                        parents.join(",")
+                    )
+                }
+            };
+            let role = if step.inference == Inference::EqualityAxiom {
+                Role::Axiom
+            } else {
+                Role::Plain
+            };
+            out.push_str(&format!(
+                "cnf(c{}, {}, {}, {}).\n",
+                step.id, role, clause, source
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        resolution::{EqualityHandling, RefutationResult, ResolutionProver},
+        Constant, Equality, Existential, FunctionCall, GenericFormula, GenericTerm, Implication,
+        PredicateCall, Signature, Universal, Variable,
+    };
+
+    use super::ClausifiedProblem;
+
+    fn p(predicate: u64, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall { predicate, terms }.into()
+    }
+
+    fn refute(problem: &ClausifiedProblem, equality: EqualityHandling) -> String {
+        let prover = ResolutionProver {
+            equality,
+            ..ResolutionProver::default()
+        };
+        match prover.refute(&problem.clauses) {
+            RefutationResult::Unsatisfiable(proof) => {
+                problem.to_tstp(&proof, &Signature::default())
+            }
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_skolemisation_steps() {
+        // ∀x.∃y.R(x, y) and ∀x.∀y.(R(x, y) ⇒ S(y)) imply ∃y.S(y).
+        let x = Variable::new(1);
+        let y = Variable::new(2);
+        let serial: GenericFormula = Universal {
+            left: x,
+            right: Existential {
+                left: y,
+                right: p(1, vec![x.into(), y.into()]),
+            }
+            .into(),
+        }
+        .into();
+        let rule: GenericFormula = Universal {
+            left: x,
+            right: Universal {
+                left: y,
+                right: Implication {
+                    left: p(1, vec![x.into(), y.into()]),
+                    right: p(2, vec![y.into()]),
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into();
+        let goal: GenericFormula = Existential {
+            left: y,
+            right: p(2, vec![y.into()]),
+        }
+        .into();
+
+        let problem = ClausifiedProblem::new(&[serial, rule], Some(&goal));
+        let tstp = refute(&problem, EqualityHandling::default());
+        let lines: Vec<&str> = tstp.lines().collect();
+
+        assert_eq!(lines[0], "fof(f0, axiom, (! [X1] : (? [X2] : p1(X1,X2)))).");
+        assert!(lines[1].starts_with("fof(f0_sk, plain, ! [X1] : p1(X1,sk"));
+        assert!(lines[1].contains("inference(skolemize, [status(esa), new_symbols(skolem, [sk"));
+        assert!(lines.contains(&"fof(f2, conjecture, (? [X2] : p2(X2)))."));
+        assert!(lines.contains(
+            &"fof(f3, negated_conjecture, ~ (? [X2] : p2(X2)), inference(negate_conjecture, [status(cth)], [f2]))."
+        ));
+        assert!(lines
+            .iter()
+            .any(|l| l.contains("inference(clausify, [status(thm)], [f0_sk])")));
+        assert!(lines
+            .last()
+            .unwrap()
+            .contains(", plain, $false, inference(resolution, [status(thm)], [c"));
+    }
+
+    #[test]
+    fn test_equality_axioms() {
+        // a = b and P(a) imply P(b).
+        let a: GenericTerm = Constant { label: 1 }.into();
+        let b: GenericTerm = FunctionCall {
+            function: 3,
+            terms: vec![a.clone()],
+        }
+        .into();
+        let equation: GenericFormula = Equality {
+            left: a.clone(),
+            right: b.clone(),
+        }
+        .into();
+
+        let problem = ClausifiedProblem::new(&[equation, p(1, vec![a])], Some(&p(1, vec![b])));
+        let tstp = refute(&problem, EqualityHandling::Axioms);
+        assert!(tstp.contains(", axiom, ") && tstp.contains("theory(equality)"));
+        assert!(tstp.contains("fof(f0, axiom, c1 = f3(c1))."));
+
+        let tstp = refute(&problem, EqualityHandling::Paramodulation);
+        assert!(tstp.contains("inference(paramodulation, [status(thm)], [c"));
+    }
+}
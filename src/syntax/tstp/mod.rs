@@ -0,0 +1,248 @@
+use std::{collections::HashSet, fmt::Display};
+
+use super::{
+    normal_forms::{Clause, Literal},
+    GenericAtomicFormula, GenericFormula, GenericTerm, Signature, Variable,
+};
+
+mod derivation;
+mod natural_deduction;
+mod sequent;
+mod tableau;
+
+pub use derivation::ClausifiedProblem;
+
+////////////////////////////////////////////////////////////////////////////////
+// Roles
+////////////////////////////////////////////////////////////////////////////////
+
+/// The role of an annotated formula in a TSTP file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// An axiom of the problem.
+    Axiom,
+    /// The formula to be proved.
+    Conjecture,
+    /// The negation of the conjecture, to be refuted.
+    NegatedConjecture,
+    /// A formula derived by an inference.
+    Plain,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Axiom => "axiom",
+            Self::Conjecture => "conjecture",
+            Self::NegatedConjecture => "negated_conjecture",
+            Self::Plain => "plain",
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Names
+////////////////////////////////////////////////////////////////////////////////
+
+fn is_word(name: &str, upper: bool) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| {
+        if upper {
+            c.is_ascii_uppercase()
+        } else {
+            c.is_ascii_lowercase()
+        }
+    }) && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// How symbols are named in TSTP.
+///
+/// Names from the signature are used when they are valid TSTP words: upper
+/// case for variables and lower case for everything else. Other symbols are
+/// named by their kind and label, such as `p80` for the predicate with label
+/// `80`, and Skolem functions are named `sk` followed by their label.
+pub(crate) struct Names<'a> {
+    signature: &'a Signature,
+    skolem: HashSet<u64>,
+}
+
+impl<'a> Names<'a> {
+    pub(crate) fn new(signature: &'a Signature, skolem: impl IntoIterator<Item = u64>) -> Self {
+        Self {
+            signature,
+            skolem: skolem.into_iter().collect(),
+        }
+    }
+
+    fn variable(&self, variable: &Variable) -> String {
+        match self.signature.variables.get(&variable.label) {
+            Some(name) if is_word(name, true) => name.clone(),
+            _ => format!("X{}", variable.label),
+        }
+    }
+
+    fn constant(&self, label: u64) -> String {
+        match self.signature.constants.get(&label) {
+            Some(name) if is_word(name, false) => name.clone(),
+            _ => format!("c{}", label),
+        }
+    }
+
+    fn function(&self, label: u64) -> String {
+        if self.skolem.contains(&label) {
+            return format!("sk{}", label);
+        }
+        match self.signature.functions.get(&label) {
+            Some(symbol) if is_word(&symbol.name, false) => symbol.name.clone(),
+            _ => format!("f{}", label),
+        }
+    }
+
+    fn predicate(&self, label: u64) -> String {
+        match self.signature.predicates.get(&label) {
+            Some(symbol) if is_word(&symbol.name, false) => symbol.name.clone(),
+            _ => format!("p{}", label),
+        }
+    }
+
+    /// The name of a Skolem function, for `new_symbols` records.
+    pub(crate) fn skolem(&self, label: u64) -> String {
+        format!("sk{}", label)
+    }
+
+    fn application(&self, name: String, terms: &[GenericTerm]) -> String {
+        if terms.is_empty() {
+            name
+        } else {
+            let terms: Vec<String> = terms.iter().map(|t| self.term(t)).collect();
+            format!("{}({})", name, terms.join(","))
+        }
+    }
+
+    fn term(&self, term: &GenericTerm) -> String {
+        match term {
+            GenericTerm::Variable(v) => self.variable(v),
+            GenericTerm::Constant(c) => self.constant(c.label),
+            GenericTerm::FunctionCall(f) => self.application(self.function(f.function), &f.terms),
+        }
+    }
+
+    fn atom(&self, atom: &GenericAtomicFormula, negated: bool) -> String {
+        match (atom, negated) {
+            (GenericAtomicFormula::Equality(e), false) => {
+                format!("{} = {}", self.term(&e.left), self.term(&e.right))
+            }
+            (GenericAtomicFormula::Equality(e), true) => {
+                format!("{} != {}", self.term(&e.left), self.term(&e.right))
+            }
+            (GenericAtomicFormula::Predicate(p), _) => {
+                let atom = self.application(self.predicate(p.predicate), &p.terms);
+                if negated {
+                    format!("~ {}", atom)
+                } else {
+                    atom
+                }
+            }
+            (GenericAtomicFormula::True, false) | (GenericAtomicFormula::False, true) => {
+                "$true".to_string()
+            }
+            (GenericAtomicFormula::False, false) | (GenericAtomicFormula::True, true) => {
+                "$false".to_string()
+            }
+        }
+    }
+
+    /// A formula in `fof` syntax, fully parenthesised. Free variables are
+    /// universally quantified, as TSTP requires `fof` formulas to be closed.
+    pub(crate) fn formula(&self, formula: &GenericFormula) -> String {
+        let free = formula.free_variables();
+        let body = self.open_formula(formula);
+        if free.is_empty() {
+            body
+        } else {
+            let free: Vec<String> = free.iter().map(|v| self.variable(v)).collect();
+            format!("! [{}] : {}", free.join(","), body)
+        }
+    }
+
+    fn open_formula(&self, formula: &GenericFormula) -> String {
+        match formula {
+            GenericFormula::Atomic(x) => self.atom(x, false),
+            GenericFormula::Universal(x) => format!(
+                "(! [{}] : {})",
+                self.variable(&x.left),
+                self.open_formula(&x.right)
+            ),
+            GenericFormula::Existential(x) => format!(
+                "(? [{}] : {})",
+                self.variable(&x.left),
+                self.open_formula(&x.right)
+            ),
+            GenericFormula::Conjunction(x) => format!(
+                "({} & {})",
+                self.open_formula(&x.left),
+                self.open_formula(&x.right)
+            ),
+            GenericFormula::Disjunction(x) => format!(
+                "({} | {})",
+                self.open_formula(&x.left),
+                self.open_formula(&x.right)
+            ),
+            GenericFormula::Implication(x) => format!(
+                "({} => {})",
+                self.open_formula(&x.left),
+                self.open_formula(&x.right)
+            ),
+            GenericFormula::Negation(x) => format!("~ {}", self.open_formula(&x.right)),
+        }
+    }
+
+    /// A clause in `cnf` syntax. The empty clause is `$false`.
+    pub(crate) fn clause(&self, clause: &Clause) -> String {
+        if clause.is_empty() {
+            return "$false".to_string();
+        }
+        let literals: Vec<String> = clause
+            .literals
+            .iter()
+            .map(|l| match l {
+                Literal::Atom(x) => self.atom(x, false),
+                Literal::Negated(x) => self.atom(&x.right, true),
+            })
+            .collect();
+        literals.join(" | ")
+    }
+}
+
+/// Write a formula in TSTP `fof` syntax, taking names from the signature.
+///
+/// Free variables are universally quantified, as TSTP requires `fof`
+/// formulas to be closed.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   tstp, GenericFormula, Implication, PredicateCall, Signature, Universal, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let formula: GenericFormula = Implication {
+///     left: PredicateCall { predicate: 1, terms: vec![x.into()] }.into(),
+///     right: PredicateCall { predicate: 2, terms: vec![x.into()] }.into(),
+/// }
+/// .into();
+///
+/// let mut signature = Signature::from(&formula);
+/// signature.name_predicate(1, "man");
+/// signature.name_variable(x.label, "X");
+///
+/// assert_eq!(tstp::formula(&formula, &signature), "! [X] : (man(X) => p2(X))");
+/// ```
+pub fn formula(formula: &GenericFormula, signature: &Signature) -> String {
+    Names::new(signature, []).formula(formula)
+}
+
+/// Write a clause in TSTP `cnf` syntax, taking names from the signature.
+pub fn clause(clause: &Clause, signature: &Signature) -> String {
+    Names::new(signature, []).clause(clause)
+}
@@ -0,0 +1,205 @@
+use crate::syntax::{
+    natural_deduction::{AssumptionLabel, Proof, Rule},
+    sequent::Sequent,
+    GenericFormula, Signature,
+};
+
+use super::{sequent::sequent_formula, Names, Role};
+
+/// The name of a rule in inference records.
+fn rule_name(rule: &Rule) -> &'static str {
+    match rule {
+        Rule::Assumption(_) => "assumption",
+        Rule::TrueIntro => "true_intro",
+        Rule::FalseElim => "false_elim",
+        Rule::ConjunctionIntro => "conjunction_intro",
+        Rule::ConjunctionElimLeft => "conjunction_elim_left",
+        Rule::ConjunctionElimRight => "conjunction_elim_right",
+        Rule::DisjunctionIntroLeft => "disjunction_intro_left",
+        Rule::DisjunctionIntroRight => "disjunction_intro_right",
+        Rule::DisjunctionElim { .. } => "disjunction_elim",
+        Rule::ImplicationIntro(_) => "implication_intro",
+        Rule::ImplicationElim => "implication_elim",
+        Rule::NegationIntro(_) => "negation_intro",
+        Rule::NegationElim => "negation_elim",
+        Rule::ReductioAdAbsurdum(_) => "reductio_ad_absurdum",
+        Rule::UniversalIntro { .. } => "universal_intro",
+        Rule::UniversalElim { .. } => "universal_elim",
+        Rule::ExistentialIntro { .. } => "existential_intro",
+        Rule::ExistentialElim { .. } => "existential_elim",
+        Rule::EqualityIntro => "equality_intro",
+        Rule::EqualityElim { .. } => "equality_elim",
+    }
+}
+
+/// The label of the assumption a rule discharges in each of its premises, if
+/// any.
+fn discharged(rule: &Rule, premise: usize) -> Option<AssumptionLabel> {
+    match (rule, premise) {
+        (Rule::ImplicationIntro(l), 0)
+        | (Rule::NegationIntro(l), 0)
+        | (Rule::ReductioAdAbsurdum(l), 0)
+        | (Rule::DisjunctionElim { left: l, .. }, 1)
+        | (Rule::DisjunctionElim { right: l, .. }, 2)
+        | (Rule::ExistentialElim { label: l, .. }, 1) => Some(*l),
+        _ => None,
+    }
+}
+
+impl Proof {
+    /// Write the lines of the proof, returning the name of the line of its
+    /// conclusion and its open assumptions.
+    fn write_tstp(
+        &self,
+        names: &Names,
+        out: &mut String,
+        lines: &mut usize,
+    ) -> (String, Vec<(AssumptionLabel, GenericFormula)>) {
+        let mut parents = Vec::with_capacity(self.premises.len());
+        let mut open: Vec<(AssumptionLabel, GenericFormula)> = Vec::new();
+        if let Rule::Assumption(label) = self.rule {
+            open.push((label, self.conclusion.clone()));
+        }
+        for (i, premise) in self.premises.iter().enumerate() {
+            let (name, premise_open) = premise.write_tstp(names, out, lines);
+            parents.push(name);
+            let discharged = discharged(&self.rule, i);
+            for assumption in premise_open {
+                if Some(assumption.0) != discharged && !open.contains(&assumption) {
+                    open.push(assumption);
+                }
+            }
+        }
+
+        let name = format!("n{}", lines);
+        *lines += 1;
+        let judgement = Sequent::new(
+            open.iter().map(|(_, formula)| formula.clone()).collect(),
+            vec![self.conclusion.clone()],
+        );
+        out.push_str(&format!(
+            "fof({}, {}, {}, inference({}, [status(thm)], [{}])).\n",
+            name,
+            Role::Plain,
+            names.formula(&sequent_formula(&judgement)),
+            rule_name(&self.rule),
+            parents.join(",")
+        ));
+        (name, open)
+    }
+
+    /// Export the proof as a TSTP derivation.
+    ///
+    /// Each step is written as the formula `⋀Γ ⇒ A`, universally closed,
+    /// where `A` is the formula it proves and `Γ` are the assumptions still
+    /// open at that step, or as just `A` when there are none. An assumption
+    /// `A` is written as `A ⇒ A`. The steps are named `n0`, `n1`, …, with
+    /// each premise before the step using it, so the conclusion comes last.
+    /// Each has an inference record naming its rule in snake case, such as
+    /// `implication_intro`, and referencing its premises.
+    ///
+    /// Names are taken from the signature, as by [`formula`](super::formula).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use first_order_logic::syntax::{
+    /// #   natural_deduction::{Proof, Rule}, GenericFormula, Implication, PredicateCall,
+    /// #   Signature,
+    /// # };
+    /// let p: GenericFormula = PredicateCall { predicate: 1, terms: vec![] }.into();
+    /// let goal: GenericFormula = Implication { left: p.clone(), right: p.clone() }.into();
+    ///
+    /// let proof = Proof::new(
+    ///     goal,
+    ///     Rule::ImplicationIntro(1),
+    ///     vec![Proof::new(p, Rule::Assumption(1), vec![])],
+    /// );
+    /// assert_eq!(
+    ///     proof.to_tstp(&Signature::default()),
+    ///     "fof(n0, plain, (p1 => p1), inference(assumption, [status(thm)], [])).\n\
+    ///      fof(n1, plain, (p1 => p1), inference(implication_intro, [status(thm)], [n0])).\n"
+    /// );
+    /// ```
+    pub fn to_tstp(&self, signature: &Signature) -> String {
+        let mut out = String::new();
+        self.write_tstp(&Names::new(signature, []), &mut out, &mut 0);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        natural_deduction::{Proof, Rule},
+        Disjunction, GenericFormula, Implication, PredicateCall, Signature,
+    };
+
+    #[test]
+    fn test_discharged_assumptions() {
+        let atom = |predicate: u8| -> GenericFormula {
+            PredicateCall {
+                predicate: predicate.into(),
+                terms: vec![],
+            }
+            .into()
+        };
+        let (p, q) = (atom(b'P'), atom(b'Q'));
+        let p_or_q: GenericFormula = Disjunction {
+            left: p.clone(),
+            right: q.clone(),
+        }
+        .into();
+        let q_or_p: GenericFormula = Disjunction {
+            left: q.clone(),
+            right: p.clone(),
+        }
+        .into();
+
+        // [P ∨ Q]¹ [P]² ⊢ Q ∨ P and [Q]³ ⊢ Q ∨ P, so P ∨ Q ⇒ Q ∨ P.
+        let proof = Proof::new(
+            Implication {
+                left: p_or_q.clone(),
+                right: q_or_p.clone(),
+            }
+            .into(),
+            Rule::ImplicationIntro(1),
+            vec![Proof::new(
+                q_or_p.clone(),
+                Rule::DisjunctionElim { left: 2, right: 3 },
+                vec![
+                    Proof::new(p_or_q.clone(), Rule::Assumption(1), vec![]),
+                    Proof::new(
+                        q_or_p.clone(),
+                        Rule::DisjunctionIntroRight,
+                        vec![Proof::new(p.clone(), Rule::Assumption(2), vec![])],
+                    ),
+                    Proof::new(
+                        q_or_p.clone(),
+                        Rule::DisjunctionIntroLeft,
+                        vec![Proof::new(q.clone(), Rule::Assumption(3), vec![])],
+                    ),
+                ],
+            )],
+        );
+        assert!(proof.check(&[], &proof.conclusion).is_ok());
+
+        let mut signature = Signature::default();
+        signature.name_predicate(b'P'.into(), "p");
+        signature.name_predicate(b'Q'.into(), "q");
+        assert_eq!(
+            proof.to_tstp(&signature),
+            [
+                "fof(n0, plain, ((p | q) => (p | q)), inference(assumption, [status(thm)], [])).",
+                "fof(n1, plain, (p => p), inference(assumption, [status(thm)], [])).",
+                "fof(n2, plain, (p => (q | p)), inference(disjunction_intro_right, [status(thm)], [n1])).",
+                "fof(n3, plain, (q => q), inference(assumption, [status(thm)], [])).",
+                "fof(n4, plain, (q => (q | p)), inference(disjunction_intro_left, [status(thm)], [n3])).",
+                "fof(n5, plain, ((p | q) => (q | p)), inference(disjunction_elim, [status(thm)], [n0,n2,n4])).",
+                "fof(n6, plain, ((p | q) => (q | p)), inference(implication_intro, [status(thm)], [n5])).",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use crate::syntax::{
+    sequent::{Derivation, Rule, Sequent},
+    Conjunction, Disjunction, GenericAtomicFormula, GenericFormula, Implication, Signature,
+};
+
+use super::{Names, Role};
+
+/// The name of a rule in inference records.
+fn rule_name(rule: &Rule) -> &'static str {
+    match rule {
+        Rule::Axiom { .. } => "axiom",
+        Rule::TrueRight(_) => "true_right",
+        Rule::FalseLeft(_) => "false_left",
+        Rule::WeakeningLeft(_) => "weakening_left",
+        Rule::WeakeningRight(_) => "weakening_right",
+        Rule::ContractionLeft(_) => "contraction_left",
+        Rule::ContractionRight(_) => "contraction_right",
+        Rule::ExchangeLeft(_) => "exchange_left",
+        Rule::ExchangeRight(_) => "exchange_right",
+        Rule::Cut(_) => "cut",
+        Rule::ConjunctionLeft(_) => "conjunction_left",
+        Rule::ConjunctionRight(_) => "conjunction_right",
+        Rule::DisjunctionLeft(_) => "disjunction_left",
+        Rule::DisjunctionRight(_) => "disjunction_right",
+        Rule::ImplicationLeft(_) => "implication_left",
+        Rule::ImplicationRight(_) => "implication_right",
+        Rule::NegationLeft(_) => "negation_left",
+        Rule::NegationRight(_) => "negation_right",
+        Rule::UniversalLeft { .. } => "universal_left",
+        Rule::UniversalRight { .. } => "universal_right",
+        Rule::ExistentialLeft { .. } => "existential_left",
+        Rule::ExistentialRight { .. } => "existential_right",
+    }
+}
+
+/// The formula `⋀Γ ⇒ ⋁Δ` a sequent `Γ ⊢ Δ` stands for. An empty conjunction
+/// is `⊤`, and an empty disjunction `⊥`.
+pub(super) fn sequent_formula(sequent: &Sequent) -> GenericFormula {
+    let join = |formulas: &[GenericFormula], empty: GenericAtomicFormula, conjunction: bool| {
+        formulas
+            .iter()
+            .cloned()
+            .reduce(|left, right| {
+                if conjunction {
+                    Conjunction { left, right }.into()
+                } else {
+                    Disjunction { left, right }.into()
+                }
+            })
+            .unwrap_or_else(|| empty.into())
+    };
+    let succedent = join(&sequent.succedent, GenericAtomicFormula::False, false);
+    if sequent.antecedent.is_empty() {
+        return succedent;
+    }
+    Implication {
+        left: join(&sequent.antecedent, GenericAtomicFormula::True, true),
+        right: succedent,
+    }
+    .into()
+}
+
+impl Derivation {
+    fn write_tstp(&self, names: &Names, out: &mut String, lines: &mut usize) -> String {
+        let premises: Vec<String> = self
+            .premises
+            .iter()
+            .map(|p| p.write_tstp(names, out, lines))
+            .collect();
+        let name = format!("s{}", lines);
+        *lines += 1;
+        out.push_str(&format!(
+            "fof({}, {}, {}, inference({}, [status(thm)], [{}])).\n",
+            name,
+            Role::Plain,
+            names.formula(&sequent_formula(&self.conclusion)),
+            rule_name(&self.rule),
+            premises.join(",")
+        ));
+        name
+    }
+
+    /// Export the derivation as a TSTP derivation.
+    ///
+    /// Each sequent `Γ ⊢ Δ` is written as the formula `⋀Γ ⇒ ⋁Δ`, universally
+    /// closed, or as just `⋁Δ` when `Γ` is empty. An empty succedent is
+    /// `$false`. The sequents are named `s0`, `s1`, …, with each premise
+    /// before the sequent it proves, so the conclusion comes last. Each has
+    /// an inference record naming its rule in snake case, such as
+    /// `implication_right`, and referencing its premises.
+    ///
+    /// Names are taken from the signature, as by [`formula`](super::formula).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use first_order_logic::syntax::{
+    /// #   sequent::{Sequent, SequentProver}, GenericFormula, Implication, PredicateCall,
+    /// #   Signature,
+    /// # };
+    /// let p: GenericFormula = PredicateCall { predicate: 1, terms: vec![] }.into();
+    /// let goal: GenericFormula = Implication { left: p.clone(), right: p }.into();
+    ///
+    /// let derivation = SequentProver::default()
+    ///     .prove(&Sequent::new(vec![], vec![goal]))
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     derivation.to_tstp(&Signature::default()),
+    ///     "fof(s0, plain, (p1 => p1), inference(axiom, [status(thm)], [])).\n\
+    ///      fof(s1, plain, (p1 => p1), inference(implication_right, [status(thm)], [s0])).\n"
+    /// );
+    /// ```
+    pub fn to_tstp(&self, signature: &Signature) -> String {
+        let mut out = String::new();
+        self.write_tstp(&Names::new(signature, []), &mut out, &mut 0);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        sequent::{Derivation, Rule, Sequent},
+        GenericFormula, Negation, PredicateCall, Signature, Universal, Variable,
+    };
+
+    #[test]
+    fn test_quantifiers_and_empty_sides() {
+        let x = Variable::new(b'x'.into());
+        let p = |t: Variable| -> GenericFormula {
+            PredicateCall {
+                predicate: b'P'.into(),
+                terms: vec![t.into()],
+            }
+            .into()
+        };
+        let everything: GenericFormula = Universal {
+            left: x,
+            right: p(x),
+        }
+        .into();
+        let not_p: GenericFormula = Negation { right: p(x) }.into();
+
+        // ∀x.P(x), ¬P(x) ⊢
+        let derivation = Derivation::new(
+            Sequent::new(vec![everything.clone(), not_p.clone()], vec![]),
+            Rule::UniversalLeft {
+                index: 0,
+                term: x.into(),
+            },
+            vec![Derivation::new(
+                Sequent::new(vec![p(x), not_p], vec![]),
+                Rule::NegationLeft(1),
+                vec![Derivation::new(
+                    Sequent::new(vec![p(x)], vec![p(x)]),
+                    Rule::Axiom { left: 0, right: 0 },
+                    vec![],
+                )],
+            )],
+        );
+        assert!(derivation.check().is_ok());
+
+        let mut signature = Signature::default();
+        signature.name_predicate(b'P'.into(), "p");
+        signature.name_variable(x.label, "X");
+        assert_eq!(
+            derivation.to_tstp(&signature),
+            [
+                "fof(s0, plain, ! [X] : (p(X) => p(X)), inference(axiom, [status(thm)], [])).",
+                "fof(s1, plain, ! [X] : ((p(X) & ~ p(X)) => $false), inference(negation_left, [status(thm)], [s0])).",
+                "fof(s2, plain, ! [X] : (((! [X] : p(X)) & ~ p(X)) => $false), inference(universal_left, [status(thm)], [s1])).",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use crate::syntax::{
+    tableaux::{TableauProof, TableauStep},
+    unification::Substitution,
+    Constant, GenericAtomicFormula, GenericFormula, GenericTerm, Signature,
+};
+
+use super::{Names, Role};
+
+/// The formulas on a branch, with the names of the lines introducing them.
+type Branch = Vec<(GenericFormula, String)>;
+
+struct Export<'a> {
+    names: Names<'a>,
+    substitution: &'a Substitution,
+    /// The term free variables left unbound by the substitution are
+    /// instantiated with.
+    default: GenericTerm,
+    out: String,
+    lines: usize,
+}
+
+impl Export<'_> {
+    /// A formula under the closing substitution, with no free variables.
+    fn ground(&self, formula: &GenericFormula) -> GenericFormula {
+        let formula = self.substitution.apply(formula);
+        let unbound: Substitution = formula
+            .free_variables()
+            .into_iter()
+            .map(|v| (v, self.default.clone()))
+            .collect();
+        unbound.apply(&formula)
+    }
+
+    fn line(&mut self, role: Role, formula: &GenericFormula, source: Option<String>) -> String {
+        let name = format!("t{}", self.lines);
+        self.lines += 1;
+        let source = source.map_or(String::new(), |s| format!(", {}", s));
+        let formula = self.names.formula(&self.ground(formula));
+        self.out.push_str(&format!(
+            "fof({}, {}, {}{}).\n",
+            name, role, formula, source
+        ));
+        name
+    }
+
+    fn inference(&mut self, formula: &GenericFormula, rule: &str, parents: &[&str]) -> String {
+        let source = format!(
+            "inference({}, [status(thm)], [{}])",
+            rule,
+            parents.join(",")
+        );
+        self.line(Role::Plain, formula, Some(source))
+    }
+
+    /// Write the lines of a branch, taking its steps from `steps`, and return
+    /// the name of the line deriving `$false` from it.
+    fn branch<'s>(
+        &mut self,
+        steps: &mut impl Iterator<Item = &'s TableauStep>,
+        mut branch: Branch,
+    ) -> String {
+        let on_branch = |branch: &Branch, formula: &GenericFormula| {
+            branch
+                .iter()
+                .rev()
+                .find(|(f, _)| f == formula)
+                .map(|(_, name)| name.clone())
+                .expect("steps only expand formulas on their branch")
+        };
+        let falsum: GenericFormula = GenericAtomicFormula::False.into();
+
+        loop {
+            match steps.next().expect("every branch of the tableau closes") {
+                TableauStep::Alpha(formula) => {
+                    let GenericFormula::Conjunction(x) = formula else {
+                        panic!("α-steps expand conjunctions")
+                    };
+                    let parent = on_branch(&branch, formula);
+                    for conjunct in [&x.left, &x.right] {
+                        let name = self.inference(conjunct, "tableau_alpha", &[&parent]);
+                        branch.push((conjunct.clone(), name));
+                    }
+                }
+                TableauStep::Beta(formula) => {
+                    let GenericFormula::Disjunction(x) = formula else {
+                        panic!("β-steps expand disjunctions")
+                    };
+                    let parent = on_branch(&branch, formula);
+                    let mut assumptions = Vec::new();
+                    let mut refutations = Vec::new();
+                    for disjunct in [&x.left, &x.right] {
+                        let source = format!("introduced(assumption, [{}])", parent);
+                        let name = self.line(Role::Plain, disjunct, Some(source));
+                        let mut sub_branch = branch.clone();
+                        sub_branch.push((disjunct.clone(), name.clone()));
+                        assumptions.push(name);
+                        refutations.push(self.branch(steps, sub_branch));
+                    }
+                    let source = format!(
+                        "inference(tableau_beta, [status(thm), discharge([{}])], [{},{}])",
+                        assumptions.join(","),
+                        parent,
+                        refutations.join(",")
+                    );
+                    return self.line(Role::Plain, &falsum, Some(source));
+                }
+                TableauStep::Gamma { formula, instance } => {
+                    let parent = on_branch(&branch, formula);
+                    let name = self.inference(instance, "tableau_gamma", &[&parent]);
+                    branch.push((instance.clone(), name));
+                }
+                TableauStep::Delta {
+                    formula,
+                    instance,
+                    witness,
+                } => {
+                    let parent = on_branch(&branch, formula);
+                    let symbol = match self.substitution.apply(witness) {
+                        GenericTerm::FunctionCall(f) => self.names.skolem(f.function),
+                        witness => self.names.term(&witness),
+                    };
+                    let source = format!(
+                        "inference(tableau_delta, [status(esa), new_symbols(skolem, [{}])], [{}])",
+                        symbol, parent
+                    );
+                    let name = self.line(Role::Plain, instance, Some(source));
+                    branch.push((instance.clone(), name));
+                }
+                TableauStep::Close(literals) => {
+                    let parents: Vec<String> =
+                        literals.iter().map(|l| on_branch(&branch, l)).collect();
+                    let parents: Vec<&str> = parents.iter().map(String::as_str).collect();
+                    return self.inference(&falsum, "tableau_close", &parents);
+                }
+            }
+        }
+    }
+}
+
+impl TableauProof {
+    /// Export the closed tableau as a TSTP refutation.
+    ///
+    /// The refuted formulas come first, as axioms named `t0`, `t1`, …, and
+    /// are followed by the formulas each step adds to its branch, in the
+    /// order of [`steps`](TableauProof::steps). Each branch ends with a line
+    /// deriving `$false` from the literals closing it. The two disjuncts of a
+    /// β-step are introduced as assumptions, and the refutations of the two
+    /// branches they start are combined into a `$false` which discharges
+    /// them. The last line is the refutation of the whole tableau.
+    ///
+    /// Formulas are written under the closing substitution, and free
+    /// variables it leaves unbound are instantiated with a constant, so that
+    /// each line is closed. The Skolem terms of δ-steps are new symbols,
+    /// named as in [`ClausifiedProblem::to_tstp`](super::ClausifiedProblem::to_tstp).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use first_order_logic::syntax::{
+    /// #   tableaux::{TableauProver, TableauResult}, Existential, GenericFormula, PredicateCall,
+    /// #   Signature, Universal, Variable,
+    /// # };
+    /// let x = Variable::new(b'x'.into());
+    /// let p = |t| -> GenericFormula {
+    ///     PredicateCall { predicate: b'P'.into(), terms: vec![t] }.into()
+    /// };
+    /// let everything: GenericFormula = Universal { left: x, right: p(x.into()) }.into();
+    /// let something: GenericFormula = Existential { left: x, right: p(x.into()) }.into();
+    ///
+    /// let TableauResult::Closed(proof) = TableauProver::default().prove(&[everything], &something)
+    /// else {
+    ///     panic!()
+    /// };
+    ///
+    /// let tstp = proof.to_tstp(&Signature::default());
+    /// assert!(tstp.starts_with("fof(t0, axiom, (! [X120] : p80(X120)))."));
+    /// assert!(tstp.contains("inference(tableau_close, [status(thm)]"));
+    /// ```
+    pub fn to_tstp(&self, signature: &Signature) -> String {
+        let mut symbols = Signature::default();
+        self.formulas
+            .iter()
+            .for_each(|f| symbols.extend_from_formula(f));
+        let default = Constant {
+            label: symbols.constants.keys().next().copied().unwrap_or_default(),
+        }
+        .into();
+
+        let skolem = self.steps.iter().filter_map(|step| match step {
+            TableauStep::Delta {
+                witness: GenericTerm::FunctionCall(f),
+                ..
+            } => Some(f.function),
+            _ => None,
+        });
+        let mut export = Export {
+            names: Names::new(signature, skolem),
+            substitution: &self.substitution,
+            default,
+            out: String::new(),
+            lines: 0,
+        };
+
+        let branch = self
+            .formulas
+            .iter()
+            .map(|f| (f.clone(), export.line(Role::Axiom, f, None)))
+            .collect();
+        export.branch(&mut self.steps.iter(), branch);
+        export.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        tableaux::{TableauProver, TableauResult},
+        Constant, Disjunction, Existential, GenericFormula, GenericTerm, Negation, PredicateCall,
+        Signature, Universal, Variable,
+    };
+
+    fn p(predicate: u8, terms: Vec<GenericTerm>) -> GenericFormula {
+        PredicateCall {
+            predicate: predicate.into(),
+            terms,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_branches_and_instances() {
+        let x = Variable::new(b'x'.into());
+        let a: GenericTerm = Constant { label: b'a'.into() }.into();
+
+        // ∀x.(P(x) ∨ Q(x)), ¬P(a), ¬Q(a)
+        let formulas: Vec<GenericFormula> = vec![
+            Universal {
+                left: x,
+                right: Disjunction {
+                    left: p(b'P', vec![x.into()]),
+                    right: p(b'Q', vec![x.into()]),
+                }
+                .into(),
+            }
+            .into(),
+            Negation {
+                right: p(b'P', vec![a.clone()]),
+            }
+            .into(),
+            Negation {
+                right: p(b'Q', vec![a]),
+            }
+            .into(),
+        ];
+        let TableauResult::Closed(proof) = TableauProver::default().refute(&formulas) else {
+            panic!()
+        };
+
+        let mut signature = Signature::default();
+        signature.name_predicate(b'P'.into(), "p");
+        signature.name_predicate(b'Q'.into(), "q");
+        signature.name_constant(b'a'.into(), "a");
+        let tstp = proof.to_tstp(&signature);
+
+        assert_eq!(
+            tstp,
+            [
+                "fof(t0, axiom, (! [X120] : (p(X120) | q(X120)))).",
+                "fof(t1, axiom, ~ p(a)).",
+                "fof(t2, axiom, ~ q(a)).",
+                "fof(t3, plain, (p(a) | q(a)), inference(tableau_gamma, [status(thm)], [t0])).",
+                "fof(t4, plain, p(a), introduced(assumption, [t3])).",
+                "fof(t5, plain, $false, inference(tableau_close, [status(thm)], [t4,t1])).",
+                "fof(t6, plain, q(a), introduced(assumption, [t3])).",
+                "fof(t7, plain, $false, inference(tableau_close, [status(thm)], [t6,t2])).",
+                "fof(t8, plain, $false, inference(tableau_beta, [status(thm), discharge([t4,t6])], [t3,t5,t7])).",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_skolem_witnesses() {
+        let x = Variable::new(b'x'.into());
+        let y = Variable::new(b'y'.into());
+
+        // ∀x.∃y.R(x, y) and ∀x.∀y.¬R(x, y)
+        let formulas: Vec<GenericFormula> = vec![
+            Universal {
+                left: x,
+                right: Existential {
+                    left: y,
+                    right: p(b'R', vec![x.into(), y.into()]),
+                }
+                .into(),
+            }
+            .into(),
+            Universal {
+                left: x,
+                right: Universal {
+                    left: y,
+                    right: Negation {
+                        right: p(b'R', vec![x.into(), y.into()]),
+                    }
+                    .into(),
+                }
+                .into(),
+            }
+            .into(),
+        ];
+        let TableauResult::Closed(proof) = TableauProver::default().refute(&formulas) else {
+            panic!()
+        };
+        let tstp = proof.to_tstp(&Signature::default());
+
+        assert!(tstp.contains("inference(tableau_delta, [status(esa), new_symbols(skolem, [sk"));
+        // The instance of x is left unbound, so is replaced by a constant.
+        assert!(tstp.contains("fof(t2, plain, (? [X121] : p82(c0,X121)), "));
+        assert!(tstp.contains("~ p82(c0,sk"));
+        assert!(tstp.lines().last().unwrap().starts_with(&format!(
+            "fof(t{}, plain, $false, ",
+            tstp.lines().count() - 1
+        )));
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use crate::syntax::{
+    normal_forms::Literal, Equality, GenericAtomicFormula, GenericTerm, Negation, PredicateCall,
+    Variable,
+};
+
+use super::Substitution;
+
+/// A syntax node which can be generalised together with another of the same
+/// type.
+pub trait AntiUnifiable: Sized {
+    /// Generalise `self` and `other`, using `generalise` to generalise each
+    /// pair of corresponding terms. Returns `None` if no node of this type is
+    /// more general than both, for example if they are calls to different
+    /// predicates.
+    fn generalise_with(
+        &self,
+        other: &Self,
+        generalise: &mut dyn FnMut(&GenericTerm, &GenericTerm) -> GenericTerm,
+    ) -> Option<Self>;
+}
+
+impl AntiUnifiable for GenericTerm {
+    fn generalise_with(
+        &self,
+        other: &Self,
+        generalise: &mut dyn FnMut(&GenericTerm, &GenericTerm) -> GenericTerm,
+    ) -> Option<Self> {
+        Some(generalise(self, other))
+    }
+}
+
+impl AntiUnifiable for PredicateCall<GenericTerm> {
+    fn generalise_with(
+        &self,
+        other: &Self,
+        generalise: &mut dyn FnMut(&GenericTerm, &GenericTerm) -> GenericTerm,
+    ) -> Option<Self> {
+        if self.predicate != other.predicate || self.terms.len() != other.terms.len() {
+            return None;
+        }
+        Some(Self {
+            predicate: self.predicate,
+            terms: self
+                .terms
+                .iter()
+                .zip(other.terms.iter())
+                .map(|(s, t)| generalise(s, t))
+                .collect(),
+        })
+    }
+}
+
+impl AntiUnifiable for GenericAtomicFormula {
+    fn generalise_with(
+        &self,
+        other: &Self,
+        generalise: &mut dyn FnMut(&GenericTerm, &GenericTerm) -> GenericTerm,
+    ) -> Option<Self> {
+        match (self, other) {
+            (Self::Equality(x), Self::Equality(y)) => Some(
+                Equality {
+                    left: generalise(&x.left, &y.left),
+                    right: generalise(&x.right, &y.right),
+                }
+                .into(),
+            ),
+            (Self::Predicate(x), Self::Predicate(y)) => {
+                x.generalise_with(y, generalise).map(Self::Predicate)
+            }
+            (Self::True, Self::True) => Some(Self::True),
+            (Self::False, Self::False) => Some(Self::False),
+            _ => None,
+        }
+    }
+}
+
+/// Literals can only be generalised if they have the same sign.
+impl AntiUnifiable for Literal {
+    fn generalise_with(
+        &self,
+        other: &Self,
+        generalise: &mut dyn FnMut(&GenericTerm, &GenericTerm) -> GenericTerm,
+    ) -> Option<Self> {
+        match (self, other) {
+            (Self::Atom(x), Self::Atom(y)) => x.generalise_with(y, generalise).map(Self::Atom),
+            (Self::Negated(x), Self::Negated(y)) => x
+                .right
+                .generalise_with(&y.right, generalise)
+                .map(|right| Self::Negated(Negation { right })),
+            _ => None,
+        }
+    }
+}
+
+/// The least general generalisation of two nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generalisation<T> {
+    /// The generalisation.
+    pub general: T,
+    /// The substitution taking the generalisation to the left node.
+    pub left: Substitution,
+    /// The substitution taking the generalisation to the right node.
+    pub right: Substitution,
+}
+
+/// Compute the least general generalisation (anti-unifier) of two terms,
+/// atoms or literals.
+///
+/// Each distinct pair of differing sub-terms is replaced by a fresh variable,
+/// with the same variable used every time the same pair occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   unification::anti_unify, Constant, FunctionCall, GenericTerm,
+/// # };
+/// let f = |l: GenericTerm, r: GenericTerm| -> GenericTerm {
+///     FunctionCall { function: b'f'.into(), terms: vec![l, r] }.into()
+/// };
+/// let a: GenericTerm = Constant { label: 1 }.into();
+/// let b: GenericTerm = Constant { label: 2 }.into();
+///
+/// // lgg(f(a, a), f(b, b)) = f(x, x)
+/// let lgg = anti_unify(&f(a.clone(), a.clone()), &f(b.clone(), b.clone())).unwrap();
+/// match &lgg.general {
+///     GenericTerm::FunctionCall(call) => assert_eq!(call.terms[0], call.terms[1]),
+///     _ => panic!(),
+/// }
+/// assert_eq!(lgg.left.apply(&lgg.general), f(a.clone(), a));
+/// assert_eq!(lgg.right.apply(&lgg.general), f(b.clone(), b));
+/// ```
+pub fn anti_unify<T: AntiUnifiable>(left: &T, right: &T) -> Option<Generalisation<T>> {
+    let mut pairs: HashMap<(GenericTerm, GenericTerm), Variable> = HashMap::new();
+    let mut left_substitution = Substitution::new();
+    let mut right_substitution = Substitution::new();
+
+    let general = {
+        let mut generalise = |s: &GenericTerm, t: &GenericTerm| {
+            generalise_terms(
+                s,
+                t,
+                &mut pairs,
+                &mut left_substitution,
+                &mut right_substitution,
+            )
+        };
+        left.generalise_with(right, &mut generalise)?
+    };
+
+    Some(Generalisation {
+        general,
+        left: left_substitution,
+        right: right_substitution,
+    })
+}
+
+fn generalise_terms(
+    s: &GenericTerm,
+    t: &GenericTerm,
+    pairs: &mut HashMap<(GenericTerm, GenericTerm), Variable>,
+    left: &mut Substitution,
+    right: &mut Substitution,
+) -> GenericTerm {
+    if s == t {
+        return s.clone();
+    }
+
+    match (s, t) {
+        (GenericTerm::FunctionCall(f), GenericTerm::FunctionCall(g))
+            if f.function == g.function && f.terms.len() == g.terms.len() =>
+        {
+            let mut call = (**f).clone();
+            call.terms = f
+                .terms
+                .iter()
+                .zip(g.terms.iter())
+                .map(|(s, t)| generalise_terms(s, t, pairs, left, right))
+                .collect();
+            call.into()
+        }
+        _ => {
+            let variable = *pairs.entry((s.clone(), t.clone())).or_insert_with(|| {
+                let v = Variable::rand();
+                left.insert(v, s.clone());
+                right.insert(v, t.clone());
+                v
+            });
+            variable.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::Literal, Constant, FunctionCall, GenericTerm, PredicateCall, Variable,
+    };
+
+    use super::anti_unify;
+
+    fn f(function: u8, terms: Vec<GenericTerm>) -> GenericTerm {
+        FunctionCall {
+            function: function.into(),
+            terms,
+        }
+        .into()
+    }
+
+    fn c(label: u64) -> GenericTerm {
+        Constant { label }.into()
+    }
+
+    fn p(terms: Vec<GenericTerm>) -> Literal {
+        Literal::Atom(
+            PredicateCall {
+                predicate: b'P'.into(),
+                terms,
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn test_anti_unify_terms() {
+        let y: GenericTerm = Variable::new(b'y'.into()).into();
+
+        // lgg(f(a, g(b)), f(c, g(y))) = f(x1, g(x2))
+        let left = f(b'f', vec![c(1), f(b'g', vec![c(2)])]);
+        let right = f(b'f', vec![c(3), f(b'g', vec![y])]);
+        let lgg = anti_unify(&left, &right).unwrap();
+
+        assert_eq!(lgg.left.apply(&lgg.general), left);
+        assert_eq!(lgg.right.apply(&lgg.general), right);
+        assert_eq!(lgg.left.len(), 2);
+        match &lgg.general {
+            GenericTerm::FunctionCall(call) => {
+                assert!(matches!(call.terms[0], GenericTerm::Variable(_)));
+                assert!(matches!(call.terms[1], GenericTerm::FunctionCall(_)));
+            }
+            _ => panic!(),
+        }
+
+        // Identical terms generalise to themselves.
+        let lgg = anti_unify(&left, &left).unwrap();
+        assert_eq!(lgg.general, left);
+        assert!(lgg.left.is_empty());
+
+        // Different functions generalise to a variable.
+        let lgg = anti_unify(&f(b'f', vec![c(1)]), &f(b'g', vec![c(1)])).unwrap();
+        assert!(matches!(lgg.general, GenericTerm::Variable(_)));
+    }
+
+    #[test]
+    fn test_anti_unify_literals() {
+        let left = p(vec![c(1), c(1)]);
+        let right = p(vec![c(2), c(2)]);
+        let lgg = anti_unify(&left, &right).unwrap();
+        assert_eq!(lgg.left.apply(&lgg.general), left);
+        assert_eq!(lgg.right.apply(&lgg.general), right);
+        assert_eq!(lgg.left.len(), 1);
+
+        let lgg = anti_unify(&left.negate(), &right.negate()).unwrap();
+        assert!(lgg.general.is_negated());
+
+        assert!(anti_unify(&left, &right.negate()).is_none());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::syntax::{GenericTerm, Variable};
+
+use super::{Substitution, Unifiable};
+
+/// Match a pattern against a target, binding only the pattern's variables.
+///
+/// Returns a substitution `σ` such that applying `σ` to the pattern gives the
+/// target exactly. Variables in the target are treated as constants, so
+/// `P(x)` matches `P(f(y))`, but `P(f(y))` does not match `P(x)`.
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   unification::match_term, Constant, FunctionCall, GenericTerm, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let y = Variable::new(b'y'.into());
+/// let f = |t: GenericTerm| -> GenericTerm {
+///     FunctionCall { function: b'f'.into(), terms: vec![t] }.into()
+/// };
+///
+/// let pattern = f(x.into());
+/// let target = f(f(y.into()));
+///
+/// let matcher = match_term(&pattern, &target).unwrap();
+/// assert_eq!(matcher.apply(&pattern), target);
+/// assert!(match_term(&target, &pattern).is_none());
+/// ```
+pub fn match_term<T: Unifiable + ?Sized>(pattern: &T, target: &T) -> Option<Substitution> {
+    match_extending(pattern, target, &Substitution::new())
+}
+
+/// Match a pattern against a target, extending an existing matcher.
+///
+/// Useful when several patterns must be matched with a single substitution,
+/// such as when checking whether one clause subsumes another.
+pub fn match_extending<T: Unifiable + ?Sized>(
+    pattern: &T,
+    target: &T,
+    substitution: &Substitution,
+) -> Option<Substitution> {
+    let mut equations = Vec::new();
+    if !pattern.equations(target, &mut equations) {
+        return None;
+    }
+
+    let mut substitution = substitution.clone();
+    while let Some((p, t)) = equations.pop() {
+        match (p, t) {
+            (GenericTerm::Variable(v), t) => match substitution.get(v) {
+                Some(bound) if bound == t => {}
+                Some(_) => return None,
+                None => {
+                    substitution.insert(*v, t.clone());
+                }
+            },
+            (GenericTerm::Constant(a), GenericTerm::Constant(b)) if a == b => {}
+            (GenericTerm::FunctionCall(f), GenericTerm::FunctionCall(g))
+                if f.function == g.function && f.terms.len() == g.terms.len() =>
+            {
+                equations.extend(f.terms.iter().zip(g.terms.iter()));
+            }
+            _ => return None,
+        }
+    }
+    Some(substitution)
+}
+
+/// Whether `general` is at least as general as `specific`: some instance of
+/// it is `specific`.
+pub fn is_instance<T: Unifiable + ?Sized>(specific: &T, general: &T) -> bool {
+    match_term(general, specific).is_some()
+}
+
+/// Whether two nodes are equal up to a renaming of their variables.
+pub fn is_variant<T: Unifiable + ?Sized>(left: &T, right: &T) -> bool {
+    let is_renaming = |s: Substitution| {
+        let mut images: Vec<Variable> = Vec::with_capacity(s.len());
+        for (_, t) in s.iter() {
+            match t {
+                GenericTerm::Variable(v) => images.push(*v),
+                _ => return false,
+            }
+        }
+        images.sort_by_key(|v| v.label);
+        images.windows(2).all(|w| w[0] != w[1])
+    };
+    match_term(left, right).is_some_and(is_renaming)
+        && match_term(right, left).is_some_and(is_renaming)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::Literal, Constant, FunctionCall, GenericTerm, PredicateCall, Variable,
+    };
+
+    use super::{is_instance, is_variant, match_extending, match_term};
+
+    fn var(c: u8) -> GenericTerm {
+        Variable::new(c.into()).into()
+    }
+
+    fn f(terms: Vec<GenericTerm>) -> GenericTerm {
+        FunctionCall {
+            function: b'f'.into(),
+            terms,
+        }
+        .into()
+    }
+
+    fn p(terms: Vec<GenericTerm>) -> Literal {
+        Literal::Atom(
+            PredicateCall {
+                predicate: b'P'.into(),
+                terms,
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn test_match() {
+        let a: GenericTerm = Constant { label: 1 }.into();
+
+        // P(x, x) matches P(a, a) but not P(a, y)
+        let pattern = p(vec![var(b'x'), var(b'x')]);
+        assert!(match_term(&pattern, &p(vec![a.clone(), a.clone()])).is_some());
+        assert!(match_term(&pattern, &p(vec![a.clone(), var(b'y')])).is_none());
+
+        // Target variables are not bound: P(x, a) does not match P(y, x).
+        assert!(match_term(
+            &p(vec![var(b'x'), a.clone()]),
+            &p(vec![var(b'y'), var(b'x')])
+        )
+        .is_none());
+
+        // Pattern variables may occur in the target.
+        let matcher = match_term(&var(b'x'), &f(vec![var(b'x')])).unwrap();
+        assert_eq!(matcher.apply(&var(b'x')), f(vec![var(b'x')]));
+
+        // Signs must agree
+        assert!(match_term(&pattern, &p(vec![a.clone(), a.clone()]).negate()).is_none());
+
+        // Extending an existing matcher
+        let matcher = match_term(&var(b'x'), &a).unwrap();
+        assert!(match_extending(&f(vec![var(b'x')]), &f(vec![var(b'y')]), &matcher).is_none());
+        assert!(match_extending(&f(vec![var(b'x')]), &f(vec![a.clone()]), &matcher).is_some());
+    }
+
+    #[test]
+    fn test_instances_and_variants() {
+        let a: GenericTerm = Constant { label: 1 }.into();
+
+        assert!(is_instance(&f(vec![a.clone()]), &f(vec![var(b'x')])));
+        assert!(!is_instance(&f(vec![var(b'x')]), &f(vec![a])));
+
+        assert!(is_variant(
+            &p(vec![var(b'x'), var(b'y')]),
+            &p(vec![var(b'y'), var(b'z')])
+        ));
+        assert!(!is_variant(
+            &p(vec![var(b'x'), var(b'x')]),
+            &p(vec![var(b'y'), var(b'z')])
+        ));
+        assert!(!is_variant(
+            &p(vec![var(b'x'), var(b'y')]),
+            &p(vec![var(b'z'), var(b'z')])
+        ));
+    }
+}
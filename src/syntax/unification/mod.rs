@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+
+use super::{normal_forms::Literal, GenericAtomicFormula, GenericTerm, PredicateCall, Variable};
+
+mod anti_unification;
+mod matching;
+mod substitution;
+
+pub use anti_unification::{anti_unify, AntiUnifiable, Generalisation};
+pub use matching::{is_instance, is_variant, match_extending, match_term};
+pub use substitution::{Substitute, Substitution};
+
+////////////////////////////////////////////////////////////////////////////////
+// Unifiable
+////////////////////////////////////////////////////////////////////////////////
+
+/// A syntax node which can be unified with another of the same type.
+pub trait Unifiable {
+    /// Decompose the problem of unifying `self` with `other` into pairs of
+    /// terms which must be unified. Returns `false` if the nodes can never be
+    /// unified, for example if they are calls to different predicates.
+    fn equations<'a>(
+        &'a self,
+        other: &'a Self,
+        out: &mut Vec<(&'a GenericTerm, &'a GenericTerm)>,
+    ) -> bool;
+}
+
+impl Unifiable for GenericTerm {
+    fn equations<'a>(
+        &'a self,
+        other: &'a Self,
+        out: &mut Vec<(&'a GenericTerm, &'a GenericTerm)>,
+    ) -> bool {
+        out.push((self, other));
+        true
+    }
+}
+
+impl Unifiable for PredicateCall<GenericTerm> {
+    fn equations<'a>(
+        &'a self,
+        other: &'a Self,
+        out: &mut Vec<(&'a GenericTerm, &'a GenericTerm)>,
+    ) -> bool {
+        if self.predicate != other.predicate || self.terms.len() != other.terms.len() {
+            return false;
+        }
+        out.extend(self.terms.iter().zip(other.terms.iter()));
+        true
+    }
+}
+
+impl Unifiable for GenericAtomicFormula {
+    fn equations<'a>(
+        &'a self,
+        other: &'a Self,
+        out: &mut Vec<(&'a GenericTerm, &'a GenericTerm)>,
+    ) -> bool {
+        match (self, other) {
+            (Self::Equality(x), Self::Equality(y)) => {
+                out.push((&x.left, &y.left));
+                out.push((&x.right, &y.right));
+                true
+            }
+            (Self::Predicate(x), Self::Predicate(y)) => x.equations(y, out),
+            (Self::True, Self::True) | (Self::False, Self::False) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Literals only unify if they have the same sign.
+impl Unifiable for Literal {
+    fn equations<'a>(
+        &'a self,
+        other: &'a Self,
+        out: &mut Vec<(&'a GenericTerm, &'a GenericTerm)>,
+    ) -> bool {
+        self.is_negated() == other.is_negated() && self.atom().equations(other.atom(), out)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Unification
+////////////////////////////////////////////////////////////////////////////////
+
+/// Compute the most general unifier of two terms, atoms or literals.
+///
+/// Returns `None` if the two cannot be unified, including when unification
+/// would bind a variable to a term containing itself (the occurs check).
+///
+/// # Examples
+///
+/// ```
+/// # use first_order_logic::syntax::{
+/// #   unification::unify, Constant, FunctionCall, GenericTerm, Variable,
+/// # };
+/// let x = Variable::new(b'x'.into());
+/// let a: GenericTerm = Constant { label: 1 }.into();
+/// let f = |t: GenericTerm| -> GenericTerm {
+///     FunctionCall { function: b'f'.into(), terms: vec![t] }.into()
+/// };
+///
+/// // f(x) = f(a)
+/// let unifier = unify(&f(x.into()), &f(a.clone())).unwrap();
+/// assert_eq!(unifier.get(&x), Some(&a));
+///
+/// // x = f(x) fails the occurs check
+/// assert!(unify(&x.into(), &f(x.into())).is_none());
+/// ```
+pub fn unify<T: Unifiable + ?Sized>(left: &T, right: &T) -> Option<Substitution> {
+    unify_all(std::iter::once((left, right)))
+}
+
+/// Compute the most general substitution which simultaneously unifies every
+/// pair.
+pub fn unify_all<'a, T: Unifiable + ?Sized + 'a>(
+    pairs: impl IntoIterator<Item = (&'a T, &'a T)>,
+) -> Option<Substitution> {
+    let mut equations = Vec::new();
+    for (left, right) in pairs {
+        if !left.equations(right, &mut equations) {
+            return None;
+        }
+    }
+
+    let mut unifier = Unifier::default();
+    if unifier.solve(equations) && unifier.acyclic() {
+        Some(unifier.into_substitution())
+    } else {
+        None
+    }
+}
+
+/// A variable, after following its bindings, is either an unbound variable
+/// or a non-variable term. Terms remember the root they were bound to, if any.
+enum Resolved<'a> {
+    Variable(Variable),
+    Term(&'a GenericTerm, Option<Variable>),
+}
+
+/// Unification over a union-find of variables.
+///
+/// Each class of variables which have been unified with each other has a
+/// representative (root), and each root may be bound to a single non-variable
+/// term. The occurs check is deferred until all of the equations are solved,
+/// and performed as a single cycle search, which keeps unification close to
+/// linear in the size of the input.
+#[derive(Default)]
+struct Unifier<'a> {
+    parents: HashMap<Variable, Variable>,
+    terms: HashMap<Variable, &'a GenericTerm>,
+}
+
+impl<'a> Unifier<'a> {
+    fn find(&mut self, variable: Variable) -> Variable {
+        let parent = *self.parents.entry(variable).or_insert(variable);
+        if parent == variable {
+            return variable;
+        }
+        let root = self.find(parent);
+        self.parents.insert(variable, root);
+        root
+    }
+
+    fn resolve(&mut self, term: &'a GenericTerm) -> Resolved<'a> {
+        match term {
+            GenericTerm::Variable(v) => {
+                let root = self.find(*v);
+                match self.terms.get(&root) {
+                    Some(t) => Resolved::Term(t, Some(root)),
+                    None => Resolved::Variable(root),
+                }
+            }
+            t => Resolved::Term(t, None),
+        }
+    }
+
+    fn solve(&mut self, mut equations: Vec<(&'a GenericTerm, &'a GenericTerm)>) -> bool {
+        while let Some((left, right)) = equations.pop() {
+            match (self.resolve(left), self.resolve(right)) {
+                (Resolved::Variable(x), Resolved::Variable(y)) => {
+                    if x != y {
+                        self.parents.insert(x, y);
+                    }
+                }
+                (Resolved::Variable(x), Resolved::Term(t, _))
+                | (Resolved::Term(t, _), Resolved::Variable(x)) => {
+                    self.terms.insert(x, t);
+                }
+                (Resolved::Term(s, x), Resolved::Term(t, y)) => {
+                    if std::ptr::eq(s, t) {
+                        continue;
+                    }
+                    // Merge the classes of two bound variables before
+                    // decomposing their terms, so that the same pair is never
+                    // decomposed twice (which would not terminate on cyclic
+                    // bindings, before the occurs check rejects them).
+                    if let (Some(x), Some(y)) = (x, y) {
+                        if x == y {
+                            continue;
+                        }
+                        self.parents.insert(x, y);
+                    }
+                    match (s, t) {
+                        (GenericTerm::Constant(a), GenericTerm::Constant(b)) if a == b => {}
+                        (GenericTerm::FunctionCall(f), GenericTerm::FunctionCall(g))
+                            if f.function == g.function && f.terms.len() == g.terms.len() =>
+                        {
+                            equations.extend(f.terms.iter().zip(g.terms.iter()));
+                        }
+                        _ => return false,
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// The occurs check: no bound variable may be reachable from its own term.
+    fn acyclic(&mut self) -> bool {
+        // false: on the current path, true: finished.
+        let mut visited: HashMap<Variable, bool> = HashMap::new();
+        let roots: Vec<Variable> = self.terms.keys().copied().collect();
+        roots.into_iter().all(|r| self.visit(r, &mut visited))
+    }
+
+    fn visit(&mut self, root: Variable, visited: &mut HashMap<Variable, bool>) -> bool {
+        match visited.get(&root) {
+            Some(true) => return true,
+            Some(false) => return false,
+            None => {}
+        }
+        let term = match self.terms.get(&root) {
+            Some(t) => *t,
+            None => return true,
+        };
+
+        visited.insert(root, false);
+        for v in term.variables() {
+            let r = self.find(v);
+            if !self.visit(r, visited) {
+                return false;
+            }
+        }
+        visited.insert(root, true);
+        true
+    }
+
+    fn into_substitution(mut self) -> Substitution {
+        let variables: Vec<Variable> = self.parents.keys().copied().collect();
+        let mut resolved: HashMap<Variable, GenericTerm> = HashMap::new();
+
+        variables
+            .into_iter()
+            .filter_map(|v| {
+                let term = self.resolve_fully(&GenericTerm::Variable(v), &mut resolved);
+                (term != GenericTerm::Variable(v)).then_some((v, term))
+            })
+            .collect()
+    }
+
+    /// Apply the unifier to a term, memoising the result for each root.
+    fn resolve_fully(
+        &mut self,
+        term: &GenericTerm,
+        resolved: &mut HashMap<Variable, GenericTerm>,
+    ) -> GenericTerm {
+        match term {
+            GenericTerm::Variable(v) => {
+                let root = self.find(*v);
+                if let Some(t) = resolved.get(&root) {
+                    return t.clone();
+                }
+                let t = match self.terms.get(&root) {
+                    Some(t) => self.resolve_fully(t, resolved),
+                    None => GenericTerm::Variable(root),
+                };
+                resolved.insert(root, t.clone());
+                t
+            }
+            GenericTerm::Constant(_) => term.clone(),
+            GenericTerm::FunctionCall(f) => {
+                let mut f = (**f).clone();
+                f.terms = f
+                    .terms
+                    .iter()
+                    .map(|t| self.resolve_fully(t, resolved))
+                    .collect();
+                f.into()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{
+        normal_forms::Literal, Constant, FunctionCall, GenericAtomicFormula, GenericFormula,
+        GenericTerm, PredicateCall, Universal, Variable,
+    };
+
+    use super::{unify, unify_all, Substitute, Substitution};
+
+    fn var(c: u8) -> Variable {
+        Variable::new(c.into())
+    }
+
+    fn f(function: u8, terms: Vec<GenericTerm>) -> GenericTerm {
+        FunctionCall {
+            function: function.into(),
+            terms,
+        }
+        .into()
+    }
+
+    fn c(label: u64) -> GenericTerm {
+        Constant { label }.into()
+    }
+
+    fn p(terms: Vec<GenericTerm>) -> PredicateCall<GenericTerm> {
+        PredicateCall {
+            predicate: b'P'.into(),
+            terms,
+        }
+    }
+
+    #[test]
+    fn test_unify_terms() {
+        let (x, y, z) = (var(b'x'), var(b'y'), var(b'z'));
+
+        // g(x, f(y)) = g(f(z), x)
+        let left = f(b'g', vec![x.into(), f(b'f', vec![y.into()])]);
+        let right = f(b'g', vec![f(b'f', vec![z.into()]), x.into()]);
+        let unifier = unify(&left, &right).unwrap();
+
+        assert_eq!(unifier.apply(&left), unifier.apply(&right));
+        assert!(unifier.get(&x).is_some());
+        // Most general: y and z are identified, not bound to anything else.
+        assert!(matches!(
+            unifier.apply(&GenericTerm::from(y)),
+            GenericTerm::Variable(_)
+        ));
+        assert_eq!(
+            unifier.apply(&GenericTerm::from(y)),
+            unifier.apply(&GenericTerm::from(z))
+        );
+
+        // Idempotent
+        for (_, t) in unifier.iter() {
+            assert_eq!(&unifier.apply(t), t);
+        }
+    }
+
+    #[test]
+    fn test_unify_failures() {
+        let (x, y) = (var(b'x'), var(b'y'));
+
+        assert!(unify(&c(1), &c(2)).is_none());
+        assert!(unify(&f(b'f', vec![x.into()]), &f(b'g', vec![x.into()])).is_none());
+        assert!(unify(&f(b'f', vec![x.into()]), &f(b'f', vec![x.into(), y.into()])).is_none());
+
+        // Indirect occurs check: x = f(y), y = g(x)
+        assert!(unify_all([
+            (&GenericTerm::from(x), &f(b'f', vec![y.into()])),
+            (&GenericTerm::from(y), &f(b'g', vec![x.into()])),
+        ])
+        .is_none());
+
+        // Cyclic bindings which are then unified: x = f(x), y = f(y), x = y
+        assert!(unify_all([
+            (&GenericTerm::from(x), &f(b'f', vec![x.into()])),
+            (&GenericTerm::from(y), &f(b'f', vec![y.into()])),
+            (&GenericTerm::from(x), &GenericTerm::from(y)),
+        ])
+        .is_none());
+    }
+
+    #[test]
+    fn test_unify_atoms_and_literals() {
+        let (x, y) = (var(b'x'), var(b'y'));
+
+        let left = p(vec![x.into(), c(1)]);
+        let right = p(vec![c(2), y.into()]);
+        let unifier = unify(&left, &right).unwrap();
+        assert_eq!(unifier.get(&x), Some(&c(2)));
+        assert_eq!(unifier.get(&y), Some(&c(1)));
+
+        let atom: GenericAtomicFormula = left.clone().into();
+        let other: GenericAtomicFormula = PredicateCall {
+            predicate: b'Q'.into(),
+            terms: right.terms.clone(),
+        }
+        .into();
+        assert!(unify(&atom, &other).is_none());
+
+        let positive = Literal::Atom(left.into());
+        let negative = Literal::Atom(right.into()).negate();
+        assert!(unify(&positive, &negative).is_none());
+        assert!(unify(&positive, &negative.negate()).is_some());
+    }
+
+    #[test]
+    fn test_substitution_into_formula_avoids_capture() {
+        let (x, y) = (var(b'x'), var(b'y'));
+
+        // ∀y.P(x, y) with {x ↦ y}
+        let formula: GenericFormula = Universal {
+            left: y,
+            right: p(vec![x.into(), y.into()]).into(),
+        }
+        .into();
+        let substitution: Substitution = [(x, GenericTerm::from(y))].into_iter().collect();
+
+        match formula.substitute(&substitution) {
+            GenericFormula::Universal(u) => {
+                assert_ne!(u.left, y);
+                assert_eq!(
+                    u.right,
+                    GenericFormula::from(p(vec![y.into(), u.left.into()]))
+                );
+            }
+            _ => panic!(),
+        }
+    }
+}
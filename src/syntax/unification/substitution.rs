@@ -0,0 +1,281 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::syntax::{
+    normal_forms::{Clause, Literal},
+    Conjunction, Disjunction, Equality, Existential, FunctionCall, GenericAtomicFormula,
+    GenericFormula, GenericTerm, Implication, Negation, PredicateCall, Universal, Variable,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+// Substitution
+////////////////////////////////////////////////////////////////////////////////
+
+/// A mapping from variables to terms.
+///
+/// Substitutions returned by this module are idempotent: no variable which is
+/// bound appears in any of the terms it is bound to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Substitution {
+    bindings: HashMap<Variable, GenericTerm>,
+}
+
+impl Substitution {
+    /// Create an empty substitution.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the term a variable is bound to.
+    pub fn get(&self, variable: &Variable) -> Option<&GenericTerm> {
+        self.bindings.get(variable)
+    }
+
+    /// Bind a variable to a term, returning any previous binding.
+    pub fn insert(&mut self, variable: Variable, term: GenericTerm) -> Option<GenericTerm> {
+        self.bindings.insert(variable, term)
+    }
+
+    /// Remove the binding for a variable.
+    pub fn remove(&mut self, variable: &Variable) -> Option<GenericTerm> {
+        self.bindings.remove(variable)
+    }
+
+    /// The number of bound variables.
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Whether no variables are bound.
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Iterate over the bindings of the substitution.
+    pub fn iter(&self) -> impl Iterator<Item = (&Variable, &GenericTerm)> {
+        self.bindings.iter()
+    }
+
+    /// Apply the substitution to a term, formula or clause.
+    pub fn apply<T: Substitute>(&self, t: &T) -> T {
+        t.substitute(self)
+    }
+
+    /// Compose two substitutions, so that applying the result is the same as
+    /// applying `self` and then `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use first_order_logic::syntax::{
+    /// #   unification::Substitution, Constant, FunctionCall, GenericTerm, Variable,
+    /// # };
+    /// let x = Variable::new(b'x'.into());
+    /// let y = Variable::new(b'y'.into());
+    /// let a: GenericTerm = Constant { label: 1 }.into();
+    ///
+    /// // {x ↦ f(y)} then {y ↦ a}
+    /// let mut first = Substitution::new();
+    /// first.insert(x, FunctionCall { function: b'f'.into(), terms: vec![y.into()] }.into());
+    /// let mut second = Substitution::new();
+    /// second.insert(y, a.clone());
+    ///
+    /// let composed = first.compose(&second);
+    /// assert_eq!(
+    ///     composed.get(&x),
+    ///     Some(&FunctionCall { function: b'f'.into(), terms: vec![a.clone()] }.into()),
+    /// );
+    /// assert_eq!(composed.get(&y), Some(&a));
+    /// ```
+    pub fn compose(&self, other: &Substitution) -> Substitution {
+        let mut bindings: HashMap<Variable, GenericTerm> = self
+            .bindings
+            .iter()
+            .map(|(v, t)| (*v, t.substitute(other)))
+            .filter(|(v, t)| *t != GenericTerm::Variable(*v))
+            .collect();
+
+        for (v, t) in &other.bindings {
+            bindings.entry(*v).or_insert_with(|| t.clone());
+        }
+
+        Self { bindings }
+    }
+}
+
+impl FromIterator<(Variable, GenericTerm)> for Substitution {
+    fn from_iter<I: IntoIterator<Item = (Variable, GenericTerm)>>(iter: I) -> Self {
+        Self {
+            bindings: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Display for Substitution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("{")?;
+        let mut first = true;
+        for (v, t) in &self.bindings {
+            if !first {
+                f.write_str(", ")?;
+            }
+            first = false;
+            v.fmt(f)?;
+            f.write_str("↦")?;
+            t.fmt(f)?;
+        }
+        f.write_str("}")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Substitute
+////////////////////////////////////////////////////////////////////////////////
+
+/// A trait for applying a [Substitution] to the variables of a syntax node.
+///
+/// Substitution into a [GenericFormula] only replaces free variables, and
+/// renames bound variables where they would otherwise capture a variable of a
+/// substituted term.
+pub trait Substitute {
+    /// Apply the substitution, returning the new node.
+    fn substitute(&self, substitution: &Substitution) -> Self;
+}
+
+impl Substitute for GenericTerm {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        match self {
+            Self::Variable(v) => substitution.get(v).cloned().unwrap_or(Self::Variable(*v)),
+            Self::Constant(_) => self.clone(),
+            Self::FunctionCall(f) => f.substitute(substitution).into(),
+        }
+    }
+}
+
+impl<T: Substitute> Substitute for FunctionCall<T> {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        Self {
+            function: self.function,
+            terms: self
+                .terms
+                .iter()
+                .map(|t| t.substitute(substitution))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Substitute> Substitute for PredicateCall<T> {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        Self {
+            predicate: self.predicate,
+            terms: self
+                .terms
+                .iter()
+                .map(|t| t.substitute(substitution))
+                .collect(),
+        }
+    }
+}
+
+impl<L: Substitute, R: Substitute> Substitute for Equality<L, R> {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        Self {
+            left: self.left.substitute(substitution),
+            right: self.right.substitute(substitution),
+        }
+    }
+}
+
+impl Substitute for GenericAtomicFormula {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        match self {
+            Self::Equality(x) => Self::Equality(x.substitute(substitution)),
+            Self::Predicate(x) => Self::Predicate(x.substitute(substitution)),
+            Self::True | Self::False => self.clone(),
+        }
+    }
+}
+
+impl<F: Substitute> Substitute for Negation<F> {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        Self {
+            right: self.right.substitute(substitution),
+        }
+    }
+}
+
+impl Substitute for Literal {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        match self {
+            Self::Atom(x) => Self::Atom(x.substitute(substitution)),
+            Self::Negated(x) => Self::Negated(x.substitute(substitution)),
+        }
+    }
+}
+
+impl Substitute for Clause {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        Self {
+            literals: self
+                .literals
+                .iter()
+                .map(|l| l.substitute(substitution))
+                .collect(),
+        }
+    }
+}
+
+/// Substitute into the body of a quantifier, returning the (possibly renamed)
+/// bound variable and the new body.
+fn substitute_quantified(
+    variable: Variable,
+    body: &GenericFormula,
+    substitution: &Substitution,
+) -> (Variable, GenericFormula) {
+    let mut inner = substitution.clone();
+    inner.remove(&variable);
+
+    let captured = inner.iter().any(|(_, t)| t.contains(&variable));
+    if captured {
+        let fresh = Variable::rand();
+        inner.insert(variable, fresh.into());
+        (fresh, body.substitute(&inner))
+    } else {
+        (variable, body.substitute(&inner))
+    }
+}
+
+impl Substitute for GenericFormula {
+    fn substitute(&self, substitution: &Substitution) -> Self {
+        match self {
+            Self::Atomic(x) => Self::Atomic(x.substitute(substitution)),
+            Self::Universal(x) => {
+                let (left, right) = substitute_quantified(x.left, &x.right, substitution);
+                Universal { left, right }.into()
+            }
+            Self::Existential(x) => {
+                let (left, right) = substitute_quantified(x.left, &x.right, substitution);
+                Existential { left, right }.into()
+            }
+            Self::Conjunction(x) => Conjunction {
+                left: x.left.substitute(substitution),
+                right: x.right.substitute(substitution),
+            }
+            .into(),
+            Self::Disjunction(x) => Disjunction {
+                left: x.left.substitute(substitution),
+                right: x.right.substitute(substitution),
+            }
+            .into(),
+            Self::Implication(x) => Implication {
+                left: x.left.substitute(substitution),
+                right: x.right.substitute(substitution),
+            }
+            .into(),
+            Self::Negation(x) => Negation {
+                right: x.right.substitute(substitution),
+            }
+            .into(),
+        }
+    }
+}